@@ -0,0 +1,137 @@
+// src/bench.rs
+use crate::experiment_grouping::{create_version_data_list, find_similar_groups, group_versions};
+use crate::file_utils::{find_hparams_files, resolve_scan_dir};
+use crate::models::Config;
+use anyhow::Result;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// 各处理阶段的耗时记录
+#[derive(Debug, Default)]
+pub struct StageTimings {
+    stages: Vec<(&'static str, Duration)>, // 按执行顺序记录的阶段名称和耗时
+}
+
+impl StageTimings {
+    /// 执行一个阶段并记录其耗时
+    pub fn record<T>(&mut self, stage: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.stages.push((stage, start.elapsed()));
+        result
+    }
+
+    /// 已记录的阶段
+    pub fn stages(&self) -> &[(&'static str, Duration)] {
+        &self.stages
+    }
+
+    /// 所有阶段的总耗时
+    pub fn total(&self) -> Duration {
+        self.stages().iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+impl fmt::Display for StageTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name_width = self
+            .stages()
+            .iter()
+            .map(|(stage, _)| stage.len())
+            .max()
+            .unwrap_or(0)
+            .max("total".len());
+        for (stage, duration) in self.stages() {
+            writeln!(
+                f,
+                "{:<name_width$}  {:>10.3} ms",
+                stage,
+                as_millis(*duration)
+            )?;
+        }
+        write!(
+            f,
+            "{:<name_width$}  {:>10.3} ms",
+            "total",
+            as_millis(self.total())
+        )
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// 依次执行扫描、解析、分组和相似组查找，记录每个阶段的耗时
+pub fn run_bench(config: &Config) -> Result<StageTimings> {
+    let mut timings = StageTimings::default();
+
+    let hparams_files = timings.record("find_hparams_files", || {
+        find_hparams_files(
+            &resolve_scan_dir(&config.general).0,
+            &config.general.hparams_file_candidates(),
+            &config.general.ignore_dir_patterns,
+        )
+    })?;
+    let (versions, _group_common_hparams, _load_report) = timings
+        .record("create_version_data_list", || {
+            create_version_data_list(config, &hparams_files)
+        })?;
+    let groups = timings.record("group_versions", || group_versions(config, versions))?;
+    timings.record("find_similar_groups", || {
+        find_similar_groups(&groups, config)
+    });
+
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_run_bench_end_to_end() {
+        let temp_dir = tempdir().unwrap();
+        for (i, lr) in [0.1, 0.01, 0.1].iter().enumerate() {
+            let version_dir = temp_dir.path().join(format!("version_{}", i));
+            fs::create_dir_all(&version_dir).unwrap();
+            fs::write(
+                version_dir.join("hparams.yaml"),
+                format!("model: cnn\nlr: {}\nseed: {}\n", lr, i),
+            )
+            .unwrap();
+        }
+
+        let config = Config::builder()
+            .log_dir(temp_dir.path().to_string_lossy())
+            .build();
+
+        let timings = run_bench(&config).expect("Bench should run end to end");
+        let stages: Vec<&str> = timings.stages().iter().map(|(stage, _)| *stage).collect();
+        assert_eq!(
+            stages,
+            vec![
+                "find_hparams_files",
+                "create_version_data_list",
+                "group_versions",
+                "find_similar_groups",
+            ]
+        );
+        assert_eq!(
+            timings.total(),
+            timings.stages().iter().map(|(_, d)| *d).sum::<Duration>()
+        );
+
+        let report = timings.to_string();
+        assert_eq!(report.lines().count(), 5);
+        assert!(report.lines().last().unwrap().starts_with("total"));
+    }
+
+    #[test]
+    fn test_run_bench_missing_log_dir() {
+        let config = Config::builder().log_dir("/nonexistent/log/dir").build();
+        assert!(run_bench(&config).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::file_utils::{VersionSelector, parse_version_selector};
+use crate::models::ToleranceConfig;
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// 命令行参数
+#[derive(Debug, Parser)]
+#[command(
+    name = "LitExplorer",
+    version,
+    about = "Explore and compare PyTorch Lightning experiment logs"
+)]
+pub struct Cli {
+    /// Read the configuration from this TOML or YAML file instead of
+    /// `lightning_explorer.toml` (or `lightning_explorer.yaml` when only that exists);
+    /// a missing file is created with the default configuration
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<String>,
+
+    /// Compare every experiment group against a reference hparams file,
+    /// print the groups ranked by distance and exit without starting the TUI
+    #[arg(long, value_name = "HPARAMS_YAML")]
+    pub compare_to: Option<PathBuf>,
+
+    /// Print the loaded versions, groups, common hparams and similar-group
+    /// relationships as a single JSON document and exit without starting the TUI
+    #[arg(long)]
+    pub json_dump: bool,
+
+    /// Write a grouping report to stdout in the given format and exit
+    /// without starting the TUI
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub report: Option<ReportFormat>,
+
+    /// Run the scan, parse, grouping and similarity stages, print how long
+    /// each stage took and exit without starting the TUI
+    #[arg(long)]
+    pub bench: bool,
+
+    /// Report which versions have and which lack the given hparams key,
+    /// then exit without starting the TUI
+    #[arg(long, value_name = "KEY")]
+    pub check_key: Option<String>,
+
+    /// Print one aligned row per version with its distinguishing parameters,
+    /// then exit without starting the TUI
+    #[arg(long)]
+    pub table: bool,
+
+    /// Print how many versions exist for each combination of values of the
+    /// two given hparams keys, then exit without starting the TUI
+    #[arg(long, num_args = 2, value_names = ["PARAM_X", "PARAM_Y"])]
+    pub coverage: Option<Vec<String>>,
+
+    /// Only consider the given version numbers and ranges, e.g. `--versions 3,7,10-25`;
+    /// numbers and ranges without any matching version directory are ignored with a warning
+    #[arg(long, value_name = "SELECTOR", value_parser = parse_version_selector)]
+    pub versions: Option<VersionSelector>,
+
+    /// Override `tolerance.float_tolerance` from the config file for this run
+    #[arg(
+        long,
+        value_name = "TOLERANCE",
+        value_parser = parse_float_tolerance,
+        allow_negative_numbers = true
+    )]
+    pub float_tolerance: Option<f64>,
+
+    /// Override `tolerance.int_tolerance` from the config file for this run
+    #[arg(
+        long,
+        value_name = "TOLERANCE",
+        value_parser = clap::value_parser!(i64).range(0..),
+        allow_negative_numbers = true
+    )]
+    pub int_tolerance: Option<i64>,
+
+    /// Override `tolerance.string_case_sensitive` from the config file for this run;
+    /// `--case-sensitive` alone means `true`
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub case_sensitive: Option<bool>,
+
+    /// Print the default configuration that would be created when the config
+    /// file is missing, then exit without reading or writing any file
+    #[arg(long)]
+    pub print_default_config: bool,
+}
+
+/// 分组报告的输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// One JSON object per line, one line per experiment group
+    Jsonl,
+}
+
+impl Cli {
+    /// 是否输出机器可读的结果，此时不打印进度信息也不启动TUI
+    pub fn is_headless(&self) -> bool {
+        self.json_dump
+            || self.report.is_some()
+            || self.bench
+            || self.check_key.is_some()
+            || self.table
+            || self.coverage.is_some()
+    }
+
+    /// 是否通过命令行覆盖了容差配置
+    pub fn overrides_tolerance(&self) -> bool {
+        self.float_tolerance.is_some()
+            || self.int_tolerance.is_some()
+            || self.case_sensitive.is_some()
+    }
+
+    /// 用命令行指定的容差覆盖配置文件中的容差，未指定的项保持不变
+    pub fn apply_tolerance_overrides(&self, tolerance: &mut ToleranceConfig) {
+        if let Some(float_tolerance) = self.float_tolerance {
+            tolerance.float_tolerance = float_tolerance;
+        }
+        if let Some(int_tolerance) = self.int_tolerance {
+            tolerance.int_tolerance = int_tolerance;
+        }
+        if let Some(case_sensitive) = self.case_sensitive {
+            tolerance.string_case_sensitive = case_sensitive;
+        }
+    }
+}
+
+/// 解析浮点数容差，必须是非负的有限值
+fn parse_float_tolerance(value: &str) -> Result<f64, String> {
+    let tolerance: f64 = value.parse().map_err(|e| format!("{}", e))?;
+    if !tolerance.is_finite() || tolerance < 0.0 {
+        return Err(format!(
+            "tolerance must be a non-negative number, got {}",
+            value
+        ));
+    }
+    Ok(tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_tolerance_overrides() {
+        let base = ToleranceConfig {
+            float_tolerance: 1e-6,
+            int_tolerance: 0,
+            string_case_sensitive: false,
+            ..ToleranceConfig::default()
+        };
+
+        // 未指定时保持配置文件中的容差
+        let cli = Cli::parse_from(["LitExplorer"]);
+        assert!(!cli.overrides_tolerance());
+        let mut tolerance = base.clone();
+        cli.apply_tolerance_overrides(&mut tolerance);
+        assert_eq!(tolerance, base);
+
+        // 只覆盖指定的项
+        let cli = Cli::parse_from([
+            "LitExplorer",
+            "--float-tolerance",
+            "0.01",
+            "--case-sensitive",
+        ]);
+        assert!(cli.overrides_tolerance());
+        let mut tolerance = base.clone();
+        cli.apply_tolerance_overrides(&mut tolerance);
+        assert_eq!(tolerance.float_tolerance, 0.01);
+        assert_eq!(tolerance.int_tolerance, 0);
+        assert!(tolerance.string_case_sensitive);
+
+        let cli = Cli::parse_from([
+            "LitExplorer",
+            "--int-tolerance",
+            "2",
+            "--case-sensitive",
+            "false",
+        ]);
+        let mut tolerance = ToleranceConfig {
+            string_case_sensitive: true,
+            ..base.clone()
+        };
+        cli.apply_tolerance_overrides(&mut tolerance);
+        assert_eq!(tolerance.float_tolerance, 1e-6);
+        assert_eq!(tolerance.int_tolerance, 2);
+        assert!(!tolerance.string_case_sensitive);
+
+        // 负数和非数值的容差被拒绝
+        assert!(Cli::try_parse_from(["LitExplorer", "--float-tolerance", "-0.1"]).is_err());
+        assert!(Cli::try_parse_from(["LitExplorer", "--float-tolerance", "inf"]).is_err());
+        assert!(Cli::try_parse_from(["LitExplorer", "--int-tolerance", "-1"]).is_err());
+    }
+}
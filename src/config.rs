@@ -3,10 +3,52 @@ use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
 
+/// 配置文件格式，根据文件扩展名确定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// `.yaml`/`.yml`为YAML，其余均按TOML处理
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                ConfigFormat::Yaml
+            }
+            _ => ConfigFormat::Toml,
+        }
+    }
+}
+
+/// 默认的配置文件路径，不存在时创建
+pub const DEFAULT_CONFIG_PATH: &str = "lightning_explorer.toml";
+
+/// 默认TOML配置文件不存在时读取的YAML配置文件
+const FALLBACK_CONFIG_PATH: &str = "lightning_explorer.yaml";
+
+/// 确定要加载的配置文件路径
+///
+/// 优先使用命令行指定的路径；否则使用`base_dir`下的`lightning_explorer.toml`，
+/// 它不存在而`lightning_explorer.yaml`存在时使用YAML文件。返回的默认路径相对于`base_dir`
+pub fn resolve_config_path(explicit: Option<&str>, base_dir: &Path) -> String {
+    if let Some(path) = explicit {
+        return path.to_string();
+    }
+    if !base_dir.join(DEFAULT_CONFIG_PATH).exists() && base_dir.join(FALLBACK_CONFIG_PATH).exists()
+    {
+        return FALLBACK_CONFIG_PATH.to_string();
+    }
+    DEFAULT_CONFIG_PATH.to_string()
+}
+
 pub fn load_config(config_path: &str) -> Result<Config> {
+    let format = ConfigFormat::from_path(Path::new(config_path));
+
     // 检查配置文件是否存在，如果不存在则创建默认配置
     if !Path::new(config_path).exists() {
-        create_default_config(config_path)?;
+        create_default_config(config_path, format)?;
         println!("Created default config file at {}", config_path);
     }
 
@@ -14,20 +56,80 @@ pub fn load_config(config_path: &str) -> Result<Config> {
     let config_content = fs::read_to_string(config_path)
         .with_context(|| format!("Failed to read config file: {}", config_path))?;
 
-    // 解析TOML配置
-    let config: Config = toml::from_str(&config_content)
-        .with_context(|| format!("Failed to parse config file: {}", config_path))?;
+    // 按扩展名解析TOML或YAML配置
+    let config: Config = match format {
+        ConfigFormat::Toml => toml::from_str(&config_content)
+            .with_context(|| format!("Failed to parse config file: {}", config_path))?,
+        ConfigFormat::Yaml => serde_yaml::from_str(&config_content)
+            .with_context(|| format!("Failed to parse config file: {}", config_path))?,
+    };
+
+    config
+        .validate()
+        .with_context(|| format!("Invalid config file: {}", config_path))?;
 
     Ok(config)
 }
 
-fn create_default_config(config_path: &str) -> Result<()> {
-    let default_config = r#"[general]
+/// 将配置按文件扩展名序列化为TOML或YAML并写回配置文件
+///
+/// 写出的是完整的生效配置，原文件中的注释不会保留
+pub fn save_config(config: &Config, config_path: &str) -> Result<()> {
+    let content = match ConfigFormat::from_path(Path::new(config_path)) {
+        ConfigFormat::Toml => {
+            toml::to_string_pretty(config).context("Failed to serialize config")?
+        }
+        ConfigFormat::Yaml => {
+            serde_yaml::to_string(config).context("Failed to serialize config")?
+        }
+    };
+    fs::write(config_path, content)
+        .with_context(|| format!("Failed to write config file: {}", config_path))
+}
+
+/// 返回默认配置模板（TOML格式），即配置文件不存在时写入的内容
+pub fn default_config_toml() -> &'static str {
+    DEFAULT_CONFIG_TOML
+}
+
+fn create_default_config(config_path: &str, format: ConfigFormat) -> Result<()> {
+    let default_config = match format {
+        ConfigFormat::Toml => DEFAULT_CONFIG_TOML.to_string(),
+        // YAML模板由TOML模板转换而来，保证两种格式的默认设置一致
+        ConfigFormat::Yaml => {
+            let value: toml::Value = toml::from_str(DEFAULT_CONFIG_TOML)?;
+            serde_yaml::to_string(&value)?
+        }
+    };
+
+    fs::write(config_path, default_config)
+        .with_context(|| format!("Failed to create default config file: {}", config_path))?;
+
+    Ok(())
+}
+
+/// 默认配置模板（TOML格式）
+const DEFAULT_CONFIG_TOML: &str = r#"[general]
 log_dir = "lightning_logs"
 hparams_file = "hparams.yaml"
 cache_enabled = true
+# 扫描时跳过的目录名模式，支持*和?通配符，默认跳过隐藏目录
+ignore_dir_patterns = [".*"]
+# 版本标注保存路径，默认保存到日志目录下的tags.json
+# tags_file = "lightning_logs/tags.json"
+# 计算每个超参数文件内容的 SHA-256 并显示在详情面板底部
+compute_content_hash = false
+# 超参数文件的最大字节数，超过时跳过该文件，0表示不限制
+max_hparams_file_bytes = 8388608
+# 版本目录下的备注文件名，存在时在详情面板中显示其内容
+# notes_file = "notes.md"
+# 容忍超参数YAML中的重复键，保留最后一次出现的值并给出警告；关闭时含重复键的文件解析失败
+warn_duplicate_keys = false
+# 只扫描 log_dir 下最近修改的实验子目录（直接包含 version_* 目录的子目录），适用于 log_dir 下有多个实验文件夹的情况
+auto_latest_experiment = false
 
 [ignored_parameters]
+# 写作 "name=re:pattern" 时，只有值匹配正则表达式的参数才被忽略，如 "output_dir=re:^/tmp/"
 parameters = [
     "fold",
     "devices",
@@ -42,9 +144,25 @@ parameters = [
 float_tolerance = 0.001
 int_tolerance = 0
 string_case_sensitive = false
+# 将 yes/no/on/off 字符串解析为布尔值
+coerce_boolean_strings = false
 
 [grouping]
 main_key = ["model_name", "dataset"]
+# 版本缺少 main_key 时的处理方式：error 报错，skip 跳过该版本，ungrouped 放入未分组的实验组
+main_key_missing_policy = "error"
+# 映射列表（如 [{id = 1}, {id = 2}]）作为无序集合比较，false 时按索引展平为 key-0-id
+unordered_map_lists = false
+# 分组策略：hash 依次与各组的基准参数比较；cluster 两两比较并传递性合并容差内相等的版本，较慢但不会拆分容差内相等的版本
+grouping_strategy = "hash"
+# 用于生成实验组可读标签的参数，未配置或参数都缺失时显示组ID
+# label_parameters = ["model", "lr"]
+# 比较参数时是否忽略只存在于部分版本中的参数，true 时只比较双方都有的参数，
+# 使新增了参数的代码版本产生的运行仍能与旧运行分到同一组
+ignore_missing_keys = false
+# 计算实验组 ID 时只使用这些参数，之后新增无关参数时已有实验组的 ID 保持不变；
+# 未配置时使用实际生效的分组参数
+# id_parameters = ["model", "dataset", "lr"]
 group_by_all_parameters = true
 grouping_parameters = [
     "model_type",
@@ -52,20 +170,60 @@ grouping_parameters = [
     "learning_rate",
 ]
 similarity_threshold = 2
+# similarity_threshold 的含义：count 为不同参数的个数，percent 为不同参数占两组参数并集的百分比
+similarity_mode = "count"
+# 每个参数配置至少应运行的次数（如不同随机种子），成员数少于该值的实验组在 TUI 中以橙色标记，
+# 并在汇总中统计数量；0 表示不检查
+min_replication = 0
 
 [diff]
 show_detailed_diff = true
 diff_format = "key: value1 vs value2"
 highlight_diff_keys = true
+show_numeric_delta = false
 
 [tui]
-color_theme = "default"
-colors = { same_experiment = "green", similar_experiment = "yellow", selected = "blue", background = "black", text = "white" }
+# 内置主题：dark、light、solarized
+color_theme = "dark"
+# 单独指定的颜色覆盖主题颜色，可用颜色名称或 #rrggbb 格式的十六进制颜色
+# colors = { selected = "blue", border = "cyan" }
 layout = "list"
 show_help_bar = true
 auto_expand_groups = false
+detail_panel_position = "Bottom"
+refresh_rate_ms = 250
+version_panel_proportion = 70
+status_bar_height = 3
+scroll_indicators = true
+float_display_precision = 6
+trim_trailing_zeros = true
+# 详情面板中参数的排列顺序：insertion 按文件中的顺序，alpha 按名称，by_type 按类型分组（字符串、数字、布尔值、列表）
+detail_sort_mode = "alpha"
+# 版本列表的排列顺序：name 按名称，recently_modified 按版本目录的修改时间从新到旧，
+# metric 按 sort_metric 指定的指标值排列
+version_sort_mode = "name"
+# 指标名（或数值型超参数名），设置后版本列表按该指标从最差到最佳渐变着色，如 "val_acc"，
+# 优化方向由 [metrics] 中的 metric_directions 决定
+# sort_metric = "val_acc"
+# 复制实验组命令行参数时，展平参数名中各层级之间使用的分隔符，如 "." 得到 --optimizer.lr，"_" 得到 --optimizer_lr
+flag_key_separator = "."
+# 是否在详情面板的实验组成员列表中附加各成员取值不同的参数，如 version_3 (lr=0.01)
+show_member_varying_params = true
+# 版本列表每页最多显示的版本数，版本过多时分页显示并在底部提示隐藏的数量，0 表示不限制
+# 分组、导出等仍然使用全部版本
+max_versions_displayed = 0
+# 是否将版本列表中同一实验组的多个版本折叠为一行，如 model=cnn (×5: v3,v7,v8,v9,v12)，
+# 选中折叠行后按确认键展开或重新折叠
+collapse_group_versions = false
+# 版本列表中显示名称的模板，{num} 替换为版本号，{name} 替换为版本目录名，其他 {参数名} 替换为该版本的超参数值，
+# 版本没有该参数时显示 missing，如 "v{num}" 或 "{num}: {model}"；未配置时显示版本目录名
+# version_name_template = "v{num}"
+# TUI 运行时每隔多少毫秒在后台重新扫描日志目录，发现新版本时加入版本列表和实验组，
+# 不影响当前选中的版本和滚动位置，适合训练过程中实时查看；0 表示不重新扫描
+rescan_interval_ms = 0
 
 [keybindings]
+# 按键写作单个字符或 up、down、left、right、enter、space，可加 ctrl+ 或 alt+ 前缀，如 "ctrl+r"、"alt+f"
 up = "up"
 down = "down"
 left = "left"
@@ -75,16 +233,302 @@ confirm = "enter"
 quit = "q"
 help = "h"
 filter = "/"
+switch_view = "v"
+scroll_detail_up = "u"
+scroll_detail_down = "d"
+diff_previous = "p"
+scroll_detail_left = "["
+scroll_detail_right = "]"
+tag = "t"
+toggle_hidden_params = "i"
+# 选中所有可见版本 / 反转可见版本的选中状态（空格键切换单个版本）
+select_all = "a"
+invert_selection = "x"
+# 调整版本面板与详情面板的高度比例
+grow_panel = "+"
+shrink_panel = "-"
+# 将版本列表当前的视图（按当前顺序）导出为CSV和JSON
+export_view = "e"
+# 输入参数名，在本次运行中将其加入或移出忽略参数并重新分组（不写回配置文件）
+toggle_ignore = "I"
+# 将当前生效的配置（包括运行时的调整）写回配置文件，文件中的注释不会保留
+save_config = "S"
+# 将当前实验组的基础参数格式化为命令行参数并复制到剪贴板
+copy_group_flags = "c"
+# 版本列表分页显示时翻到下一页/上一页
+next_page = "n"
+previous_page = "N"
+# 显示配置文件、日志目录、加载统计和容差设置等概要信息
+info = "o"
+# 多选的版本恰好属于两个实验组时，将两组基础参数的差异导出为 group_diff.md 和 group_diff.json
+export_group_diff = "g"
+# 固定当前版本，浏览其他版本时在详情面板左侧并排显示固定版本的详情；再按一次取消固定
+pin_version = "f"
+# 跳转到与当前实验组相似的下一个实验组（选中其代表版本），连续按键在相似组之间循环
+next_similar_group = "s"
+# 切换字符串比较是否区分大小写并立即重新分组，只在本次运行中生效，当前方式显示在版本列表边框右上角
+toggle_case_sensitivity = "C"
 
 [test_script]
 path = "test.py"
 default_args = { filter = "", sort_key = "fold" }
 prompt_for_args = true
 fixed_args = []
+
+[metrics]
+# 版本目录下的指标文件名（CSVLogger写出的metrics.csv），不存在时该版本没有指标
+metrics_file = "metrics.csv"
+# 指标多行记录的默认汇总方式：last 最后一条，max 最大值，min 最小值，mean 平均值
+default_aggregation = "last"
+# 按指标名覆盖汇总方式，如 { val_acc = "max", val_loss = "min" }
+metric_aggregation = {}
+# 记录监控指标名的超参数（如 call_back_monitor: VAL--acc），详情面板显示该指标最佳值所在的epoch
+monitor_parameter = "call_back_monitor"
+# 按指标名配置优化方向（higher 越大越好，lower 越小越好），未配置的指标按名称推断：含 loss、err 等字样的越小越好
+metric_directions = {}
+
+[derived_parameters]
+# 由已有数值参数计算得到的派生参数，在解析后注入超参数，可用于分组和显示
+# 支持 + - * / 、括号和参数引用；参数名含 - 等字符时写作 {trainer-max_epochs}
+# 只能引用超参数文件中的参数，引用的参数缺失或不是数值时该版本没有此派生参数
+# 与超参数文件中已有的参数同名时保留文件中的值，并给出一次警告
+# parameters = { effective_batch_size = "batch_size * num_gpus" }
+parameters = {}
 "#;
 
-    fs::write(config_path, default_config)
-        .with_context(|| format!("Failed to create default config file: {}", config_path))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
 
-    Ok(())
+    const EQUIVALENT_TOML: &str = r#"
+[general]
+log_dir = "logs"
+hparams_file = "hparams.yaml"
+cache_enabled = false
+
+[ignored_parameters]
+parameters = ["seed", "fold"]
+
+[tolerance]
+float_tolerance = 0.01
+int_tolerance = 1
+string_case_sensitive = true
+per_parameter_tolerance = { lr = 0.1 }
+
+[grouping]
+group_by_all_parameters = false
+grouping_parameters = ["model", "lr"]
+similarity_threshold = 3
+main_key = ["dataset"]
+
+[diff]
+show_detailed_diff = true
+diff_format = "key: value1 vs value2"
+highlight_diff_keys = false
+
+[tui]
+color_theme = "dark"
+colors = { same_experiment = "green", similar_experiment = "yellow", selected = "blue", background = "black", text = "white", border = "cyan", highlight = "white", status_bar_bg = "dark_gray", status_bar_text = "white" }
+layout = "list"
+show_help_bar = true
+auto_expand_groups = false
+detail_panel_position = "Left"
+refresh_rate_ms = 100
+version_panel_proportion = 60
+status_bar_height = 2
+scroll_indicators = false
+pinned_parameters = ["model"]
+
+[keybindings]
+up = "k"
+down = "j"
+left = "h"
+right = "l"
+select = "space"
+confirm = "enter"
+quit = "q"
+help = "?"
+filter = "/"
+switch_view = "v"
+scroll_detail_up = "u"
+scroll_detail_down = "d"
+
+[test_script]
+path = "test.py"
+default_args = { filter = "", sort_key = "fold" }
+prompt_for_args = false
+fixed_args = ["--verbose"]
+"#;
+
+    const EQUIVALENT_YAML: &str = r#"
+general:
+  log_dir: logs
+  hparams_file: hparams.yaml
+  cache_enabled: false
+ignored_parameters:
+  parameters: [seed, fold]
+tolerance:
+  float_tolerance: 0.01
+  int_tolerance: 1
+  string_case_sensitive: true
+  per_parameter_tolerance:
+    lr: 0.1
+grouping:
+  group_by_all_parameters: false
+  grouping_parameters: [model, lr]
+  similarity_threshold: 3
+  main_key: [dataset]
+diff:
+  show_detailed_diff: true
+  diff_format: "key: value1 vs value2"
+  highlight_diff_keys: false
+tui:
+  color_theme: dark
+  colors:
+    same_experiment: green
+    similar_experiment: yellow
+    selected: blue
+    background: black
+    text: white
+    border: cyan
+    highlight: white
+    status_bar_bg: dark_gray
+    status_bar_text: white
+  layout: list
+  show_help_bar: true
+  auto_expand_groups: false
+  detail_panel_position: Left
+  refresh_rate_ms: 100
+  version_panel_proportion: 60
+  status_bar_height: 2
+  scroll_indicators: false
+  pinned_parameters: [model]
+keybindings:
+  up: k
+  down: j
+  left: h
+  right: l
+  select: space
+  confirm: enter
+  quit: q
+  help: "?"
+  filter: /
+  switch_view: v
+  scroll_detail_up: u
+  scroll_detail_down: d
+test_script:
+  path: test.py
+  default_args:
+    filter: ""
+    sort_key: fold
+  prompt_for_args: false
+  fixed_args: [--verbose]
+"#;
+
+    #[test]
+    fn test_config_format_from_path() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("a.toml")),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("a.yaml")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("a.YML")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("config")),
+            ConfigFormat::Toml
+        );
+    }
+
+    #[test]
+    fn test_resolve_config_path() {
+        let temp_dir = tempdir().unwrap();
+        let base_dir = temp_dir.path();
+
+        // 命令行指定的路径优先，即使文件不存在
+        assert_eq!(
+            resolve_config_path(Some("configs/run.yaml"), base_dir),
+            "configs/run.yaml"
+        );
+        assert_eq!(resolve_config_path(None, base_dir), DEFAULT_CONFIG_PATH);
+
+        // 只有YAML配置文件时使用它
+        fs::write(base_dir.join(FALLBACK_CONFIG_PATH), "").unwrap();
+        assert_eq!(resolve_config_path(None, base_dir), FALLBACK_CONFIG_PATH);
+
+        // 两者都存在时使用TOML配置文件
+        fs::write(base_dir.join(DEFAULT_CONFIG_PATH), "").unwrap();
+        assert_eq!(resolve_config_path(None, base_dir), DEFAULT_CONFIG_PATH);
+    }
+
+    #[test]
+    fn test_toml_and_yaml_configs_equivalent() {
+        let temp_dir = tempdir().unwrap();
+        let toml_path = temp_dir.path().join("config.toml");
+        let yaml_path = temp_dir.path().join("config.yaml");
+        fs::write(&toml_path, EQUIVALENT_TOML).unwrap();
+        fs::write(&yaml_path, EQUIVALENT_YAML).unwrap();
+
+        let from_toml = load_config(toml_path.to_str().unwrap()).unwrap();
+        let from_yaml = load_config(yaml_path.to_str().unwrap()).unwrap();
+
+        assert_eq!(from_toml, from_yaml);
+        assert_eq!(from_yaml.grouping.similarity_threshold, 3);
+        assert_eq!(from_yaml.tolerance.float_tolerance_for("lr"), 0.1);
+    }
+
+    #[test]
+    fn test_create_default_config_matches_extension() {
+        let temp_dir = tempdir().unwrap();
+        let toml_path = temp_dir.path().join("default.toml");
+        let yaml_path = temp_dir.path().join("default.yml");
+
+        let from_toml = load_config(toml_path.to_str().unwrap()).unwrap();
+        let from_yaml = load_config(yaml_path.to_str().unwrap()).unwrap();
+
+        // 生成的YAML文件确实是YAML而不是TOML
+        let yaml_content = fs::read_to_string(&yaml_path).unwrap();
+        assert!(!yaml_content.contains("[general]"));
+        assert!(serde_yaml::from_str::<serde_yaml::Value>(&yaml_content).is_ok());
+
+        assert_eq!(from_toml, from_yaml);
+    }
+
+    #[test]
+    fn test_save_config_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        for file_name in ["saved.toml", "saved.yaml"] {
+            let path = temp_dir.path().join(file_name);
+            let path = path.to_str().unwrap();
+
+            let mut config: Config = toml::from_str(default_config_toml()).unwrap();
+            config
+                .ignored_parameters
+                .parameters
+                .push("timestamp".to_string());
+            config.tui.version_panel_proportion = 55;
+            config.tui.version_sort_mode = crate::models::VersionSortMode::RecentlyModified;
+            config
+                .tolerance
+                .per_parameter_tolerance
+                .insert("lr".to_string(), 0.5);
+
+            save_config(&config, path).unwrap();
+            let reloaded = load_config(path).unwrap();
+            assert_eq!(reloaded, config);
+        }
+    }
+
+    #[test]
+    fn test_default_config_toml_is_valid() {
+        let config: Config = toml::from_str(default_config_toml()).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.general.log_dir, "lightning_logs");
+    }
 }
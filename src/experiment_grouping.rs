@@ -1,13 +1,19 @@
 // src/experiment_grouping.rs
+use crate::expression::Expression;
 use crate::file_utils::extract_version_number_safe;
+use crate::metrics::{VersionMetrics, load_version_metrics, monitored_metric};
 use crate::models::{
-    BasicParameterValue, Config, ExperimentGroup, GroupingConfig, IgnoredConfig, ParameterValue,
-    ToleranceConfig, VersionData,
+    BasicParameterValue, Config, ExperimentGroup, GroupDiff, GroupingConfig, GroupingStrategy,
+    IgnoreRule, IgnoredConfig, LoadReport, MainKeyMissingPolicy, ParameterDifference,
+    ParameterValue, SimilarityMode, ToleranceConfig, VersionData,
+};
+use crate::yaml_parser::{
+    FileTooLarge, ParseOptions, ParsedHparamsFile, parse_multiple_hparams_files,
 };
-use crate::yaml_parser::parse_multiple_hparams_files;
 use anyhow::Result;
 use serde_yaml::{Mapping, Value};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
@@ -16,34 +22,76 @@ use std::path::PathBuf;
 /// 如果没有配置main_key，则在所有版本中删除共有参数
 ///
 /// # 返回值
-/// 返回版本数据列表和每个main_key分组内的相同hparams数据
+/// 返回版本数据列表、每个main_key分组内的相同hparams数据和加载诊断信息
 pub fn create_version_data_list(
     config: &Config,
     hparams_files: &[PathBuf],
-) -> Result<(
-    Vec<VersionData>,
-    HashMap<String, HashMap<String, ParameterValue>>,
-)> {
+) -> Result<(Vec<VersionData>, CommonHparams, LoadReport)> {
     let mut versions = Vec::new();
 
-    // 批量解析所有hparams文件
-    let parsed_results = parse_multiple_hparams_files(hparams_files)?;
+    // 批量解析所有hparams文件，解析失败的文件被跳过并记录在加载报告中
+    let (parsed_results, failures) =
+        parse_multiple_hparams_files(hparams_files, &ParseOptions::from(config))?;
+    let mut load_report = LoadReport {
+        files_found: hparams_files.len(),
+        files_parsed: parsed_results.len(),
+        ..LoadReport::default()
+    };
+    for (path, error) in failures {
+        if error.downcast_ref::<FileTooLarge>().is_some() {
+            load_report.oversized_files.push(path.clone());
+        }
+        load_report
+            .warnings
+            .push(format!("Failed to parse {}: {:#}", path.display(), error));
+        load_report.failed_files.push(path);
+    }
 
     // 处理每个解析结果，创建VersionData
-    for (file_path, hparams) in parsed_results {
+    let derived_parameters = config.derived_parameters.expressions();
+    for ParsedHparamsFile {
+        path: file_path,
+        mut hparams,
+        mut key_order,
+        content_hash,
+        warnings,
+    } in parsed_results
+    {
+        // 解析时的警告（如被覆盖的重复键）
+        load_report.warnings.extend(warnings);
+
         // 提取版本号
         let version_num = extract_version_number_safe(&file_path)?;
 
+        // 注入派生参数，表达式只引用超参数文件中的参数
+        inject_derived_parameters(
+            &derived_parameters,
+            &mut hparams,
+            &mut key_order,
+            &mut load_report,
+        );
+
         // 检查是否配置了main_key
         if let Some(main_keys) = &config.grouping.main_key {
-            // 验证该版本是否包含所有配置的main_key
-            for main_key in main_keys {
-                if !hparams.contains_key(main_key) {
-                    return Err(anyhow::anyhow!(
-                        "Version {} is missing required main_key '{}'",
-                        version_num,
-                        main_key
-                    ));
+            // 验证该版本是否包含所有配置的main_key，缺少时按配置的策略处理
+            if let Some(main_key) = main_keys.iter().find(|key| !hparams.contains_key(*key)) {
+                match config.grouping.main_key_missing_policy {
+                    MainKeyMissingPolicy::Error => {
+                        return Err(anyhow::anyhow!(
+                            "Version {} is missing required main_key '{}'",
+                            version_num,
+                            main_key
+                        ));
+                    }
+                    MainKeyMissingPolicy::Skip => {
+                        load_report.warnings.push(format!(
+                            "Skipping version {} because it is missing main_key '{}'",
+                            version_num, main_key
+                        ));
+                        load_report.skipped_versions.push(version_num);
+                        continue;
+                    }
+                    MainKeyMissingPolicy::Ungrouped => {}
                 }
             }
         }
@@ -52,14 +100,34 @@ pub fn create_version_data_list(
         let filtered_hparams = filter_parameters(
             &hparams,
             &config.ignored_parameters.parameters,
-            &config.grouping.grouping_parameters,
+            config.grouping.effective_grouping_parameters(),
         );
 
         // 创建VersionData实例
+        let version_dir = file_path.parent().unwrap().to_path_buf();
+        let monitor = monitored_metric(&hparams, &config.metrics);
+        let VersionMetrics {
+            metrics,
+            best_epoch,
+        } = load_version_metrics(&version_dir, &config.metrics, monitor).unwrap_or_else(|e| {
+            load_report.warnings.push(format!("{:#}", e));
+            VersionMetrics::default()
+        });
         let version_data = VersionData {
             version_num,
-            path: file_path.parent().unwrap().to_path_buf(), // 保存目录路径
+            mtime: std::fs::metadata(&version_dir)
+                .and_then(|metadata| metadata.modified())
+                .ok(),
+            metrics,
+            best_epoch,
+            path: version_dir, // 保存目录路径
             hparams: filtered_hparams,
+            hparams_file: file_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            content_hash,
+            full_hparams: hparams,
+            param_order: key_order,
         };
 
         versions.push(version_data);
@@ -68,6 +136,85 @@ pub fn create_version_data_list(
     // 按版本号排序
     versions.sort_by(|a, b| a.version_num.cmp(&b.version_num));
 
+    // 检查不同目录下是否出现了相同的版本号
+    for (version_num, paths) in find_duplicate_versions(&versions) {
+        let path_list: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+        load_report.warnings.push(format!(
+            "Duplicate version number {} found in: {}",
+            version_num,
+            path_list.join(", ")
+        ));
+        load_report.duplicate_versions.push(version_num);
+    }
+    for (name, expression) in &derived_parameters {
+        let Some(count) = load_report.derived_unavailable.get(*name) else {
+            continue;
+        };
+        load_report.warnings.push(format!(
+            "Derived parameter '{}' ({}) is not available for {} versions \
+             (a referenced parameter is missing or not numeric)",
+            name,
+            expression.as_str(),
+            count
+        ));
+    }
+    for name in &load_report.derived_collisions {
+        load_report.warnings.push(format!(
+            "Derived parameter '{}' has the same name as a parameter in the hparams files, \
+             keeping the value from the files",
+            name
+        ));
+    }
+    load_report.versions_loaded = versions.len();
+
+    // 删除共有参数，并获取每个main_key分组内的相同hparams数据
+    let group_common_hparams = remove_common_hparams(config, &mut versions);
+
+    Ok((versions, group_common_hparams, load_report))
+}
+
+/// 计算派生参数并加入超参数
+///
+/// 引用的参数缺失或不是数值时跳过该派生参数，并在加载报告中计数；
+/// 与超参数文件中已有参数同名时保留文件中的值，并在加载报告中记录该名称
+fn inject_derived_parameters(
+    derived_parameters: &[(&str, Expression)],
+    hparams: &mut HashMap<String, ParameterValue>,
+    key_order: &mut Vec<String>,
+    load_report: &mut LoadReport,
+) {
+    let mut derived_values = Vec::new();
+    for (name, expression) in derived_parameters {
+        match expression.evaluate(hparams) {
+            Ok(value) => derived_values.push((name.to_string(), value)),
+            Err(_) => {
+                *load_report
+                    .derived_unavailable
+                    .entry(name.to_string())
+                    .or_default() += 1;
+            }
+        }
+    }
+    for (name, value) in derived_values {
+        if hparams.contains_key(&name) {
+            load_report.derived_collisions.insert(name);
+            continue;
+        }
+        hparams.insert(name.clone(), value);
+        key_order.push(name);
+    }
+}
+
+/// 每个main_key分组内的相同hparams数据，键为分组键，值为共有参数
+pub type CommonHparams = HashMap<String, HashMap<String, ParameterValue>>;
+
+/// 删除版本间的共有参数，返回每个main_key分组内的相同hparams数据
+///
+/// 配置了main_key时在每个main_key分组内删除共有参数；否则在未指定分组参数时删除所有版本的共有参数
+fn remove_common_hparams(
+    config: &Config,
+    versions: &mut [VersionData],
+) -> HashMap<String, HashMap<String, ParameterValue>> {
     // 存储每个main_key分组内的相同hparams数据
     let mut group_common_hparams: HashMap<String, HashMap<String, ParameterValue>> = HashMap::new();
 
@@ -77,18 +224,9 @@ pub fn create_version_data_list(
         let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
 
         for (index, version) in versions.iter().enumerate() {
-            // 获取所有main_key的值作为分组键
-            let mut group_key_parts = Vec::new();
-            for main_key in main_keys {
-                if let Some(main_key_value) = version.hparams.get(main_key) {
-                    group_key_parts.push(format!("{}={}", main_key, main_key_value));
-                }
-            }
-
             // 如果所有main_key都存在，则创建分组键
-            if group_key_parts.len() == main_keys.len() {
-                let group_key = group_key_parts.join(", ");
-                groups.entry(group_key.clone()).or_default().push(index);
+            if let Some(group_key) = main_key_group_key(&version.hparams, main_keys) {
+                groups.entry(group_key).or_default().push(index);
             }
         }
 
@@ -141,7 +279,7 @@ pub fn create_version_data_list(
                 }
             }
         }
-    } else if config.grouping.grouping_parameters.is_none() {
+    } else if config.grouping.effective_grouping_parameters().is_none() {
         // 只有在没有指定分组参数时，才删除共有参数
         // 如果指定了分组参数，我们已经过滤了需要的参数，不应该再删除
         if !versions.is_empty() {
@@ -171,7 +309,7 @@ pub fn create_version_data_list(
             }
 
             // 从所有版本中删除共有的hparams键值对
-            for version in &mut versions {
+            for version in versions.iter_mut() {
                 for key in &common_params {
                     version.hparams.remove(key.0);
                 }
@@ -179,7 +317,204 @@ pub fn create_version_data_list(
         }
     }
 
-    Ok((versions, group_common_hparams))
+    group_common_hparams
+}
+
+/// 按当前配置重新过滤版本参数
+///
+/// 从每个版本的完整解析结果重新应用忽略参数和分组参数，并重新删除共有参数，
+/// 用于运行时修改`ignored_parameters`后无需重新读取文件
+///
+/// # 返回值
+/// 返回每个main_key分组内的相同hparams数据
+pub fn refilter_versions(
+    config: &Config,
+    versions: &mut [VersionData],
+) -> HashMap<String, HashMap<String, ParameterValue>> {
+    for version in versions.iter_mut() {
+        version.hparams = filter_parameters(
+            &version.full_hparams,
+            &config.ignored_parameters.parameters,
+            config.grouping.effective_grouping_parameters(),
+        );
+    }
+    remove_common_hparams(config, versions)
+}
+
+/// 生成版本所属main_key分组的分组键，格式为`key1=value1, key2=value2`
+///
+/// 任一main_key缺失时返回None
+pub fn main_key_group_key(
+    hparams: &HashMap<String, ParameterValue>,
+    main_keys: &[String],
+) -> Option<String> {
+    let group_key_parts = main_keys
+        .iter()
+        .map(|main_key| {
+            hparams
+                .get(main_key)
+                .map(|value| format!("{}={}", main_key, value))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(group_key_parts.join(", "))
+}
+
+/// 查找重复的版本号
+///
+/// 当扫描多个根目录时，不同目录可能产生相同的`version_num`，
+/// 此函数返回每个重复版本号对应的所有目录路径（按版本号排序）
+///
+/// # 参数
+/// * `versions` - 版本数据列表
+///
+/// # 返回值
+/// * `BTreeMap<u32, Vec<PathBuf>>` - 重复版本号到其目录路径列表的映射，无重复时为空
+pub fn find_duplicate_versions(versions: &[VersionData]) -> BTreeMap<u32, Vec<PathBuf>> {
+    let mut paths_by_version: BTreeMap<u32, Vec<PathBuf>> = BTreeMap::new();
+    for version in versions {
+        paths_by_version
+            .entry(version.version_num)
+            .or_default()
+            .push(version.path.clone());
+    }
+
+    paths_by_version.retain(|_, paths| paths.len() > 1);
+    paths_by_version
+}
+
+/// 查找只在一个版本中出现的参数，通常是参数名的拼写错误（如`lerning_rate`）
+///
+/// 使用每个版本解析得到的完整参数（未过滤忽略参数和共同参数），
+/// 没有完整参数的版本使用过滤后的参数；少于两个版本时无从比较，返回空列表
+///
+/// # 返回值
+/// * `Vec<(String, u32)>` - 参数名及唯一包含它的版本号，按参数名排序
+pub fn orphan_parameters(versions: &[VersionData]) -> Vec<(String, u32)> {
+    if versions.len() < 2 {
+        return Vec::new();
+    }
+    let mut occurrences: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+    for version in versions {
+        let params = if version.full_hparams.is_empty() {
+            &version.hparams
+        } else {
+            &version.full_hparams
+        };
+        for key in params.keys() {
+            occurrences
+                .entry(key.as_str())
+                .or_default()
+                .push(version.version_num);
+        }
+    }
+
+    occurrences
+        .into_iter()
+        .filter_map(|(key, version_nums)| match version_nums.as_slice() {
+            [version_num] => Some((key.to_string(), *version_num)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 统计两个参数各取值组合下的版本数量，用于发现参数扫描中缺失的组合
+///
+/// 与`orphan_parameters`一样优先使用完整参数；版本缺少某个参数时该参数的取值记为`-`，
+/// 没有任何版本的组合不出现在结果中
+///
+/// # 返回值
+/// * `HashMap<(String, String), usize>` - 键为`(param_x的取值, param_y的取值)`，值为版本数量
+pub fn coverage_matrix(
+    versions: &[VersionData],
+    param_x: &str,
+    param_y: &str,
+) -> HashMap<(String, String), usize> {
+    let mut matrix = HashMap::new();
+    for version in versions {
+        let params = if version.full_hparams.is_empty() {
+            &version.hparams
+        } else {
+            &version.full_hparams
+        };
+        let value_of = |key: &str| {
+            params
+                .get(key)
+                .map_or_else(|| "-".to_string(), ParameterValue::to_simple_string)
+        };
+        *matrix
+            .entry((value_of(param_x), value_of(param_y)))
+            .or_insert(0) += 1;
+    }
+    matrix
+}
+
+/// 查找在不同版本中值类型不一致的参数，如某些版本中为`Int`、另一些版本中为`String`
+///
+/// 类型不一致的参数在分组时总是被视为不同，通常是日志记录方式变化或需要开启类型转换。
+/// 与`orphan_parameters`一样优先使用完整参数
+///
+/// # 返回值
+/// * `Vec<(String, Vec<&'static str>)>` - 参数名及出现过的类型名（按名称排序），按参数名排序
+pub fn type_inconsistent_keys(versions: &[VersionData]) -> Vec<(String, Vec<&'static str>)> {
+    let mut types: BTreeMap<&str, BTreeSet<&'static str>> = BTreeMap::new();
+    for version in versions {
+        let params = if version.full_hparams.is_empty() {
+            &version.hparams
+        } else {
+            &version.full_hparams
+        };
+        for (key, value) in params {
+            types
+                .entry(key.as_str())
+                .or_default()
+                .insert(value.type_name());
+        }
+    }
+
+    types
+        .into_iter()
+        .filter(|(_, type_names)| type_names.len() > 1)
+        .map(|(key, type_names)| (key.to_string(), type_names.into_iter().collect()))
+        .collect()
+}
+
+/// 参数键在各版本中的出现情况
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyPresence {
+    pub with_key: Vec<u32>,         // 包含该参数的版本号（升序）
+    pub without_key: Vec<u32>,      // 缺少该参数的版本号（升序）
+    pub failed_files: Vec<PathBuf>, // 解析失败而无法检查的文件
+}
+
+/// 检查每个版本是否包含指定参数，不因缺少参数而报错
+///
+/// 与`create_version_data_list`中的main_key检查不同，此函数只报告结果，
+/// 便于在配置main_key之前确认哪些版本缺少该参数
+///
+/// # 参数
+/// * `hparams_files` - hparams文件路径列表
+/// * `key` - 要检查的参数名（嵌套参数使用展平后的键名）
+/// * `options` - 解析选项，应与分组时使用的选项一致
+pub fn check_key_presence(
+    hparams_files: &[PathBuf],
+    key: &str,
+    options: &ParseOptions,
+) -> Result<KeyPresence> {
+    let mut presence = KeyPresence::default();
+    let (parsed_files, failures) = parse_multiple_hparams_files(hparams_files, options)?;
+    presence.failed_files = failures.into_iter().map(|(path, _)| path).collect();
+    for parsed in parsed_files {
+        let version_num = extract_version_number_safe(&parsed.path)?;
+        if parsed.hparams.contains_key(key) {
+            presence.with_key.push(version_num);
+        } else {
+            presence.without_key.push(version_num);
+        }
+    }
+
+    presence.with_key.sort_unstable();
+    presence.without_key.sort_unstable();
+    Ok(presence)
 }
 
 /// 过滤参数，排除被忽略的参数
@@ -190,7 +525,7 @@ pub fn create_version_data_list(
 ///
 /// # 参数
 /// * `hparams` - 原始参数映射
-/// * `ignored_params` - 需要排除的参数名列表
+/// * `ignored_params` - 需要排除的参数名列表，`name=re:pattern`形式的项只排除值匹配正则的参数
 /// * `grouping_params` - 可选的分组参数列表，如果指定则只包含这些参数
 ///
 /// # 返回值
@@ -198,18 +533,20 @@ pub fn create_version_data_list(
 ///
 /// # 示例
 /// ```ignore
-/// let filtered = filter_parameters(&params, &["timestamp".to_string()], &Some(vec!["lr".to_string()]));
+/// let filtered = filter_parameters(&params, &["timestamp".to_string()], Some(&["lr".to_string()]));
 /// // 只返回"lr"参数（如果存在且未被忽略）
 /// ```
 fn filter_parameters(
     hparams: &HashMap<String, ParameterValue>,
     ignored_params: &[String],
-    grouping_params: &Option<Vec<String>>,
+    grouping_params: Option<&[String]>,
 ) -> HashMap<String, ParameterValue> {
     let mut filtered_params = HashMap::new();
 
-    // 构建忽略参数的HashSet以便快速查找
-    let ignored_set: HashSet<_> = ignored_params.iter().collect();
+    // 解析忽略规则，`name=re:pattern`形式的规则只在值匹配时忽略
+    let ignore_rules = IgnoreRule::parse_all(ignored_params);
+    let is_ignored =
+        |key: &str, value: &ParameterValue| ignore_rules.iter().any(|rule| rule.matches(key, value));
 
     // 检查是否指定了分组参数
     match grouping_params {
@@ -217,7 +554,7 @@ fn filter_parameters(
             // 如果指定了分组参数，只包含这些参数
             for param_name in params {
                 if let Some(value) = hparams.get(param_name) {
-                    if !ignored_set.contains(param_name) {
+                    if !is_ignored(param_name, value) {
                         filtered_params.insert(param_name.clone(), value.clone());
                     }
                 }
@@ -226,7 +563,7 @@ fn filter_parameters(
         None => {
             // 如果没有指定分组参数，包含所有未被忽略的参数
             for (key, value) in hparams {
-                if !ignored_set.contains(key) {
+                if !is_ignored(key, value) {
                     filtered_params.insert(key.clone(), value.clone());
                 }
             }
@@ -241,6 +578,7 @@ fn filter_parameters(
 /// 此函数根据参数值计算一致的哈希值，用于标识具有相同参数配置的实验组。
 /// 哈希计算考虑了配置的容差设置，确保在容差范围内相等的参数产生相同的哈希值。
 /// 参数按键排序以确保哈希的一致性，不受参数顺序影响。
+/// 配置了`id_parameters`时只哈希其中的参数，否则只哈希实际生效的分组参数。
 ///
 /// # 参数
 /// * `params` - 要计算哈希的参数映射
@@ -254,7 +592,7 @@ fn filter_parameters(
 /// - 浮点数：根据容差进行舍入处理
 /// - 整数：根据容差进行调整
 /// - 布尔值：直接使用原始值
-/// - 列表：递归处理每个元素并考虑长度
+/// - 列表：递归处理每个元素并考虑长度，空列表是一个独立的取值，与缺失的参数哈希不同
 ///
 /// # 示例
 /// ```ignore
@@ -264,8 +602,9 @@ fn filter_parameters(
 fn compute_params_hash(params: &HashMap<String, ParameterValue>, config: &Config) -> String {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
 
-    // 辅助函数：递归哈希单个ParameterValue
+    // 辅助函数：递归哈希单个ParameterValue，使用该参数对应的容差
     fn hash_parameter_value(
+        key: &str,
         value: &ParameterValue,
         hasher: &mut std::collections::hash_map::DefaultHasher,
         config: &Config,
@@ -279,15 +618,12 @@ fn compute_params_hash(params: &HashMap<String, ParameterValue>, config: &Config
                 }
             }
             ParameterValue::Basic(BasicParameterValue::Float(f)) => {
-                // 对浮点数进行舍入，考虑容差
-                let rounded = (f / config.tolerance.float_tolerance).round()
-                    * config.tolerance.float_tolerance;
-                rounded.to_bits().hash(hasher);
+                // 对浮点数按容差分桶，inf/-inf/NaN各自单独成桶
+                float_tolerance_bucket(*f, config.tolerance.float_tolerance_for(key)).hash(hasher);
             }
             ParameterValue::Basic(BasicParameterValue::Int(i)) => {
-                // 对整数进行处理，考虑容差
-                let adjusted = i - (i % (config.tolerance.int_tolerance + 1));
-                adjusted.hash(hasher);
+                // 对整数按容差分桶
+                int_tolerance_bucket(*i, config.tolerance.int_tolerance_for(key)).hash(hasher);
             }
             ParameterValue::Basic(BasicParameterValue::Bool(b)) => b.hash(hasher),
             ParameterValue::List(list) => {
@@ -295,7 +631,15 @@ fn compute_params_hash(params: &HashMap<String, ParameterValue>, config: &Config
                 list.len().hash(hasher);
                 for item in list {
                     // 递归处理列表中的每个ParameterValue
-                    hash_parameter_value(item, hasher, config);
+                    hash_parameter_value(key, item, hasher, config);
+                }
+            }
+            ParameterValue::Map(map) => {
+                // 映射按键名有序哈希
+                map.len().hash(hasher);
+                for (map_key, item) in map {
+                    map_key.hash(hasher);
+                    hash_parameter_value(key, item, hasher, config);
                 }
             }
         }
@@ -303,9 +647,9 @@ fn compute_params_hash(params: &HashMap<String, ParameterValue>, config: &Config
 
     // 确定要哈希的参数
     let params_to_hash: HashMap<String, ParameterValue> =
-        if let Some(ref grouping_params) = config.grouping.grouping_parameters {
-            // 如果指定了分组参数，只哈希这些参数
-            grouping_params
+        if let Some(id_params) = config.grouping.effective_id_parameters() {
+            // 如果指定了组ID参数或分组参数，只哈希这些参数
+            id_params
                 .iter()
                 .filter_map(|param| {
                     params
@@ -328,13 +672,68 @@ fn compute_params_hash(params: &HashMap<String, ParameterValue>, config: &Config
 
         // 对值进行哈希（使用equals_with_tolerance方法来考虑容差）
         let value = params_to_hash.get(key).unwrap();
-        hash_parameter_value(value, &mut hasher, config);
+        hash_parameter_value(key, value, &mut hasher, config);
     }
 
     // 将哈希值转换为字符串
     format!("{:x}", hasher.finish())
 }
 
+/// 计算以该版本为基准的实验组ID
+///
+/// 配置了`id_parameters`时使用版本的完整参数，使被分组参数过滤或作为共有参数删除的
+/// 参数仍然参与计算，组ID不随其他版本的加入而变化
+fn compute_group_id(version: &VersionData, config: &Config) -> String {
+    let has_id_parameters = config
+        .grouping
+        .id_parameters
+        .as_ref()
+        .is_some_and(|params| !params.is_empty());
+    if has_id_parameters && !version.full_hparams.is_empty() {
+        compute_params_hash(&version.full_hparams, config)
+    } else {
+        compute_params_hash(&version.hparams, config)
+    }
+}
+
+/// 按容差将整数映射到桶编号
+///
+/// 桶宽为`int_tolerance + 1`，整数被舍入到最近的桶宽倍数，
+/// 因此同一个桶内任意两个整数的差值都不超过容差，与`equals_with_tolerance`保持一致。
+/// 使用欧几里得除法，负数与正数使用同样宽度的连续桶，不会因为`%`产生负余数而错位。
+/// 只有容差为偶数（桶宽为奇数）时分桶才关于0对称；容差为奇数时桶边界偏向负方向，
+/// 如容差为3时0所在的桶为`[-2, 1]`，`-2`与`2`落入不同的桶
+fn int_tolerance_bucket(value: i64, int_tolerance: i64) -> i64 {
+    let width = i128::from(int_tolerance.max(0)) + 1;
+    let shifted = i128::from(value) + width / 2;
+    // 结果的绝对值不超过原值，转换不会溢出
+    shifted.div_euclid(width) as i64
+}
+
+/// 按容差将浮点数映射到桶，返回`(类别, 舍入值的位模式)`
+///
+/// 有限值被舍入到最近的容差倍数（容差不大于0时不舍入），-0.0与0.0落入同一个桶；
+/// `inf`、`-inf`和`NaN`使用单独的类别，所有NaN（不论符号和载荷）落入同一个桶，
+/// 与`equals_with_tolerance`中两个NaN相等的约定一致。
+/// 类别也保证舍入时溢出为无穷大的极大有限值不会与真正的`inf`混在一起
+fn float_tolerance_bucket(value: f64, float_tolerance: f64) -> (u8, u64) {
+    if value.is_nan() {
+        (3, 0)
+    } else if value == f64::INFINITY {
+        (1, 0)
+    } else if value == f64::NEG_INFINITY {
+        (2, 0)
+    } else {
+        let rounded = if float_tolerance > 0.0 {
+            (value / float_tolerance).round() * float_tolerance
+        } else {
+            value
+        };
+        // 加0.0将-0.0规范化为0.0
+        (0, (rounded + 0.0).to_bits())
+    }
+}
+
 /// 比较两个参数集，返回差异参数的数量
 ///
 /// 此函数比较两个参数映射，计算在考虑容差设置的情况下有多少参数不同。
@@ -360,88 +759,336 @@ fn count_different_parameters(
     params1: &HashMap<String, ParameterValue>,
     params2: &HashMap<String, ParameterValue>,
     tolerance: &Config,
+) -> usize {
+    count_different_parameters_bounded(params1, params2, tolerance, usize::MAX)
+}
+
+/// 比较两个参数集，差异数量超过`max`时提前停止计数
+///
+/// 差异的判定规则与`count_different_parameters`一致。只关心差异数量是否超过阈值时
+/// （如查找相似组），可以避免完整遍历明显不同的参数集。
+/// `grouping.ignore_missing_keys`为true时只比较双方都有的参数
+///
+/// # 返回值
+/// * `usize` - 差异数量不超过`max`时返回准确的差异数量，否则返回`max + 1`
+pub fn count_different_parameters_bounded(
+    params1: &HashMap<String, ParameterValue>,
+    params2: &HashMap<String, ParameterValue>,
+    tolerance: &Config,
+    max: usize,
 ) -> usize {
     let mut diff_count = 0;
+    let ignore_missing_keys = tolerance.grouping.ignore_missing_keys;
 
     // 检查第一个参数集中的所有参数
     for (key, value1) in params1 {
-        if let Some(value2) = params2.get(key) {
-            if !value1.equals_with_tolerance(value2, &tolerance.tolerance) {
-                diff_count += 1;
-            }
-        } else {
+        let differs = match params2.get(key) {
+            Some(value2) => !value1.equals_with_tolerance_for_key(value2, key, &tolerance.tolerance),
+            None => !ignore_missing_keys,
+        };
+        if differs {
             diff_count += 1;
+            if diff_count > max {
+                return diff_count;
+            }
         }
     }
 
     // 检查第二个参数集中独有的参数
-    for (key, _) in params2 {
+    if ignore_missing_keys {
+        return diff_count;
+    }
+    for key in params2.keys() {
         if !params1.contains_key(key) {
             diff_count += 1;
+            if diff_count > max {
+                return diff_count;
+            }
         }
     }
 
     diff_count
 }
 
+/// 比较两个参数集，返回差异参数的键名列表（已排序）
+///
+/// 包括值不同（考虑容差）的参数和只存在于其中一个参数集中的参数。
+/// 用于展示差异，因此不受`grouping.ignore_missing_keys`影响，缺失的参数始终列出
+pub fn find_different_parameters(
+    params1: &HashMap<String, ParameterValue>,
+    params2: &HashMap<String, ParameterValue>,
+    config: &Config,
+) -> Vec<String> {
+    let mut diff_keys: Vec<String> = params1
+        .iter()
+        .filter(|(key, value1)| match params2.get(*key) {
+            Some(value2) => !value1.equals_with_tolerance_for_key(value2, key, &config.tolerance),
+            None => true,
+        })
+        .map(|(key, _)| key.clone())
+        .chain(
+            params2
+                .keys()
+                .filter(|key| !params1.contains_key(*key))
+                .cloned(),
+        )
+        .collect();
+
+    diff_keys.sort();
+    diff_keys
+}
+
+/// 比较两个实验组的基础参数，返回按参数名排序的差异
+///
+/// 取值比较与[`find_different_parameters`]一致，考虑容差，只存在于一个组中的参数也列出
+pub fn diff_groups(a: &ExperimentGroup, b: &ExperimentGroup, config: &Config) -> GroupDiff {
+    let differences = find_different_parameters(&a.base_parameters, &b.base_parameters, config)
+        .into_iter()
+        .map(|key| ParameterDifference {
+            left: a.base_parameters.get(&key).cloned(),
+            right: b.base_parameters.get(&key).cloned(),
+            key,
+        })
+        .collect();
+    GroupDiff {
+        left_group_id: a.group_id.clone(),
+        left_label: a.label(config),
+        right_group_id: b.group_id.clone(),
+        right_label: b.label(config),
+        differences,
+    }
+}
+
 /// 将版本数据分组为实验组
 pub fn group_versions(config: &Config, versions: Vec<VersionData>) -> Result<Vec<ExperimentGroup>> {
     let mut groups: Vec<ExperimentGroup> = Vec::new();
-    let mut ungrouped_versions: Vec<VersionData> = versions.clone();
 
-    // 对每个未分组的版本进行分组
-    while let Some(version) = ungrouped_versions.pop() {
-        // 尝试将版本添加到现有组
-        let mut added_to_existing = false;
+    // 缺少main_key的版本不参与分组，统一放入未分组的实验组
+    let (versions, ungrouped): (Vec<_>, Vec<_>) = versions
+        .into_iter()
+        .partition(|version| !is_ungrouped_version(version, &config.grouping));
 
-        for group in &mut groups {
-            // 检查是否可以添加到该组
-            let can_add_to_group = if let Some(ref grouping_params) =
-                config.grouping.grouping_parameters
-            {
-                // 如果指定了分组参数，基于参数结构进行分组
-                // 检查两个版本是否都有相同的参数结构（即分组参数都存在）
-                grouping_params.iter().all(|param| {
-                    version.hparams.contains_key(param) && group.base_parameters.contains_key(param)
-                })
-            } else {
-                // 如果没有指定分组参数，检查所有参数是否完全相同
-                count_different_parameters(&version.hparams, &group.base_parameters, config) == 0
+    match config.grouping.grouping_strategy {
+        // 从最后一个版本开始依次分组
+        GroupingStrategy::Hash => {
+            add_versions_to_groups(&mut groups, versions.into_iter().rev().collect(), config)
+        }
+        GroupingStrategy::Cluster => groups = cluster_versions(versions, config),
+    }
+
+    // 按组内版本数量排序（可选）
+    groups.sort_by(|a, b| b.member_versions.len().cmp(&a.member_versions.len()));
+    make_group_ids_unique(&mut groups, 0);
+
+    // 未分组的实验组始终排在最后
+    if !ungrouped.is_empty() {
+        groups.push(ExperimentGroup {
+            group_id: UNGROUPED_GROUP_ID.to_string(),
+            base_parameters: HashMap::new(),
+            member_versions: ungrouped,
+        });
+    }
+
+    Ok(groups)
+}
+
+/// 为组ID重复的实验组加上`-2`、`-3`等后缀，使组ID在分组结果中唯一
+///
+/// 配置了`id_parameters`时，只在其他参数上不同的实验组会得到相同的组ID；
+/// 重复的组按代表版本号排序，版本号最小的组保留原ID，因此加入新版本时已有组的ID通常不变。
+/// 前`fixed`个组的ID不会被修改，与它们重复的其他组全部加后缀
+fn make_group_ids_unique(groups: &mut [ExperimentGroup], fixed: usize) {
+    let mut indices_by_id: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, group) in groups.iter().enumerate() {
+        indices_by_id
+            .entry(group.group_id.clone())
+            .or_default()
+            .push(index);
+    }
+    let mut used_ids: HashSet<String> = indices_by_id.keys().cloned().collect();
+    for (group_id, mut indices) in indices_by_id {
+        if indices.len() < 2 {
+            continue;
+        }
+        let keep_first = indices.iter().all(|&index| index >= fixed);
+        indices.retain(|&index| index >= fixed);
+        indices.sort_by_key(|&index| groups[index].representative().version_num);
+        let mut suffix = 2;
+        for index in indices.into_iter().skip(usize::from(keep_first)) {
+            let unique_id = loop {
+                let candidate = format!("{}-{}", group_id, suffix);
+                suffix += 1;
+                if used_ids.insert(candidate.clone()) {
+                    break candidate;
+                }
             };
+            groups[index].group_id = unique_id;
+        }
+    }
+}
+
+/// 按容差内相等的传递闭包对版本聚类
+///
+/// 两两比较所有版本，匹配的版本合并到同一组（并查集），因此A与B相等、B与C相等时，
+/// 即使A与C超出容差也会被分到同一组。每组以版本号最大的成员作为基准参数
+fn cluster_versions(versions: Vec<VersionData>, config: &Config) -> Vec<ExperimentGroup> {
+    fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+        index
+    }
 
-            // 如果可以添加到该组，则添加
-            if can_add_to_group {
-                group.member_versions.push(version.clone());
-                added_to_existing = true;
-                break;
+    let mut parents: Vec<usize> = (0..versions.len()).collect();
+    for i in 0..versions.len() {
+        for j in (i + 1)..versions.len() {
+            let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+            if root_i != root_j
+                && parameters_match(&versions[j].hparams, &versions[i].hparams, config)
+            {
+                parents[root_j] = root_i;
             }
         }
+    }
 
-        // 如果没有添加到现有组，则创建新组
-        if !added_to_existing {
-            let group_id = compute_params_hash(&version.hparams, config);
+    // 按首个成员出现的顺序收集各组成员
+    let mut root_order: Vec<usize> = Vec::new();
+    let mut members: HashMap<usize, Vec<VersionData>> = HashMap::new();
+    for (index, version) in versions.into_iter().enumerate() {
+        let root = find_root(&mut parents, index);
+        if !members.contains_key(&root) {
+            root_order.push(root);
+        }
+        members.entry(root).or_default().push(version);
+    }
 
-            let new_group = ExperimentGroup {
+    root_order
+        .into_iter()
+        .filter_map(|root| members.remove(&root))
+        .map(|mut member_versions| {
+            member_versions.sort_by_key(|version| version.version_num);
+            // 并查集的每个根至少有一个成员
+            let base_version = member_versions.last().expect("group has members");
+            let base_parameters = base_version.hparams.clone();
+            let group_id = compute_group_id(base_version, config);
+            ExperimentGroup {
                 group_id,
-                base_parameters: version.hparams.clone(),
-                member_versions: vec![version],
-            };
+                base_parameters,
+                member_versions,
+            }
+        })
+        .collect()
+}
+
+/// 收集缺少main_key的版本的实验组ID
+pub const UNGROUPED_GROUP_ID: &str = "ungrouped";
+
+/// 检查版本是否因缺少main_key而应放入未分组的实验组
+///
+/// 只在`main_key_missing_policy`为`ungrouped`时生效
+fn is_ungrouped_version(version: &VersionData, grouping: &GroupingConfig) -> bool {
+    if grouping.main_key_missing_policy != MainKeyMissingPolicy::Ungrouped {
+        return false;
+    }
+    grouping
+        .main_key
+        .as_ref()
+        .is_some_and(|keys| keys.iter().any(|key| !version.hparams.contains_key(key)))
+}
+
+/// 将新版本增量加入已有的实验组
+///
+/// 新版本会被放入第一个匹配的已有组，没有匹配的组时创建新组并追加到列表末尾。
+/// 已有组的`group_id`和顺序保持不变，便于重新加载后导出结果保持稳定
+///
+/// # 参数
+/// * `existing` - 已有的实验组列表，会被原地修改
+/// * `new_versions` - 需要加入的新版本
+/// * `config` - 包含分组和容差配置的配置对象
+pub fn add_versions_to_groups(
+    existing: &mut Vec<ExperimentGroup>,
+    new_versions: Vec<VersionData>,
+    config: &Config,
+) {
+    let mut touched_groups: HashSet<usize> = HashSet::new();
+    let existing_count = existing.len();
 
-            groups.push(new_group);
+    for version in new_versions {
+        // 尝试将版本添加到现有组
+        if let Some(group_idx) = existing
+            .iter()
+            .position(|group| can_add_to_group(&version, group, config))
+        {
+            existing[group_idx].member_versions.push(version);
+            touched_groups.insert(group_idx);
+            continue;
         }
+
+        // 如果没有添加到现有组，则创建新组
+        let group_id = compute_group_id(&version, config);
+        existing.push(ExperimentGroup {
+            group_id,
+            base_parameters: version.hparams.clone(),
+            member_versions: vec![version],
+        });
     }
 
-    // 对每个组的成员按版本号排序
-    for group in &mut groups {
-        group
+    // 对有新成员加入的组按版本号重新排序
+    for group_idx in touched_groups {
+        existing[group_idx]
             .member_versions
             .sort_by(|a, b| a.version_num.cmp(&b.version_num));
     }
 
-    // 按组内版本数量排序（可选）
-    groups.sort_by(|a, b| b.member_versions.len().cmp(&a.member_versions.len()));
+    // 新组的ID可能与已有组或其他新组重复，已有组的ID保持不变
+    make_group_ids_unique(existing, existing_count);
+}
 
-    Ok(groups)
+/// 检查版本是否可以加入指定的实验组
+fn can_add_to_group(version: &VersionData, group: &ExperimentGroup, config: &Config) -> bool {
+    parameters_match(&version.hparams, &group.base_parameters, config)
+}
+
+/// 检查参数集是否与基准参数属于同一实验组
+fn parameters_match(
+    params: &HashMap<String, ParameterValue>,
+    base_parameters: &HashMap<String, ParameterValue>,
+    config: &Config,
+) -> bool {
+    let explicit_params = config
+        .grouping
+        .grouping_parameters
+        .as_deref()
+        .filter(|params| !params.is_empty());
+
+    match (
+        explicit_params,
+        config.grouping.effective_grouping_parameters(),
+    ) {
+        (Some(grouping_params), _) => {
+            // 如果指定了分组参数，基于参数结构进行分组
+            // 检查两个版本是否都有相同的参数结构（即分组参数都存在）
+            grouping_params.iter().all(|param| {
+                params.contains_key(param) && base_parameters.contains_key(param)
+            })
+        }
+        (None, Some(main_keys)) => {
+            // 回退到按main_key分组时，检查所有main_key的值是否相同
+            main_keys.iter().all(|key| {
+                match (params.get(key), base_parameters.get(key)) {
+                    (Some(a), Some(b)) => {
+                        a.equals_with_tolerance_for_key(b, key, &config.tolerance)
+                    }
+                    _ => false,
+                }
+            })
+        }
+        (None, None) => {
+            // 如果没有指定分组参数，检查所有参数是否完全相同
+            count_different_parameters(params, base_parameters, config) == 0
+        }
+    }
 }
 
 /// 查找相似的实验组
@@ -461,13 +1108,7 @@ pub fn find_similar_groups(
                 continue;
             }
 
-            let diff_count = count_different_parameters(
-                &groups[i].base_parameters,
-                &groups[j].base_parameters,
-                config,
-            );
-
-            if diff_count <= config.grouping.similarity_threshold {
+            if are_groups_similar(&groups[i].base_parameters, &groups[j].base_parameters, config) {
                 similar_groups
                     .get_mut(group_id)
                     .unwrap()
@@ -479,68 +1120,399 @@ pub fn find_similar_groups(
     similar_groups
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::{GroupingConfig, IgnoredConfig, ToleranceConfig};
-
-    // 辅助函数：创建测试配置
-    fn create_test_config() -> Config {
-        Config {
-            general: Default::default(),
-            ignored_parameters: IgnoredConfig {
-                parameters: vec!["fold".to_string(), "devices".to_string()],
-            },
-            tolerance: ToleranceConfig {
-                float_tolerance: 0.001,
-                int_tolerance: 0,
-                string_case_sensitive: false,
-            },
-            grouping: GroupingConfig {
-                group_by_all_parameters: true,
-                grouping_parameters: None,
-                similarity_threshold: 2,
-                main_key: None,
-            },
-            diff: Default::default(),
-            tui: Default::default(),
-            keybindings: Default::default(),
-            test_script: Default::default(),
+/// 判断两组基础参数是否相似
+///
+/// `count`模式下不同参数的个数不超过`similarity_threshold`即相似；
+/// `percent`模式下不同参数占两组参数并集的百分比不超过`similarity_threshold`即相似
+fn are_groups_similar(
+    params1: &HashMap<String, ParameterValue>,
+    params2: &HashMap<String, ParameterValue>,
+    config: &Config,
+) -> bool {
+    let threshold = config.grouping.similarity_threshold;
+    match config.grouping.similarity_mode {
+        SimilarityMode::Count => {
+            count_different_parameters_bounded(params1, params2, config, threshold) <= threshold
+        }
+        SimilarityMode::Percent => {
+            let union_size = params1.len()
+                + params2.keys().filter(|key| !params1.contains_key(*key)).count();
+            // 差异数超过该值时百分比必然超过阈值，可以提前结束计数
+            let max_diff = threshold * union_size / 100;
+            let diff_count = count_different_parameters_bounded(params1, params2, config, max_diff);
+            diff_count * 100 <= threshold * union_size
         }
     }
+}
 
-    // 辅助函数：创建带有main_key的测试配置
-    fn create_test_config_with_main_key(main_key: Option<Vec<String>>) -> Config {
-        let mut config = create_test_config();
-        config.grouping.main_key = main_key;
-        config
-    }
+/// 查找相似的实验组对，每个无序对只返回一次
+///
+/// 与`find_similar_groups`使用相同的相似度判定，返回的每对按组ID排序
+#[allow(dead_code)] // 对外接口，主程序对已计算的相似组映射直接去重
+pub fn find_similar_pairs(groups: &[ExperimentGroup], config: &Config) -> Vec<(String, String)> {
+    dedupe_similar_pairs(&find_similar_groups(groups, config))
+}
 
-    // 测试过滤参数功能
-    #[test]
-    fn test_filter_parameters() {
-        let mut hparams = HashMap::new();
-        hparams.insert(
-            "model".to_string(),
-            ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
-        );
-        hparams.insert(
-            "fold".to_string(),
-            ParameterValue::Basic(BasicParameterValue::Int(1)),
-        );
-        hparams.insert(
-            "devices".to_string(),
-            ParameterValue::Basic(BasicParameterValue::Int(2)),
-        );
-        hparams.insert(
-            "lr".to_string(),
-            ParameterValue::Basic(BasicParameterValue::Float(0.001)),
-        );
+/// 将双向记录的相似组关系去重为无序的组ID对
+///
+/// `find_similar_groups`会同时记录A→B和B→A，这里每对只保留一次，
+/// 对内按组ID排序，整体按字典序排列
+pub fn dedupe_similar_pairs(similar_groups: &HashMap<String, Vec<String>>) -> Vec<(String, String)> {
+    let pairs: BTreeSet<(String, String)> = similar_groups
+        .iter()
+        .flat_map(|(group_id, similar_ids)| {
+            similar_ids.iter().filter(move |id| *id != group_id).map(move |id| {
+                if group_id < id {
+                    (group_id.clone(), id.clone())
+                } else {
+                    (id.clone(), group_id.clone())
+                }
+            })
+        })
+        .collect();
+    pairs.into_iter().collect()
+}
 
-        let ignored_params = vec!["fold".to_string(), "devices".to_string()];
-        let grouping_params: Option<Vec<String>> = None;
+/// 分组结果的简要统计
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub total_versions: usize,      // 所有组内的版本总数
+    pub total_groups: usize,        // 实验组数量
+    pub multi_member_groups: usize, // 包含多个版本的实验组数量
+    pub similar_pairs: usize,       // 相似组对数（已去重）
+    pub under_replicated_groups: Option<usize>, // 成员数少于min_replication的实验组数量，未启用检查时为None
+}
 
-        let filtered = filter_parameters(&hparams, &ignored_params, &grouping_params);
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Summary: {} versions, {} groups ({} with multiple members), {} similar group pairs",
+            self.total_versions, self.total_groups, self.multi_member_groups, self.similar_pairs
+        )?;
+        if let Some(count) = self.under_replicated_groups {
+            write!(f, ", {} under-replicated groups", count)?;
+        }
+        Ok(())
+    }
+}
+
+/// 判断实验组的运行次数是否少于`min_replication`
+///
+/// `min_replication`为0时不检查；收集缺少main_key版本的未分组实验组不代表一个参数配置，始终不标记
+pub fn is_under_replicated(group: &ExperimentGroup, min_replication: usize) -> bool {
+    group.group_id != UNGROUPED_GROUP_ID && group.member_versions.len() < min_replication
+}
+
+/// 统计分组结果，`min_replication`大于0时同时统计重复次数不足的实验组
+pub fn summarize(
+    groups: &[ExperimentGroup],
+    similar_groups: &HashMap<String, Vec<String>>,
+    min_replication: usize,
+) -> Summary {
+    Summary {
+        total_versions: groups.iter().map(|g| g.member_versions.len()).sum(),
+        total_groups: groups.len(),
+        multi_member_groups: groups
+            .iter()
+            .filter(|g| g.member_versions.len() > 1)
+            .count(),
+        similar_pairs: dedupe_similar_pairs(similar_groups).len(),
+        under_replicated_groups: (min_replication > 0).then(|| {
+            groups
+                .iter()
+                .filter(|g| is_under_replicated(g, min_replication))
+                .count()
+        }),
+    }
+}
+
+/// 跨main_key分组的比较结果
+///
+/// 用于发现在每个main_key分组（如每个模型）中重复进行的参数扫描
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CrossGroupSummary {
+    // ————————————————————————————————————————————————————————————————————————
+    // 所有main_key分组中都是共有参数且取值相同的参数
+    // ————————————————————————————————————————————————————————————————————————
+    pub shared_common_params: BTreeMap<String, ParameterValue>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 各分组特有的共有参数，键为分组键，值为该分组内共有但不被所有分组共享的参数
+    // ————————————————————————————————————————————————————————————————————————
+    pub group_specific_common_params: BTreeMap<String, BTreeMap<String, ParameterValue>>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 在所有分组中都出现的变化参数组合（不含main_key），按首次出现顺序排列
+    // ————————————————————————————————————————————————————————————————————————
+    pub shared_configurations: Vec<BTreeMap<String, ParameterValue>>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 只在部分分组中出现的变化参数组合，键为分组键
+    // ————————————————————————————————————————————————————————————————————————
+    pub unique_configurations: BTreeMap<String, Vec<BTreeMap<String, ParameterValue>>>,
+}
+
+impl fmt::Display for CrossGroupSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Across main_key groups: {} shared common parameters, {} shared configurations",
+            self.shared_common_params.len(),
+            self.shared_configurations.len()
+        )?;
+        for (group_key, configurations) in &self.unique_configurations {
+            write!(
+                f,
+                "\n  {}: {} unique configurations",
+                group_key,
+                configurations.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 比较各main_key分组的共有参数和变化参数组合
+///
+/// # 参数
+/// * `versions` - `create_version_data_list`返回的版本列表（已删除分组内共有参数）
+/// * `group_common_hparams` - `create_version_data_list`返回的各分组共有参数
+/// * `main_keys` - 配置的main_key列表
+pub fn compare_main_key_groups(
+    versions: &[VersionData],
+    group_common_hparams: &HashMap<String, HashMap<String, ParameterValue>>,
+    main_keys: &[String],
+) -> CrossGroupSummary {
+    // 收集每个分组的变化参数组合，去除重复组合
+    let mut configurations: BTreeMap<String, Vec<BTreeMap<String, ParameterValue>>> =
+        BTreeMap::new();
+    for version in versions {
+        let Some(group_key) = main_key_group_key(&version.hparams, main_keys) else {
+            continue;
+        };
+        let configuration: BTreeMap<String, ParameterValue> = version
+            .hparams
+            .iter()
+            .filter(|(key, _)| !main_keys.contains(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let group_configurations = configurations.entry(group_key).or_default();
+        if !group_configurations.contains(&configuration) {
+            group_configurations.push(configuration);
+        }
+    }
+
+    let mut summary = CrossGroupSummary::default();
+    if configurations.is_empty() {
+        return summary;
+    }
+
+    // 只包含单个版本的分组没有共有参数记录，视为空
+    let empty = HashMap::new();
+    let common_of = |group_key: &str| group_common_hparams.get(group_key).unwrap_or(&empty);
+
+    // 共有参数：在所有分组中取值都相同时为共享参数，否则归为各分组特有
+    let first_group = configurations
+        .keys()
+        .next()
+        .expect("configurations is not empty");
+    for (key, value) in common_of(first_group) {
+        if configurations
+            .keys()
+            .all(|group_key| common_of(group_key).get(key) == Some(value))
+        {
+            summary.shared_common_params.insert(key.clone(), value.clone());
+        }
+    }
+    for group_key in configurations.keys() {
+        let specific: BTreeMap<String, ParameterValue> = common_of(group_key)
+            .iter()
+            .filter(|(key, _)| !summary.shared_common_params.contains_key(*key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        if !specific.is_empty() {
+            summary
+                .group_specific_common_params
+                .insert(group_key.clone(), specific);
+        }
+    }
+
+    // 变化参数组合：在所有分组中都出现时为共享组合，否则归为各分组特有
+    for configuration in &configurations[first_group] {
+        if configurations
+            .values()
+            .all(|group_configurations| group_configurations.contains(configuration))
+        {
+            summary.shared_configurations.push(configuration.clone());
+        }
+    }
+    for (group_key, group_configurations) in &configurations {
+        let unique: Vec<_> = group_configurations
+            .iter()
+            .filter(|configuration| !summary.shared_configurations.contains(configuration))
+            .cloned()
+            .collect();
+        if !unique.is_empty() {
+            summary.unique_configurations.insert(group_key.clone(), unique);
+        }
+    }
+
+    summary
+}
+
+/// 实验组与参考参数集之间的距离
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupDistance {
+    pub group_index: usize,          // 实验组在分组列表中的索引
+    pub distance: usize,             // 差异参数的数量
+    pub differing_keys: Vec<String>, // 差异参数的键名列表
+}
+
+/// 将所有实验组与参考参数集比较，按差异参数数量从小到大排序
+///
+/// 参考参数集会先按配置过滤被忽略的参数，并且只保留在任意实验组中出现过的参数，
+/// 因为所有版本共有的参数在创建版本数据时已被删除，不应计入差异
+///
+/// # 参数
+/// * `reference` - 参考hparams文件解析得到的参数映射
+/// * `groups` - 实验组列表
+/// * `config` - 包含忽略参数和容差配置的配置对象
+///
+/// # 返回值
+/// * `Vec<GroupDistance>` - 按距离升序排列的比较结果，距离相同时按组索引排列
+pub fn rank_groups_by_reference(
+    reference: &HashMap<String, ParameterValue>,
+    groups: &[ExperimentGroup],
+    config: &Config,
+) -> Vec<GroupDistance> {
+    let known_keys: HashSet<&String> = groups
+        .iter()
+        .flat_map(|group| group.base_parameters.keys())
+        .collect();
+
+    let reference: HashMap<String, ParameterValue> = filter_parameters(
+        reference,
+        &config.ignored_parameters.parameters,
+        config.grouping.effective_grouping_parameters(),
+    )
+    .into_iter()
+    .filter(|(key, _)| known_keys.contains(key))
+    .collect();
+
+    let mut distances: Vec<GroupDistance> = groups
+        .iter()
+        .enumerate()
+        .map(|(group_index, group)| {
+            let differing_keys =
+                find_different_parameters(&reference, &group.base_parameters, config);
+            GroupDistance {
+                group_index,
+                distance: differing_keys.len(),
+                differing_keys,
+            }
+        })
+        .collect();
+
+    distances.sort_by_key(|d| (d.distance, d.group_index));
+    distances
+}
+
+/// 贪心地查找能区分所有实验组的最小参数集合
+///
+/// 每一步选择能区分最多尚未区分的组对的参数（数量相同时取名称靠前的参数），
+/// 直到所有组的签名互不相同，或剩余的组对无法被任何参数区分（基础参数完全相同）。
+/// 两组的参数值在容差外不同，或参数只存在于其中一组时，视为该参数能区分这两组。
+/// 未分组的实验组不参与计算。返回的参数按选择顺序排列，可作为`label_parameters`或
+/// `grouping_parameters`的建议值
+pub fn minimal_distinguishing_keys(groups: &[ExperimentGroup], config: &Config) -> Vec<String> {
+    let groups: Vec<&ExperimentGroup> = groups
+        .iter()
+        .filter(|group| group.group_id != UNGROUPED_GROUP_ID)
+        .collect();
+    let distinguishes = |i: usize, j: usize, key: &str| match (
+        groups[i].base_parameters.get(key),
+        groups[j].base_parameters.get(key),
+    ) {
+        (Some(a), Some(b)) => !a.equals_with_tolerance_for_key(b, key, &config.tolerance),
+        (None, None) => false,
+        _ => true,
+    };
+
+    let candidates: BTreeSet<&String> = groups
+        .iter()
+        .flat_map(|group| group.base_parameters.keys())
+        .collect();
+    let mut pairs: Vec<(usize, usize)> = (0..groups.len())
+        .flat_map(|i| ((i + 1)..groups.len()).map(move |j| (i, j)))
+        .collect();
+    let mut chosen: Vec<String> = Vec::new();
+
+    while !pairs.is_empty() {
+        let best = candidates
+            .iter()
+            .filter(|key| !chosen.contains(key))
+            .map(|key| {
+                let count = pairs
+                    .iter()
+                    .filter(|(i, j)| distinguishes(*i, *j, key))
+                    .count();
+                (count, *key)
+            })
+            .max_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(a.1)));
+        let Some((_, key)) = best.filter(|(count, _)| *count > 0) else {
+            break;
+        };
+        pairs.retain(|(i, j)| !distinguishes(*i, *j, key));
+        chosen.push(key.clone());
+    }
+
+    chosen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::write_version_fixtures;
+
+    // 辅助函数：创建测试配置
+    fn create_test_config() -> Config {
+        Config::builder()
+            .ignored_parameters(["fold", "devices"])
+            .float_tolerance(0.001)
+            .similarity_threshold(2)
+            .build()
+    }
+
+    // 辅助函数：创建带有main_key的测试配置
+    fn create_test_config_with_main_key(main_key: Option<Vec<String>>) -> Config {
+        let mut config = create_test_config();
+        config.grouping.main_key = main_key;
+        config
+    }
+
+    // 测试过滤参数功能
+    #[test]
+    fn test_filter_parameters() {
+        let mut hparams = HashMap::new();
+        hparams.insert(
+            "model".to_string(),
+            ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+        );
+        hparams.insert(
+            "fold".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Int(1)),
+        );
+        hparams.insert(
+            "devices".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Int(2)),
+        );
+        hparams.insert(
+            "lr".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.001)),
+        );
+
+        let ignored_params = vec!["fold".to_string(), "devices".to_string()];
+        let grouping_params: Option<Vec<String>> = None;
+
+        let filtered = filter_parameters(&hparams, &ignored_params, grouping_params.as_deref());
 
         assert!(filtered.contains_key("model"));
         assert!(filtered.contains_key("lr"));
@@ -548,6 +1520,38 @@ mod tests {
         assert!(!filtered.contains_key("devices"));
     }
 
+    #[test]
+    fn test_filter_parameters_by_value_pattern() {
+        let output_dir = |path: &str| {
+            let mut hparams = HashMap::new();
+            hparams.insert(
+                "output_dir".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String(path.to_string())),
+            );
+            hparams.insert(
+                "seed".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(42)),
+            );
+            hparams
+        };
+        let ignored_params = vec!["output_dir=re:^/tmp/".to_string(), "seed".to_string()];
+
+        // 值匹配模式时忽略
+        let filtered = filter_parameters(&output_dir("/tmp/run_1"), &ignored_params, None);
+        assert!(!filtered.contains_key("output_dir"));
+        assert!(!filtered.contains_key("seed"));
+
+        // 值不匹配时保留，按名称忽略的参数仍被排除
+        let filtered = filter_parameters(&output_dir("/data/exp/run_1"), &ignored_params, None);
+        assert!(filtered.contains_key("output_dir"));
+        assert!(!filtered.contains_key("seed"));
+
+        // 指定分组参数时同样生效
+        let grouping = vec!["output_dir".to_string()];
+        let filtered = filter_parameters(&output_dir("/tmp/x"), &ignored_params, Some(&grouping));
+        assert!(filtered.is_empty());
+    }
+
     // 测试递归过滤嵌套的参数（现在是扁平结构）
     #[test]
     fn test_filter_nested_parameters() {
@@ -580,7 +1584,7 @@ mod tests {
         let ignored_params = vec!["trainer-devices".to_string(), "config-fold".to_string()];
         let grouping_params: Option<Vec<String>> = None;
 
-        let filtered = filter_parameters(&hparams, &ignored_params, &grouping_params);
+        let filtered = filter_parameters(&hparams, &ignored_params, grouping_params.as_deref());
 
         // 验证参数
         assert!(filtered.contains_key("model"));
@@ -614,7 +1618,7 @@ mod tests {
         let ignored_params = vec!["fold".to_string()];
         let grouping_params = Some(vec!["model".to_string(), "lr".to_string()]);
 
-        let filtered = filter_parameters(&hparams, &ignored_params, &grouping_params);
+        let filtered = filter_parameters(&hparams, &ignored_params, grouping_params.as_deref());
 
         assert!(filtered.contains_key("model"));
         assert!(filtered.contains_key("lr"));
@@ -646,16 +1650,12 @@ mod tests {
     // 测试扁平结构的嵌套参数比较
     #[test]
     fn test_count_different_parameters_nested() {
-        let config = create_test_config();
         // 创建一个没有浮点数容差的配置，确保0.001和0.002被识别为不同的值
-        let strict_config = Config {
-            tolerance: ToleranceConfig {
-                float_tolerance: 0.0,
-                int_tolerance: 0,
-                string_case_sensitive: false,
-            },
-            ..config
-        };
+        let strict_config = Config::builder()
+            .ignored_parameters(["fold", "devices"])
+            .float_tolerance(0.0)
+            .similarity_threshold(2)
+            .build();
 
         let mut params1 = HashMap::new();
         params1.insert(
@@ -733,6 +1733,431 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    // 测试贪心查找区分所有实验组的最小参数集合
+    #[test]
+    fn test_minimal_distinguishing_keys() {
+        let make_group = |id: &str, params: &[(&str, ParameterValue)]| ExperimentGroup {
+            group_id: id.to_string(),
+            base_parameters: params
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+            member_versions: Vec::new(),
+        };
+        let string = |s: &str| ParameterValue::Basic(BasicParameterValue::String(s.to_string()));
+        let float = |f: f64| ParameterValue::Basic(BasicParameterValue::Float(f));
+        let int = |i: i64| ParameterValue::Basic(BasicParameterValue::Int(i));
+        let config = create_test_config();
+
+        // 2x2网格：需要model和lr共同区分，seed对所有组相同
+        let mut groups = vec![
+            make_group(
+                "a",
+                &[
+                    ("model", string("cnn")),
+                    ("lr", float(0.01)),
+                    ("seed", int(1)),
+                ],
+            ),
+            make_group(
+                "b",
+                &[
+                    ("model", string("cnn")),
+                    ("lr", float(0.1)),
+                    ("seed", int(1)),
+                ],
+            ),
+            make_group(
+                "c",
+                &[
+                    ("model", string("rnn")),
+                    ("lr", float(0.01)),
+                    ("seed", int(1)),
+                ],
+            ),
+            make_group(
+                "d",
+                &[
+                    ("model", string("rnn")),
+                    ("lr", float(0.1)),
+                    ("seed", int(1)),
+                ],
+            ),
+        ];
+        assert_eq!(
+            minimal_distinguishing_keys(&groups, &config),
+            vec!["lr", "model"]
+        );
+
+        // 单个参数能区分所有组时只返回该参数
+        for (index, group) in groups.iter_mut().enumerate() {
+            group
+                .base_parameters
+                .insert("run".to_string(), int(index as i64));
+        }
+        assert_eq!(minimal_distinguishing_keys(&groups, &config), vec!["run"]);
+
+        // 参数只存在于部分组中也能区分；容差内相等的值不能区分
+        let groups = vec![
+            make_group("a", &[("lr", float(0.0100))]),
+            make_group("b", &[("lr", float(0.0105)), ("warmup", int(10))]),
+        ];
+        assert_eq!(
+            minimal_distinguishing_keys(&groups, &config),
+            vec!["warmup"]
+        );
+
+        // 无法区分或只有一组时返回空
+        let same = vec![
+            make_group("a", &[("lr", float(0.1))]),
+            make_group("b", &[("lr", float(0.1))]),
+        ];
+        assert!(minimal_distinguishing_keys(&same, &config).is_empty());
+        assert!(minimal_distinguishing_keys(&same[..1], &config).is_empty());
+    }
+
+    // 测试ignore_missing_keys：共有参数都相同、只多出新参数的版本分到同一组
+    #[test]
+    fn test_ignore_missing_keys() {
+        let make_version = |version_num: u32, extra: Option<i64>| {
+            let mut hparams = HashMap::from([
+                (
+                    "model".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+                ),
+                (
+                    "lr".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::Float(0.01)),
+                ),
+            ]);
+            if let Some(extra) = extra {
+                hparams.insert(
+                    "warmup_steps".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::Int(extra)),
+                );
+            }
+            VersionData {
+                hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+        let versions = vec![
+            make_version(1, None),
+            make_version(2, Some(100)),
+            make_version(3, Some(100)),
+        ];
+        let mut config = Config::default();
+
+        // 默认缺失的参数算作差异，旧版本单独成组
+        assert_eq!(
+            count_different_parameters(&versions[0].hparams, &versions[1].hparams, &config),
+            1
+        );
+        let groups = group_versions(&config, versions.clone()).unwrap();
+        assert_eq!(groups.len(), 2);
+
+        config.grouping.ignore_missing_keys = true;
+        assert_eq!(
+            count_different_parameters(&versions[0].hparams, &versions[1].hparams, &config),
+            0
+        );
+        for strategy in [GroupingStrategy::Hash, GroupingStrategy::Cluster] {
+            config.grouping.grouping_strategy = strategy;
+            let groups = group_versions(&config, versions.clone()).unwrap();
+            assert_eq!(groups.len(), 1);
+            assert_eq!(groups[0].member_versions.len(), 3);
+        }
+
+        // 共有参数不同时仍然分开
+        let mut different = make_version(4, Some(100));
+        different.hparams.insert(
+            "lr".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.1)),
+        );
+        assert_eq!(
+            count_different_parameters(&versions[0].hparams, &different.hparams, &config),
+            1
+        );
+        // 差异展示仍然列出缺失的参数
+        assert_eq!(
+            find_different_parameters(&versions[0].hparams, &versions[1].hparams, &config),
+            vec!["warmup_steps"]
+        );
+    }
+
+    // 测试inf/-inf/NaN的分桶与分组
+    #[test]
+    fn test_non_finite_float_grouping() {
+        // 非有限值各自单独成桶，所有NaN落入同一个桶
+        let nan_bucket = float_tolerance_bucket(f64::NAN, 1e-6);
+        assert_eq!(nan_bucket, float_tolerance_bucket(-f64::NAN, 1e-6));
+        assert_ne!(nan_bucket, float_tolerance_bucket(f64::INFINITY, 1e-6));
+        assert_ne!(
+            float_tolerance_bucket(f64::INFINITY, 1e-6),
+            float_tolerance_bucket(f64::NEG_INFINITY, 1e-6)
+        );
+        // 舍入时溢出的极大有限值不与inf混在一起
+        assert_ne!(
+            float_tolerance_bucket(f64::MAX, 1e-6),
+            float_tolerance_bucket(f64::INFINITY, 1e-6)
+        );
+        assert_eq!(
+            float_tolerance_bucket(-0.0, 0.0),
+            float_tolerance_bucket(0.0, 0.0)
+        );
+
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let contents = [
+            (1, "model: cnn\nlr: .nan\n"),
+            (2, "model: cnn\nlr: .NaN\n"),
+            (3, "model: cnn\nlr: .inf\n"),
+            (4, "model: cnn\nlr: -.inf\n"),
+            (5, "model: cnn\nlr: .inf\n"),
+        ];
+        let hparams_files = write_version_fixtures(temp_dir.path(), &contents);
+        let config = Config::default();
+        let (versions, _, _) =
+            create_version_data_list(&config, &hparams_files).expect("Failed to load versions");
+
+        // 多次分组结果一致：两个NaN一组，两个inf一组，-inf单独一组
+        for _ in 0..2 {
+            let groups = group_versions(&config, versions.clone()).unwrap();
+            let mut members: Vec<Vec<u32>> = groups
+                .iter()
+                .map(|group| {
+                    let mut nums: Vec<u32> = group
+                        .member_versions
+                        .iter()
+                        .map(|v| v.version_num)
+                        .collect();
+                    nums.sort();
+                    nums
+                })
+                .collect();
+            members.sort();
+            assert_eq!(members, vec![vec![1, 2], vec![3, 5], vec![4]]);
+        }
+    }
+
+    // 测试整数分桶对负数的处理
+    #[test]
+    fn test_int_tolerance_bucket_negative() {
+        // 容差为0时每个整数各自成桶
+        assert_ne!(int_tolerance_bucket(-1, 0), int_tolerance_bucket(-2, 0));
+        assert_eq!(int_tolerance_bucket(-3, 0), int_tolerance_bucket(-3, 0));
+
+        // 容差为偶数时，负数与对应正数的分桶关于0对称
+        for value in 0..20 {
+            assert_eq!(
+                int_tolerance_bucket(-value, 2),
+                -int_tolerance_bucket(value, 2),
+                "Bucketing should be symmetric for {}",
+                value
+            );
+        }
+
+        // 差值在容差内的负整数落入同一个桶
+        assert_eq!(int_tolerance_bucket(-4, 2), int_tolerance_bucket(-2, 2));
+        assert_ne!(int_tolerance_bucket(-5, 2), int_tolerance_bucket(-1, 2));
+
+        // 容差为奇数时桶宽为偶数，分桶不关于0对称：0所在的桶为[-2, 1]
+        assert_eq!(int_tolerance_bucket(-2, 3), 0);
+        assert_eq!(int_tolerance_bucket(1, 3), 0);
+        assert_eq!(int_tolerance_bucket(2, 3), 1);
+        assert_eq!(int_tolerance_bucket(-3, 3), -1);
+        assert_eq!(int_tolerance_bucket(-6, 3), -1);
+        assert_eq!(int_tolerance_bucket(-7, 3), -2);
+        // 每个桶都是4个连续整数，桶内差值不超过容差
+        for value in -20..20 {
+            let bucket = int_tolerance_bucket(value, 3);
+            let members: Vec<i64> = (-30..30)
+                .filter(|&other| int_tolerance_bucket(other, 3) == bucket)
+                .collect();
+            assert_eq!(members.len(), 4, "Bucket of {} should hold 4 values", value);
+            assert_eq!(members[3] - members[0], 3);
+        }
+
+        // 极端值不应溢出
+        assert_eq!(int_tolerance_bucket(i64::MAX, 0), i64::MAX);
+        assert_eq!(int_tolerance_bucket(i64::MIN, 0), i64::MIN);
+        let _ = int_tolerance_bucket(i64::MAX, i64::MAX);
+    }
+
+    // 测试容差范围内的整数产生相同的哈希值
+    #[test]
+    fn test_compute_params_hash_int_tolerance() {
+        let mut config = create_test_config();
+        config.tolerance.int_tolerance = 2;
+
+        let params_with = |epochs: i64| {
+            let mut params = HashMap::new();
+            params.insert(
+                "epochs".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(epochs)),
+            );
+            params
+        };
+
+        // 同一个桶内的整数差值不超过容差
+        assert_eq!(
+            compute_params_hash(&params_with(5), &config),
+            compute_params_hash(&params_with(7), &config)
+        );
+        assert_eq!(
+            compute_params_hash(&params_with(-4), &config),
+            compute_params_hash(&params_with(-2), &config)
+        );
+        assert!(
+            params_with(-4)["epochs"]
+                .equals_with_tolerance(&params_with(-2)["epochs"], &config.tolerance)
+        );
+
+        // 超出容差的整数产生不同的哈希值
+        assert_ne!(
+            compute_params_hash(&params_with(1), &config),
+            compute_params_hash(&params_with(10), &config)
+        );
+    }
+
+    // 测试只用id_parameters计算组ID，新增无关参数时组ID不变
+    #[test]
+    fn test_id_parameters() {
+        let mut config = create_test_config();
+        let base = HashMap::from([
+            (
+                "model".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+            ),
+            (
+                "lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(0.01)),
+            ),
+        ]);
+        let mut extended = base.clone();
+        extended.insert(
+            "warmup_steps".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Int(500)),
+        );
+        let mut changed = base.clone();
+        changed.insert(
+            "lr".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.1)),
+        );
+
+        // 未指定时新增参数改变组ID
+        assert_ne!(
+            compute_params_hash(&base, &config),
+            compute_params_hash(&extended, &config)
+        );
+
+        config.grouping.id_parameters = Some(vec!["model".to_string(), "lr".to_string()]);
+        let base_id = compute_params_hash(&base, &config);
+        assert_eq!(compute_params_hash(&extended, &config), base_id);
+        assert_ne!(compute_params_hash(&changed, &config), base_id);
+
+        // 组ID使用完整参数，id_parameters作为共有参数被删除后组ID不变
+        let version = VersionData {
+            hparams: HashMap::from([(
+                "warmup_steps".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(500)),
+            )]),
+            full_hparams: extended,
+            ..VersionData::for_test(1)
+        };
+        let groups = group_versions(&config, vec![version]).expect("Failed to group versions");
+        assert_eq!(groups[0].group_id, base_id);
+    }
+
+    // 测试id_parameters取值相同、其他参数不同的实验组得到不同的组ID
+    #[test]
+    fn test_id_parameters_unique_group_ids() {
+        let mut config = create_test_config();
+        config.grouping.id_parameters = Some(vec!["model".to_string()]);
+        let make_version = |version_num: u32, lr: f64| {
+            let hparams = HashMap::from([
+                (
+                    "model".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+                ),
+                (
+                    "lr".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::Float(lr)),
+                ),
+            ]);
+            VersionData {
+                full_hparams: hparams.clone(),
+                hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+        let versions = vec![
+            make_version(1, 0.01),
+            make_version(2, 0.1),
+            make_version(3, 0.01),
+            make_version(4, 1.0),
+        ];
+        let groups = group_versions(&config, versions).expect("Failed to group versions");
+        assert_eq!(groups.len(), 3);
+
+        let ids: HashSet<&str> = groups.iter().map(|g| g.group_id.as_str()).collect();
+        assert_eq!(ids.len(), 3, "Group IDs must be unique: {:?}", ids);
+        // 代表版本号最小的组保留原ID，其余组按代表版本号加后缀
+        let id_of = |version_num: u32| {
+            groups
+                .iter()
+                .find(|g| g.representative().version_num == version_num)
+                .map(|g| g.group_id.clone())
+                .unwrap()
+        };
+        let base_id = id_of(1);
+        assert_eq!(id_of(2), format!("{}-2", base_id));
+        assert_eq!(id_of(4), format!("{}-3", base_id));
+    }
+
+    // 测试按参数名覆盖的容差同时作用于比较和哈希
+    #[test]
+    fn test_per_parameter_tolerance() {
+        let mut config = create_test_config();
+        config
+            .tolerance
+            .per_parameter_tolerance
+            .insert("lr".to_string(), 1e-6);
+        config
+            .tolerance
+            .per_parameter_tolerance
+            .insert("dropout".to_string(), 0.1);
+
+        let params_with = |lr: f64, dropout: f64| {
+            let mut params = HashMap::new();
+            params.insert(
+                "lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(lr)),
+            );
+            params.insert(
+                "dropout".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(dropout)),
+            );
+            params
+        };
+
+        // lr相差0.0004，在全局容差0.001内，但超出lr的参数容差
+        let diff = find_different_parameters(
+            &params_with(0.0010, 0.5),
+            &params_with(0.0014, 0.5),
+            &config,
+        );
+        assert_eq!(diff, vec!["lr".to_string()]);
+
+        // dropout相差0.02，超出全局容差，但在dropout的参数容差内
+        let a = params_with(0.001, 0.50);
+        let b = params_with(0.001, 0.52);
+        assert_eq!(count_different_parameters(&a, &b, &config), 0);
+        assert_eq!(
+            compute_params_hash(&a, &config),
+            compute_params_hash(&b, &config)
+        );
+    }
+
     // 测试版本分组功能
     // #[test]
     // fn test_group_versions() {
@@ -823,12 +2248,15 @@ mod tests {
 
         // 调用被测试的函数
         let hparams_files = vec![file1_path, file2_path, file3_path];
-        let (versions, _group_common_hparams) = create_version_data_list(&config, &hparams_files)
+        let (versions, _group_common_hparams, _) = create_version_data_list(&config, &hparams_files)
             .expect("Failed to create version data list");
 
         // 验证结果：
-        // 1. 应该有3个版本
+        // 1. 应该有3个版本，并记录了读取的文件名
         assert_eq!(versions.len(), 3, "Should have 3 versions");
+        for version in &versions {
+            assert_eq!(version.hparams_file.as_deref(), Some("hparams.yaml"));
+        }
 
         // 2. 所有版本都不应该包含共同参数（model和batch_size）
         for version in &versions {
@@ -889,7 +2317,7 @@ mod tests {
 
         // 调用create_version_data_list进行参数过滤
         let hparams_files = vec![file1, file2];
-        let (versions, _) = create_version_data_list(&config, &hparams_files)
+        let (versions, _, _) = create_version_data_list(&config, &hparams_files)
             .expect("Failed to create version data list");
 
         // 验证参数过滤结果
@@ -979,7 +2407,7 @@ mod tests {
             rnn_file1.clone(),
             rnn_file2.clone(),
         ];
-        let (versions, group_common_hparams) = create_version_data_list(&config, &hparams_files)
+        let (versions, group_common_hparams, _) = create_version_data_list(&config, &hparams_files)
             .expect("Failed to create version data list");
 
         // 验证分组内相同hparams数据
@@ -1111,7 +2539,7 @@ mod tests {
 
         // 调用被测试的函数
         let hparams_files = vec![file_path];
-        let (versions, _) = create_version_data_list(&config, &hparams_files)
+        let (versions, _, _) = create_version_data_list(&config, &hparams_files)
             .expect("Failed to create version data list");
 
         // 验证结果
@@ -1226,7 +2654,7 @@ mod tests {
             cnn_cifar_file2.clone(),
             rnn_mnist_file1.clone(),
         ];
-        let (versions, group_common_hparams) = create_version_data_list(&config, &hparams_files)
+        let (versions, group_common_hparams, _) = create_version_data_list(&config, &hparams_files)
             .expect("Failed to create version data list");
 
         // 验证分组内相同hparams数据
@@ -1452,29 +2880,849 @@ mod tests {
         temp_dir.close().expect("Failed to clean up temp directory");
     }
 
-    // 测试相似组查找功能
+    // 创建混合了包含和缺少main_key的版本的测试文件
+    fn write_mixed_main_key_fixture(dir: &std::path::Path) -> Vec<PathBuf> {
+        let contents = [
+            (1, "model: cnn\nlr: 0.001\n"),
+            (2, "lr: 0.01\nbatch_size: 32\n"),
+            (3, "model: cnn\nlr: 0.01\n"),
+            (4, "lr: 0.1\nbatch_size: 64\n"),
+        ];
+        write_version_fixtures(dir, &contents)
+    }
+
+    // 测试main_key_missing_policy的三种处理方式
     #[test]
-    fn test_find_similar_groups1() {
+    fn test_main_key_missing_policy() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let hparams_files = write_mixed_main_key_fixture(temp_dir.path());
+        let mut config = create_test_config_with_main_key(Some(vec!["model".to_string()]));
+
+        // error：保持原有行为，直接报错
+        config.grouping.main_key_missing_policy = MainKeyMissingPolicy::Error;
+        assert!(create_version_data_list(&config, &hparams_files).is_err());
+
+        // skip：丢弃缺少main_key的版本
+        config.grouping.main_key_missing_policy = MainKeyMissingPolicy::Skip;
+        let (versions, _, _) = create_version_data_list(&config, &hparams_files)
+            .expect("Skip policy should not error");
+        let version_nums: Vec<u32> = versions.iter().map(|v| v.version_num).collect();
+        assert_eq!(version_nums, vec![1, 3]);
+        let groups = group_versions(&config, versions).unwrap();
+        assert!(groups.iter().all(|g| g.group_id != UNGROUPED_GROUP_ID));
+
+        // ungrouped：保留这些版本并放入最后一个未分组的实验组
+        config.grouping.main_key_missing_policy = MainKeyMissingPolicy::Ungrouped;
+        let (versions, _, _) = create_version_data_list(&config, &hparams_files)
+            .expect("Ungrouped policy should not error");
+        assert_eq!(versions.len(), 4);
+        let groups = group_versions(&config, versions).unwrap();
+        let ungrouped = groups.last().expect("Groups should not be empty");
+        assert_eq!(ungrouped.group_id, UNGROUPED_GROUP_ID);
+        let ungrouped_nums: Vec<u32> = ungrouped
+            .member_versions
+            .iter()
+            .map(|v| v.version_num)
+            .collect();
+        assert_eq!(ungrouped_nums, vec![2, 4]);
+        let grouped_nums: Vec<u32> = groups[..groups.len() - 1]
+            .iter()
+            .flat_map(|g| g.member_versions.iter().map(|v| v.version_num))
+            .collect();
+        assert!(grouped_nums.iter().all(|num| [1, 3].contains(num)));
+        assert_eq!(grouped_nums.len(), 2);
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    // 测试不同根目录下的相同版本号应被检测为重复
+    #[test]
+    fn test_duplicate_versions_across_roots() {
         let config = create_test_config();
 
-        // 创建几个VersionData实例用于member_versions
-        let version1 = VersionData {
-            path: "version_001".to_string().into(),
-            version_num: 1,
-            hparams: HashMap::new(),
-        };
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
 
-        let version2 = VersionData {
-            path: "version_002".to_string().into(),
-            version_num: 2,
-            hparams: HashMap::new(),
-        };
+        // 在两个不同的根目录下各创建一个version_1
+        let file1 = temp_dir.path().join("root_a/version_1/hparams.yaml");
+        let file2 = temp_dir.path().join("root_b/version_1/hparams.yaml");
+        let file3 = temp_dir.path().join("root_b/version_2/hparams.yaml");
 
-        let version3 = VersionData {
-            path: "version_003".to_string().into(),
-            version_num: 3,
-            hparams: HashMap::new(),
-        };
+        for file in [&file1, &file2, &file3] {
+            std::fs::create_dir_all(file.parent().unwrap()).expect("Failed to create directory");
+        }
+        std::fs::write(&file1, "model: cnn\nlr: 0.001\n").expect("Failed to write file1");
+        std::fs::write(&file2, "model: cnn\nlr: 0.01\n").expect("Failed to write file2");
+        std::fs::write(&file3, "model: cnn\nlr: 0.1\n").expect("Failed to write file3");
+
+        let hparams_files = vec![file1.clone(), file2.clone(), file3];
+        let (versions, _, report) = create_version_data_list(&config, &hparams_files)
+            .expect("Failed to create version data list");
+
+        // 重复的版本仍然保留为不同的行，警告记录在加载报告中
+        assert_eq!(versions.len(), 3);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].starts_with("Duplicate version number 1 found in: "));
+
+        let duplicates = find_duplicate_versions(&versions);
+        assert_eq!(duplicates.len(), 1, "Only version 1 should be duplicated");
+        let paths = duplicates.get(&1).expect("Version 1 should be reported");
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&file1.parent().unwrap().to_path_buf()));
+        assert!(paths.contains(&file2.parent().unwrap().to_path_buf()));
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    // 测试两个实验组基础参数之间的差异
+    #[test]
+    fn test_diff_groups() {
+        let mut config = create_test_config();
+        config.grouping.label_parameters = Some(vec!["model".to_string()]);
+        let string = |s: &str| ParameterValue::Basic(BasicParameterValue::String(s.to_string()));
+        let float = |n| ParameterValue::Basic(BasicParameterValue::Float(n));
+        let int = |n| ParameterValue::Basic(BasicParameterValue::Int(n));
+        let group = |group_id: &str, params: Vec<(&str, ParameterValue)>| ExperimentGroup {
+            group_id: group_id.to_string(),
+            base_parameters: params
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+            member_versions: Vec::new(),
+        };
+
+        let a = group(
+            "a1",
+            vec![
+                ("model", string("cnn")),
+                ("lr", float(0.01)),
+                ("dropout", float(0.1)),
+                ("batch_size", int(32)),
+            ],
+        );
+        let b = group(
+            "b2",
+            vec![
+                ("model", string("resnet")),
+                ("lr", float(0.0105)),
+                ("dropout", float(0.1)),
+                ("warmup", int(100)),
+            ],
+        );
+
+        let diff = diff_groups(&a, &b, &config);
+        assert_eq!(diff.left_group_id, "a1");
+        assert_eq!(diff.left_label, "model=cnn");
+        assert_eq!(diff.right_group_id, "b2");
+        assert_eq!(diff.right_label, "model=resnet");
+        // lr在容差内相等，dropout相同，均不列出；只在一侧存在的参数也列出
+        assert_eq!(
+            diff.differences,
+            vec![
+                ParameterDifference {
+                    key: "batch_size".to_string(),
+                    left: Some(int(32)),
+                    right: None,
+                },
+                ParameterDifference {
+                    key: "model".to_string(),
+                    left: Some(string("cnn")),
+                    right: Some(string("resnet")),
+                },
+                ParameterDifference {
+                    key: "warmup".to_string(),
+                    left: None,
+                    right: Some(int(100)),
+                },
+            ]
+        );
+
+        assert!(diff_groups(&a, &a, &config).differences.is_empty());
+    }
+
+    // 测试派生参数的计算以及按派生参数分组
+    #[test]
+    fn test_derived_parameters() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let contents = [
+            (1, "model: cnn\nbatch_size: 32\nnum_gpus: 4\n"),
+            (2, "model: cnn\nbatch_size: 64\nnum_gpus: 2\n"),
+            (3, "model: cnn\nbatch_size: 16\nnum_gpus: 4\n"),
+            (4, "model: cnn\nbatch_size: 16\n"),
+        ];
+        let hparams_files = write_version_fixtures(temp_dir.path(), &contents);
+
+        let mut config =
+            create_test_config_with_main_key(Some(vec!["effective_batch_size".to_string()]));
+        config.grouping.main_key_missing_policy = MainKeyMissingPolicy::Ungrouped;
+        config.grouping.group_by_all_parameters = false;
+        config.derived_parameters.parameters.insert(
+            "effective_batch_size".to_string(),
+            "batch_size * num_gpus".to_string(),
+        );
+        assert!(config.validate().is_ok());
+
+        let (versions, _, report) = create_version_data_list(&config, &hparams_files)
+            .expect("Failed to create version data list");
+        // 无法计算的版本只计数，不逐个版本给出警告
+        assert_eq!(
+            report.derived_unavailable,
+            BTreeMap::from([("effective_batch_size".to_string(), 1)])
+        );
+        assert_eq!(
+            report.warnings,
+            vec![
+                "Derived parameter 'effective_batch_size' (batch_size * num_gpus) is not available \
+                 for 1 versions (a referenced parameter is missing or not numeric)"
+                    .to_string()
+            ]
+        );
+        let effective: Vec<Option<&ParameterValue>> = versions
+            .iter()
+            .map(|v| v.full_hparams.get("effective_batch_size"))
+            .collect();
+        let int = |n| ParameterValue::Basic(BasicParameterValue::Int(n));
+        assert_eq!(
+            effective,
+            vec![Some(&int(128)), Some(&int(128)), Some(&int(64)), None]
+        );
+        assert_eq!(
+            versions[0].param_order.last().map(String::as_str),
+            Some("effective_batch_size")
+        );
+
+        // 有效批大小相同的版本1和版本2分到同一组，无法计算的版本4未分组
+        let groups = group_versions(&config, versions).unwrap();
+        let version_nums: Vec<Vec<u32>> = groups
+            .iter()
+            .map(|g| g.member_versions.iter().map(|v| v.version_num).collect())
+            .collect();
+        assert_eq!(version_nums, vec![vec![1, 2], vec![3], vec![4]]);
+        assert_eq!(groups[2].group_id, UNGROUPED_GROUP_ID);
+
+        // 与已有参数同名的派生参数不覆盖文件中的值，只记录一次
+        config
+            .derived_parameters
+            .parameters
+            .insert("batch_size".to_string(), "num_gpus * 2".to_string());
+        let (versions, _, report) = create_version_data_list(&config, &hparams_files)
+            .expect("Failed to create version data list");
+        assert_eq!(versions[0].full_hparams.get("batch_size"), Some(&int(32)));
+        assert_eq!(
+            report.derived_collisions,
+            BTreeSet::from(["batch_size".to_string()])
+        );
+
+        // 无法解析的表达式在加载配置时报错
+        config
+            .derived_parameters
+            .parameters
+            .insert("broken".to_string(), "batch_size *".to_string());
+        assert!(config.validate().is_err());
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    // 测试加载报告中的各项统计与fixture一致
+    #[test]
+    fn test_load_report_counts() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let mut hparams_files = write_mixed_main_key_fixture(&temp_dir.path().join("root_a"));
+        // 另一个根目录下重复的version_1，以及一个无法读取的文件
+        let duplicate_files = write_mixed_main_key_fixture(&temp_dir.path().join("root_b"));
+        hparams_files.push(duplicate_files[0].clone());
+        let missing_file = temp_dir.path().join("root_c/version_9/hparams.yaml");
+        hparams_files.push(missing_file.clone());
+        // 无法解析的指标文件不影响加载，只记录警告
+        std::fs::write(
+            temp_dir.path().join("root_a/version_3/metrics.csv"),
+            "epoch,val_loss\n0,0.5,0.1\n",
+        )
+        .expect("Failed to write metrics file");
+
+        let mut config = create_test_config_with_main_key(Some(vec!["model".to_string()]));
+        config.grouping.main_key_missing_policy = MainKeyMissingPolicy::Skip;
+        let (versions, _, report) = create_version_data_list(&config, &hparams_files)
+            .expect("Failed to create version data list");
+
+        assert_eq!(report.config_path, None);
+        assert_eq!(report.files_found, 6);
+        assert_eq!(report.files_parsed, 5);
+        assert_eq!(report.failed_files, vec![missing_file]);
+        assert_eq!(report.skipped_versions, vec![2, 4]);
+        assert!(
+            report
+                .warnings
+                .contains(&"Skipping version 2 because it is missing main_key 'model'".to_string())
+        );
+        assert_eq!(report.duplicate_versions, vec![1]);
+        assert_eq!(report.versions_loaded, versions.len());
+        assert_eq!(report.versions_loaded, 3);
+        assert!(
+            report
+                .warnings
+                .iter()
+                .any(|warning| warning.starts_with("Failed to parse metrics file"))
+        );
+        assert_eq!(
+            report.summary(),
+            "Loaded 3 versions from 6 hparams files (1 failed to parse, \
+             2 skipped for missing main_key, 1 duplicate version numbers)"
+        );
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    // 测试解析警告和解析失败的原因记录在加载报告中，而不是直接打印
+    #[test]
+    fn test_load_report_parse_warnings() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let hparams_files = write_version_fixtures(
+            temp_dir.path(),
+            &[(1, "lr: 0.01\nlr: 0.1\n"), (2, "lr: [unclosed\n")],
+        );
+        let mut config = create_test_config();
+        config.general.warn_duplicate_keys = true;
+
+        let (versions, _, report) = create_version_data_list(&config, &hparams_files)
+            .expect("Failed to create version data list");
+        assert_eq!(versions.len(), 1);
+        assert_eq!(report.failed_files, vec![hparams_files[1].clone()]);
+        assert!(report.oversized_files.is_empty());
+        assert_eq!(report.warnings.len(), 2);
+        assert!(
+            report.warnings[0]
+                .starts_with(&format!("Failed to parse {}", hparams_files[1].display()))
+        );
+        assert!(report.warnings[1].starts_with("Duplicate key 'lr'"));
+
+        // 超过大小限制的文件单独记录
+        config.general.max_hparams_file_bytes = 15;
+        let (versions, _, report) = create_version_data_list(&config, &hparams_files)
+            .expect("Failed to create version data list");
+        assert!(versions.is_empty());
+        assert_eq!(report.failed_files, hparams_files);
+        assert_eq!(report.oversized_files, vec![hparams_files[0].clone()]);
+    }
+
+    // 测试检查参数在各版本中的出现情况
+    #[test]
+    fn test_orphan_parameters() {
+        let make_version = |version_num: u32, keys: &[&str]| {
+            let full_hparams: HashMap<String, ParameterValue> = keys
+                .iter()
+                .map(|key| {
+                    (
+                        key.to_string(),
+                        ParameterValue::Basic(BasicParameterValue::Int(1)),
+                    )
+                })
+                .collect();
+            VersionData {
+                full_hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+        let versions = vec![
+            make_version(1, &["learning_rate", "batch_size"]),
+            make_version(2, &["lerning_rate", "batch_size"]),
+            make_version(3, &["learning_rate", "batch_size", "seed"]),
+        ];
+
+        assert_eq!(
+            orphan_parameters(&versions),
+            vec![("lerning_rate".to_string(), 2), ("seed".to_string(), 3)]
+        );
+        // 少于两个版本时不报告任何参数
+        assert!(orphan_parameters(&versions[..1]).is_empty());
+        assert!(orphan_parameters(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_type_inconsistent_keys() {
+        let make_version = |version_num: u32, lr: BasicParameterValue| {
+            let mut full_hparams = HashMap::new();
+            full_hparams.insert("lr".to_string(), ParameterValue::Basic(lr));
+            full_hparams.insert(
+                "batch_size".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(32)),
+            );
+            VersionData {
+                full_hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+        let versions = vec![
+            make_version(1, BasicParameterValue::Int(1)),
+            make_version(2, BasicParameterValue::Float(0.5)),
+            make_version(3, BasicParameterValue::Float(1.0)),
+        ];
+
+        assert_eq!(
+            type_inconsistent_keys(&versions),
+            vec![("lr".to_string(), vec!["float", "int"])]
+        );
+        assert!(type_inconsistent_keys(&versions[1..]).is_empty());
+    }
+
+    #[test]
+    fn test_check_key_presence() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+
+        let contents = [
+            (3, "model: cnn\nlr: 0.001\n"),
+            (1, "lr: 0.01\nbatch_size: 32\n"),
+            (2, "model: rnn\nlr: 0.1\n"),
+            (4, "optimizer:\n  model: adam\n"),
+        ];
+        let hparams_files = write_version_fixtures(temp_dir.path(), &contents);
+
+        let presence = check_key_presence(&hparams_files, "model", &ParseOptions::default())
+            .expect("Check should not abort");
+        assert_eq!(presence.with_key, vec![2, 3]);
+        // 嵌套参数展平后的键名不同
+        assert_eq!(presence.without_key, vec![1, 4]);
+
+        let presence =
+            check_key_presence(&hparams_files, "lr", &ParseOptions::default()).unwrap();
+        assert_eq!(presence.with_key, vec![1, 2, 3]);
+        assert_eq!(presence.without_key, vec![4]);
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    // 测试参考hparams文件的解析和按距离排序
+    #[test]
+    fn test_rank_groups_by_reference() {
+        let config = create_test_config();
+
+        let make_group =
+            |id: &str, version_num: u32, params: &[(&str, ParameterValue)]| ExperimentGroup {
+                group_id: id.to_string(),
+                base_parameters: params
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.clone()))
+                    .collect(),
+                member_versions: vec![VersionData::for_test(version_num)],
+            };
+        let string = |s: &str| ParameterValue::Basic(BasicParameterValue::String(s.to_string()));
+        let float = |f: f64| ParameterValue::Basic(BasicParameterValue::Float(f));
+        let int = |i: i64| ParameterValue::Basic(BasicParameterValue::Int(i));
+
+        let groups = vec![
+            make_group(
+                "far",
+                1,
+                &[
+                    ("model", string("rnn")),
+                    ("lr", float(0.1)),
+                    ("batch_size", int(64)),
+                ],
+            ),
+            make_group(
+                "exact",
+                2,
+                &[
+                    ("model", string("cnn")),
+                    ("lr", float(0.01)),
+                    ("batch_size", int(32)),
+                ],
+            ),
+            make_group(
+                "near",
+                3,
+                &[
+                    ("model", string("cnn")),
+                    ("lr", float(0.1)),
+                    ("batch_size", int(32)),
+                ],
+            ),
+        ];
+
+        // 参考文件中包含被忽略的参数和所有版本共有（已被删除）的参数
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let reference_file = temp_dir.path().join("baseline.yaml");
+        std::fs::write(
+            &reference_file,
+            "model: cnn\nlr: 0.01\nbatch_size: 32\nfold: 3\noptimizer: adam\n",
+        )
+        .expect("Failed to write reference file");
+
+        let reference = crate::yaml_parser::parse_hparams_file(&reference_file)
+            .expect("Failed to parse reference file");
+        let ranking = rank_groups_by_reference(&reference, &groups, &config);
+
+        let order: Vec<&str> = ranking
+            .iter()
+            .map(|d| groups[d.group_index].group_id.as_str())
+            .collect();
+        assert_eq!(order, vec!["exact", "near", "far"]);
+
+        assert_eq!(ranking[0].distance, 0);
+        assert!(ranking[0].differing_keys.is_empty());
+        assert_eq!(ranking[1].differing_keys, vec!["lr".to_string()]);
+        assert_eq!(
+            ranking[2].differing_keys,
+            vec![
+                "batch_size".to_string(),
+                "lr".to_string(),
+                "model".to_string()
+            ]
+        );
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    // 测试group_by_all_parameters为false且未指定分组参数时按main_key分组
+    #[test]
+    fn test_group_by_all_parameters_flag() {
+        let make_version = |version_num: u32, model: &str, lr: f64| {
+            let mut hparams = HashMap::new();
+            hparams.insert(
+                "model".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String(model.to_string())),
+            );
+            hparams.insert(
+                "lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(lr)),
+            );
+            VersionData {
+                hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+        let versions = vec![
+            make_version(1, "cnn", 0.01),
+            make_version(2, "cnn", 0.1),
+            make_version(3, "rnn", 0.1),
+        ];
+
+        // true：使用所有参数，三个版本各成一组
+        let config = create_test_config_with_main_key(Some(vec!["model".to_string()]));
+        let groups = group_versions(&config, versions.clone()).expect("Failed to group versions");
+        assert_eq!(groups.len(), 3);
+
+        // false：只按main_key分组，两个cnn版本合为一组
+        let mut config = create_test_config_with_main_key(Some(vec!["model".to_string()]));
+        config.grouping.group_by_all_parameters = false;
+        let groups = group_versions(&config, versions).expect("Failed to group versions");
+        assert_eq!(groups.len(), 2);
+        let members: Vec<u32> = groups[0]
+            .member_versions
+            .iter()
+            .map(|v| v.version_num)
+            .collect();
+        assert_eq!(members, vec![1, 2]);
+    }
+
+    // 测试启用unordered_map_lists后，映射列表顺序不同的版本被分到同一组
+    #[test]
+    fn test_cluster_grouping_strategy() {
+        let make_version = |version_num: u32, lr: f64| {
+            let mut hparams = HashMap::new();
+            hparams.insert(
+                "lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(lr)),
+            );
+            VersionData {
+                hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+        // 相邻版本的差值在容差内，但首尾版本超出容差
+        let versions = vec![
+            make_version(1, 0.100),
+            make_version(2, 0.108),
+            make_version(3, 0.116),
+            make_version(4, 0.500),
+        ];
+        let member_lists = |groups: &[ExperimentGroup]| {
+            let mut members: Vec<Vec<u32>> = groups
+                .iter()
+                .map(|g| g.member_versions.iter().map(|v| v.version_num).collect())
+                .collect();
+            members.sort();
+            members
+        };
+
+        // hash：与组的基准参数（最后一个版本）比较，version_1被拆分出去
+        let config = Config::builder().float_tolerance(0.01).build();
+        let groups = group_versions(&config, versions.clone()).unwrap();
+        assert_eq!(member_lists(&groups), vec![vec![1], vec![2, 3], vec![4]]);
+
+        // cluster：传递性合并为一组
+        let config = Config::builder()
+            .float_tolerance(0.01)
+            .grouping_strategy(GroupingStrategy::Cluster)
+            .build();
+        let groups = group_versions(&config, versions).unwrap();
+        assert_eq!(member_lists(&groups), vec![vec![1, 2, 3], vec![4]]);
+        assert_eq!(groups[0].member_versions.len(), 3);
+        assert_eq!(
+            groups[0].base_parameters.get("lr"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Float(0.116)))
+        );
+    }
+
+    #[test]
+    fn test_unordered_map_lists_grouping() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let contents = [
+            (1, "lr: 0.01\nlayers:\n  - {type: conv, size: 64}\n  - {type: fc, size: 10}\n"),
+            (2, "lr: 0.01\nlayers:\n  - {type: fc, size: 10}\n  - {type: conv, size: 64}\n"),
+            (3, "lr: 0.01\nlayers:\n  - {type: fc, size: 20}\n  - {type: conv, size: 64}\n"),
+        ];
+        let hparams_files = write_version_fixtures(temp_dir.path(), &contents);
+
+        let group_members = |config: &Config| -> Vec<Vec<u32>> {
+            let (versions, _, _) = create_version_data_list(config, &hparams_files)
+                .expect("Failed to create version data list");
+            let mut members: Vec<Vec<u32>> = group_versions(config, versions)
+                .expect("Failed to group versions")
+                .iter()
+                .map(|g| g.member_versions.iter().map(|v| v.version_num).collect())
+                .collect();
+            members.sort();
+            members
+        };
+
+        // 默认按索引展平，顺序不同的列表被视为不同
+        let mut config = create_test_config();
+        assert_eq!(group_members(&config), vec![vec![1], vec![2], vec![3]]);
+
+        // 作为无序集合比较时，版本1和2属于同一组
+        config.grouping.unordered_map_lists = true;
+        assert_eq!(group_members(&config), vec![vec![1, 2], vec![3]]);
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    #[test]
+    fn test_empty_list_hash_and_grouping() {
+        let config = create_test_config();
+        let params = |gpus: Option<ParameterValue>| {
+            let mut params = HashMap::from([(
+                "lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(0.01)),
+            )]);
+            if let Some(gpus) = gpus {
+                params.insert("gpus".to_string(), gpus);
+            }
+            params
+        };
+        let empty = || Some(ParameterValue::List(Vec::new()));
+
+        // 空列表之间哈希相同，与缺失的参数哈希不同
+        assert_eq!(
+            compute_params_hash(&params(empty()), &config),
+            compute_params_hash(&params(empty()), &config)
+        );
+        assert_ne!(
+            compute_params_hash(&params(empty()), &config),
+            compute_params_hash(&params(None), &config)
+        );
+
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let contents = [
+            (1, "lr: 0.01\ngpus: []\n"),
+            (2, "lr: 0.01\ngpus: []\n"),
+            (3, "lr: 0.01\ngpus:\n"),
+            (4, "lr: 0.01\n"),
+        ];
+        let hparams_files = write_version_fixtures(temp_dir.path(), &contents);
+
+        let (versions, _, _) = create_version_data_list(&config, &hparams_files)
+            .expect("Failed to create version data list");
+        let mut members: Vec<Vec<u32>> = group_versions(&config, versions)
+            .expect("Failed to group versions")
+            .iter()
+            .map(|g| g.member_versions.iter().map(|v| v.version_num).collect())
+            .collect();
+        members.sort();
+
+        // 空列表的版本同组，null与缺失的版本同组
+        assert_eq!(members, vec![vec![1, 2], vec![3, 4]]);
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    // 测试跨main_key分组比较共有参数和变化参数组合
+    #[test]
+    fn test_compare_main_key_groups() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+        let contents = [
+            (1, "model: cnn\nlr: 0.1\nepochs: 10\ndropout: 0.5\n"),
+            (2, "model: cnn\nlr: 0.01\nepochs: 10\ndropout: 0.5\n"),
+            (3, "model: rnn\nlr: 0.1\nepochs: 10\ndropout: 0.3\n"),
+            (4, "model: rnn\nlr: 0.001\nepochs: 10\ndropout: 0.3\n"),
+        ];
+        let hparams_files = write_version_fixtures(temp_dir.path(), &contents);
+
+        let main_keys = vec!["model".to_string()];
+        let config = create_test_config_with_main_key(Some(main_keys.clone()));
+        let (versions, group_common_hparams, _) = create_version_data_list(&config, &hparams_files)
+            .expect("Failed to create version data list");
+        let summary = compare_main_key_groups(&versions, &group_common_hparams, &main_keys);
+
+        let float = |v: f64| ParameterValue::Basic(BasicParameterValue::Float(v));
+        let lr = |v: f64| BTreeMap::from([("lr".to_string(), float(v))]);
+
+        // epochs在两个分组中取值相同，dropout取值不同
+        assert_eq!(
+            summary.shared_common_params,
+            BTreeMap::from([(
+                "epochs".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(10))
+            )])
+        );
+        assert_eq!(
+            summary.group_specific_common_params["model=cnn"],
+            BTreeMap::from([("dropout".to_string(), float(0.5))])
+        );
+        assert_eq!(
+            summary.group_specific_common_params["model=rnn"],
+            BTreeMap::from([("dropout".to_string(), float(0.3))])
+        );
+
+        // lr=0.1在两个模型中都运行过，其余取值只属于单个模型
+        assert_eq!(summary.shared_configurations, vec![lr(0.1)]);
+        assert_eq!(summary.unique_configurations["model=cnn"], vec![lr(0.01)]);
+        assert_eq!(summary.unique_configurations["model=rnn"], vec![lr(0.001)]);
+        assert!(summary.to_string().starts_with(
+            "Across main_key groups: 1 shared common parameters, 1 shared configurations"
+        ));
+
+        temp_dir.close().expect("Failed to clean up temp directory");
+    }
+
+    // 测试增量分组：匹配已有组的版本加入该组，不匹配的版本创建新组，已有组ID保持不变
+    #[test]
+    fn test_add_versions_to_groups() {
+        let config = create_test_config();
+
+        let make_version = |version_num: u32, model: &str, lr: f64| {
+            let mut hparams = HashMap::new();
+            hparams.insert(
+                "model".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String(model.to_string())),
+            );
+            hparams.insert(
+                "lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(lr)),
+            );
+            VersionData {
+                hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+
+        let mut groups = group_versions(
+            &config,
+            vec![
+                make_version(1, "cnn", 0.01),
+                make_version(3, "cnn", 0.01),
+                make_version(2, "rnn", 0.1),
+            ],
+        )
+        .expect("Failed to group versions");
+        assert_eq!(groups.len(), 2);
+        let original_ids: Vec<String> = groups.iter().map(|g| g.group_id.clone()).collect();
+
+        // 版本0与cnn组匹配，版本4形成新组
+        add_versions_to_groups(
+            &mut groups,
+            vec![make_version(0, "cnn", 0.01), make_version(4, "mlp", 0.5)],
+            &config,
+        );
+
+        assert_eq!(groups.len(), 3);
+        let ids: Vec<String> = groups.iter().map(|g| g.group_id.clone()).collect();
+        assert_eq!(
+            &ids[..2],
+            &original_ids[..],
+            "Existing group IDs must not change"
+        );
+
+        let cnn_members: Vec<u32> = groups[0]
+            .member_versions
+            .iter()
+            .map(|v| v.version_num)
+            .collect();
+        assert_eq!(cnn_members, vec![0, 1, 3], "Members should stay sorted");
+        assert_eq!(groups[1].member_versions.len(), 1);
+
+        let new_group = &groups[2];
+        assert_eq!(new_group.member_versions.len(), 1);
+        assert_eq!(new_group.member_versions[0].version_num, 4);
+        assert_eq!(
+            new_group.group_id,
+            compute_params_hash(&new_group.base_parameters, &config)
+        );
+    }
+
+    // 测试增量添加时，id_parameters相同、其他参数不同的新组不会与已有组ID重复
+    #[test]
+    fn test_add_versions_to_groups_unique_ids() {
+        let mut config = create_test_config();
+        config.grouping.id_parameters = Some(vec!["model".to_string()]);
+        let make_version = |version_num: u32, lr: f64| {
+            let hparams = HashMap::from([
+                (
+                    "model".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+                ),
+                (
+                    "lr".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::Float(lr)),
+                ),
+            ]);
+            VersionData {
+                full_hparams: hparams.clone(),
+                hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+
+        let mut groups = group_versions(&config, vec![make_version(1, 0.01)])
+            .expect("Failed to group versions");
+        let base_id = groups[0].group_id.clone();
+
+        add_versions_to_groups(
+            &mut groups,
+            vec![make_version(2, 0.1), make_version(3, 1.0)],
+            &config,
+        );
+
+        let ids: Vec<&str> = groups.iter().map(|g| g.group_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                base_id.as_str(),
+                format!("{}-2", base_id).as_str(),
+                format!("{}-3", base_id).as_str(),
+            ]
+        );
+    }
+
+    // 测试相似组查找功能
+    #[test]
+    fn test_find_similar_groups1() {
+        let config = create_test_config();
+
+        // 创建几个VersionData实例用于member_versions
+        let version1 = VersionData {
+            path: "version_001".to_string().into(),
+            ..VersionData::for_test(1)
+        };
+
+        let version2 = VersionData {
+            path: "version_002".to_string().into(),
+            ..VersionData::for_test(2)
+        };
+
+        let version3 = VersionData {
+            path: "version_003".to_string().into(),
+            ..VersionData::for_test(3)
+        };
 
         // 创建几个组
         let mut group1 = ExperimentGroup {
@@ -1591,4 +3839,238 @@ mod tests {
             panic!("optimizers should be a List");
         }
     }
+
+    #[test]
+    fn test_dedupe_similar_pairs() {
+        let mut similar_groups = HashMap::new();
+        similar_groups.insert("b".to_string(), vec!["a".to_string(), "c".to_string()]);
+        similar_groups.insert("a".to_string(), vec!["b".to_string()]);
+        similar_groups.insert("c".to_string(), vec!["b".to_string()]);
+        similar_groups.insert("d".to_string(), vec![]);
+
+        assert_eq!(
+            dedupe_similar_pairs(&similar_groups),
+            vec![
+                ("a".to_string(), "b".to_string()),
+                ("b".to_string(), "c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize() {
+        let make_version = VersionData::for_test;
+        let make_group = |id: &str, versions: Vec<VersionData>| ExperimentGroup {
+            group_id: id.to_string(),
+            base_parameters: HashMap::new(),
+            member_versions: versions,
+        };
+        let groups = vec![
+            make_group("g1", vec![make_version(1), make_version(2), make_version(3)]),
+            make_group("g2", vec![make_version(4), make_version(5)]),
+            make_group("g3", vec![make_version(6)]),
+        ];
+
+        let mut similar_groups = HashMap::new();
+        similar_groups.insert("g1".to_string(), vec!["g2".to_string()]);
+        similar_groups.insert("g2".to_string(), vec!["g1".to_string(), "g3".to_string()]);
+        similar_groups.insert("g3".to_string(), vec!["g2".to_string()]);
+
+        let summary = summarize(&groups, &similar_groups, 0);
+        assert_eq!(
+            summary,
+            Summary {
+                total_versions: 6,
+                total_groups: 3,
+                multi_member_groups: 2,
+                similar_pairs: 2,
+                under_replicated_groups: None,
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "Summary: 6 versions, 3 groups (2 with multiple members), 2 similar group pairs"
+        );
+
+        // 启用min_replication时统计重复次数不足的实验组
+        let summary = summarize(&groups, &similar_groups, 3);
+        assert_eq!(summary.under_replicated_groups, Some(2));
+        assert!(summary.to_string().ends_with(", 2 under-replicated groups"));
+
+        // 空输入
+        let empty = summarize(&[], &HashMap::new(), 0);
+        assert_eq!(empty.total_versions, 0);
+        assert_eq!(empty.similar_pairs, 0);
+    }
+
+    #[test]
+    fn test_coverage_matrix() {
+        let make_version = |version_num: u32, model: &str, dataset: Option<&str>| {
+            let mut full_hparams = HashMap::from([(
+                "model".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String(model.to_string())),
+            )]);
+            if let Some(dataset) = dataset {
+                full_hparams.insert(
+                    "dataset".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::String(dataset.to_string())),
+                );
+            }
+            VersionData {
+                full_hparams,
+                ..VersionData::for_test(version_num)
+            }
+        };
+        // cnn只在mnist上运行过，rnn只在cifar上运行过
+        let versions = vec![
+            make_version(1, "cnn", Some("mnist")),
+            make_version(2, "cnn", Some("mnist")),
+            make_version(3, "rnn", Some("cifar")),
+            make_version(4, "rnn", None),
+        ];
+        let key = |x: &str, y: &str| (x.to_string(), y.to_string());
+
+        let matrix = coverage_matrix(&versions, "model", "dataset");
+        assert_eq!(
+            matrix,
+            HashMap::from([
+                (key("cnn", "mnist"), 2),
+                (key("rnn", "cifar"), 1),
+                (key("rnn", "-"), 1),
+            ])
+        );
+        assert!(!matrix.contains_key(&key("cnn", "cifar")));
+        assert!(!matrix.contains_key(&key("rnn", "mnist")));
+    }
+
+    #[test]
+    fn test_is_under_replicated() {
+        let make_group = |id: &str, size: u32| ExperimentGroup {
+            group_id: id.to_string(),
+            base_parameters: HashMap::new(),
+            member_versions: (1..=size)
+                .map(VersionData::for_test)
+                .collect(),
+        };
+
+        // 少于阈值的实验组被标记，等于或多于阈值的不标记
+        assert!(is_under_replicated(&make_group("g1", 1), 3));
+        assert!(is_under_replicated(&make_group("g2", 2), 3));
+        assert!(!is_under_replicated(&make_group("g3", 3), 3));
+        assert!(!is_under_replicated(&make_group("g4", 4), 3));
+
+        // 阈值为0时不检查，未分组的实验组始终不标记
+        assert!(!is_under_replicated(&make_group("g1", 1), 0));
+        assert!(!is_under_replicated(&make_group(UNGROUPED_GROUP_ID, 1), 3));
+    }
+
+    #[test]
+    fn test_find_similar_pairs() {
+        let config = create_test_config();
+
+        // 5个组只有batch_size不同，在相似度阈值内两两相似
+        let groups: Vec<ExperimentGroup> = (0..5)
+            .map(|i| {
+                let mut base_parameters = HashMap::new();
+                base_parameters.insert(
+                    "model".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+                );
+                base_parameters.insert(
+                    "batch_size".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::Int(16 * (i + 1))),
+                );
+                ExperimentGroup {
+                    group_id: format!("group_{}", 4 - i),
+                    base_parameters,
+                    member_versions: vec![],
+                }
+            })
+            .collect();
+
+        let pairs = find_similar_pairs(&groups, &config);
+        let n = groups.len();
+        assert_eq!(pairs.len(), n * (n - 1) / 2);
+
+        // 每对按组ID排序，且没有重复
+        for (a, b) in &pairs {
+            assert!(a < b, "Pair ({}, {}) is not canonically ordered", a, b);
+        }
+        let unique: HashSet<_> = pairs.iter().collect();
+        assert_eq!(unique.len(), pairs.len());
+        assert_eq!(pairs[0], ("group_0".to_string(), "group_1".to_string()));
+    }
+
+    #[test]
+    fn test_similarity_mode_count_and_percent() {
+        // 两组共5个参数，其中2个不同（40%）
+        let make_group = |group_id: &str, values: [&str; 5]| {
+            let base_parameters = ["model", "dataset", "optimizer", "scheduler", "loss"]
+                .iter()
+                .zip(values)
+                .map(|(key, value)| {
+                    (
+                        key.to_string(),
+                        ParameterValue::Basic(BasicParameterValue::String(value.to_string())),
+                    )
+                })
+                .collect();
+            ExperimentGroup {
+                group_id: group_id.to_string(),
+                base_parameters,
+                member_versions: vec![],
+            }
+        };
+        let groups = vec![
+            make_group("a", ["cnn", "cifar", "adam", "cosine", "ce"]),
+            make_group("b", ["cnn", "cifar", "sgd", "step", "ce"]),
+        ];
+        let is_similar = |mode: SimilarityMode, threshold: usize| {
+            let config = Config::builder()
+                .similarity_mode(mode)
+                .similarity_threshold(threshold)
+                .build();
+            !find_similar_groups(&groups, &config)["a"].is_empty()
+        };
+
+        assert!(!is_similar(SimilarityMode::Count, 1));
+        assert!(is_similar(SimilarityMode::Count, 2));
+
+        assert!(!is_similar(SimilarityMode::Percent, 30));
+        assert!(is_similar(SimilarityMode::Percent, 40));
+        // 文档示例中的80%下两组相似
+        assert!(is_similar(SimilarityMode::Percent, 80));
+    }
+
+    #[test]
+    fn test_count_different_parameters_bounded() {
+        let config = create_test_config();
+        let int = |i: i64| ParameterValue::Basic(BasicParameterValue::Int(i));
+
+        let base: HashMap<String, ParameterValue> =
+            (0..20).map(|i| (format!("p{}", i), int(i))).collect();
+        let mut close = base.clone();
+        close.insert("p0".to_string(), int(100));
+        close.insert("extra".to_string(), int(1));
+        // 所有参数都不同
+        let far: HashMap<String, ParameterValue> =
+            (0..20).map(|i| (format!("p{}", i), int(i + 1000))).collect();
+
+        for other in [&base, &close, &far] {
+            let full = count_different_parameters(&base, other, &config);
+            for max in 0..=25 {
+                let bounded = count_different_parameters_bounded(&base, other, &config, max);
+                // 是否相似的判定与完整计数一致
+                assert_eq!(bounded <= max, full <= max);
+                if full <= max {
+                    assert_eq!(bounded, full);
+                }
+            }
+        }
+
+        // 明显不同的参数集在超过阈值后立即停止计数
+        assert_eq!(count_different_parameters(&base, &far, &config), 20);
+        assert_eq!(count_different_parameters_bounded(&base, &far, &config, 2), 3);
+        assert_eq!(count_different_parameters_bounded(&base, &far, &config, 0), 1);
+    }
 }
@@ -0,0 +1,305 @@
+//! 派生参数使用的简单算术表达式，如`batch_size * num_gpus`
+//!
+//! 支持的语法：数字字面量、参数引用、`+ - * /`、一元负号和括号。
+//! 参数名由字母、数字、`_`和`.`组成；含有其他字符（如展平后的`trainer-max_epochs`）时写作`{trainer-max_epochs}`。
+//! 整数之间的加减乘结果仍为整数，除法和含浮点数的运算结果为浮点数
+
+use crate::models::{BasicParameterValue, ParameterValue};
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+
+/// 解析后的算术表达式
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    source: String, // 原始表达式文本
+    root: Node,     // 语法树根节点
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(Number),
+    Reference(String),
+    Negate(Box<Node>),
+    Binary(Box<Node>, Operator, Box<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// 运算过程中的数值，尽量保持整数
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(n) => n as f64,
+            Number::Float(n) => n,
+        }
+    }
+
+    fn apply(self, operator: Operator, rhs: Number) -> Result<Number> {
+        if let (Number::Int(a), Number::Int(b)) = (self, rhs) {
+            let result = match operator {
+                Operator::Add => a.checked_add(b),
+                Operator::Subtract => a.checked_sub(b),
+                Operator::Multiply => a.checked_mul(b),
+                Operator::Divide => None,
+            };
+            if let Some(result) = result {
+                return Ok(Number::Int(result));
+            }
+        }
+        let (a, b) = (self.as_f64(), rhs.as_f64());
+        Ok(Number::Float(match operator {
+            Operator::Add => a + b,
+            Operator::Subtract => a - b,
+            Operator::Multiply => a * b,
+            Operator::Divide => {
+                if b == 0.0 {
+                    bail!("Division by zero");
+                }
+                a / b
+            }
+        }))
+    }
+}
+
+impl Expression {
+    /// 解析表达式文本，语法错误时返回错误
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut parser = Parser {
+            chars: source.chars().collect(),
+            pos: 0,
+        };
+        let root = parser.parse_sum()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            bail!(
+                "Unexpected character '{}' at position {} in expression {:?}",
+                c,
+                parser.pos,
+                source
+            );
+        }
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    /// 原始表达式文本
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// 使用给定参数计算表达式，引用的参数缺失或不是数值时返回错误
+    pub fn evaluate(&self, hparams: &HashMap<String, ParameterValue>) -> Result<ParameterValue> {
+        let value = match evaluate_node(&self.root, hparams)? {
+            Number::Int(n) => BasicParameterValue::Int(n),
+            Number::Float(n) => BasicParameterValue::Float(n),
+        };
+        Ok(ParameterValue::Basic(value))
+    }
+}
+
+fn evaluate_node(node: &Node, hparams: &HashMap<String, ParameterValue>) -> Result<Number> {
+    match node {
+        Node::Number(n) => Ok(*n),
+        Node::Reference(name) => match hparams.get(name) {
+            Some(ParameterValue::Basic(BasicParameterValue::Int(n))) => Ok(Number::Int(*n)),
+            Some(ParameterValue::Basic(BasicParameterValue::Float(n))) => Ok(Number::Float(*n)),
+            Some(value) => bail!("Parameter '{}' is not numeric: {}", name, value),
+            None => bail!("Parameter '{}' is missing", name),
+        },
+        Node::Negate(inner) => {
+            Number::Int(0).apply(Operator::Subtract, evaluate_node(inner, hparams)?)
+        }
+        Node::Binary(lhs, operator, rhs) => {
+            evaluate_node(lhs, hparams)?.apply(*operator, evaluate_node(rhs, hparams)?)
+        }
+    }
+}
+
+/// 递归下降解析器
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// sum := product (('+' | '-') product)*
+    fn parse_sum(&mut self) -> Result<Node> {
+        let mut node = self.parse_product()?;
+        loop {
+            self.skip_whitespace();
+            let operator = match self.peek() {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Subtract,
+                _ => return Ok(node),
+            };
+            self.pos += 1;
+            node = Node::Binary(Box::new(node), operator, Box::new(self.parse_product()?));
+        }
+    }
+
+    /// product := unary (('*' | '/') unary)*
+    fn parse_product(&mut self) -> Result<Node> {
+        let mut node = self.parse_unary()?;
+        loop {
+            self.skip_whitespace();
+            let operator = match self.peek() {
+                Some('*') => Operator::Multiply,
+                Some('/') => Operator::Divide,
+                _ => return Ok(node),
+            };
+            self.pos += 1;
+            node = Node::Binary(Box::new(node), operator, Box::new(self.parse_unary()?));
+        }
+    }
+
+    /// unary := '-' unary | atom
+    fn parse_unary(&mut self) -> Result<Node> {
+        self.skip_whitespace();
+        if self.peek() == Some('-') {
+            self.pos += 1;
+            return Ok(Node::Negate(Box::new(self.parse_unary()?)));
+        }
+        self.parse_atom()
+    }
+
+    /// atom := number | name | '{' name '}' | '(' sum ')'
+    fn parse_atom(&mut self) -> Result<Node> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let node = self.parse_sum()?;
+                self.skip_whitespace();
+                if self.peek() != Some(')') {
+                    bail!("Missing ')' at position {}", self.pos);
+                }
+                self.pos += 1;
+                Ok(node)
+            }
+            Some('{') => {
+                self.pos += 1;
+                let name = self.take_while(|c| c != '}');
+                if self.peek() != Some('}') || name.trim().is_empty() {
+                    bail!("Unterminated or empty '{{...}}' parameter reference");
+                }
+                self.pos += 1;
+                Ok(Node::Reference(name.trim().to_string()))
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let literal = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                if let Ok(n) = literal.parse::<i64>() {
+                    Ok(Node::Number(Number::Int(n)))
+                } else if let Ok(n) = literal.parse::<f64>() {
+                    Ok(Node::Number(Number::Float(n)))
+                } else {
+                    bail!("Invalid number '{}'", literal)
+                }
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '.');
+                Ok(Node::Reference(name))
+            }
+            Some(c) => bail!("Unexpected character '{}' at position {}", c, self.pos),
+            None => bail!("Unexpected end of expression"),
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(&predicate) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(entries: &[(&str, ParameterValue)]) -> HashMap<String, ParameterValue> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+
+    fn int(n: i64) -> ParameterValue {
+        ParameterValue::Basic(BasicParameterValue::Int(n))
+    }
+
+    fn float(n: f64) -> ParameterValue {
+        ParameterValue::Basic(BasicParameterValue::Float(n))
+    }
+
+    #[test]
+    fn test_evaluate_expression() {
+        let hparams = params(&[
+            ("batch_size", int(32)),
+            ("num_gpus", int(4)),
+            ("lr", float(0.5)),
+            ("trainer-accumulate_grad_batches", int(2)),
+            (
+                "model",
+                ParameterValue::Basic(BasicParameterValue::String("cnn".into())),
+            ),
+        ]);
+        let eval = |source: &str| Expression::parse(source).unwrap().evaluate(&hparams);
+
+        assert_eq!(eval("batch_size * num_gpus").unwrap(), int(128));
+        assert_eq!(
+            eval("batch_size * num_gpus * {trainer-accumulate_grad_batches}").unwrap(),
+            int(256)
+        );
+        // 运算符优先级、括号和一元负号
+        assert_eq!(eval("1 + 2 * 3").unwrap(), int(7));
+        assert_eq!(eval("(1 + 2) * 3").unwrap(), int(9));
+        assert_eq!(eval("-num_gpus + 10").unwrap(), int(6));
+        assert_eq!(eval("10 - 2 - 3").unwrap(), int(5));
+        // 除法和浮点数运算得到浮点数
+        assert_eq!(eval("batch_size / num_gpus").unwrap(), float(8.0));
+        assert_eq!(eval("lr * num_gpus").unwrap(), float(2.0));
+        assert_eq!(eval("1.5 + 1").unwrap(), float(2.5));
+
+        assert!(eval("batch_size / (num_gpus - 4)").is_err());
+        assert!(eval("missing * 2").is_err());
+        assert!(eval("model * 2").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for source in ["", "a +", "(a + b", "a b", "{}", "{a", "a % b", "1..2"] {
+            assert!(
+                Expression::parse(source).is_err(),
+                "{:?} should not parse",
+                source
+            );
+        }
+        let expression = Expression::parse(" a * (b + {c-d}) ").unwrap();
+        assert_eq!(expression.as_str(), " a * (b + {c-d}) ");
+    }
+}
@@ -1,9 +1,20 @@
+use crate::models::config::GeneralConfig;
 use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 use walkdir::{DirEntry, WalkDir};
 
 /// 遍历日志目录，收集所有hparams.yaml文件路径
-pub fn find_hparams_files(log_dir: &str, hparams_file: &str) -> Result<Vec<PathBuf>> {
+///
+/// `hparams_files`为按优先级排列的候选文件名，每个version目录只取其中第一个存在的文件，
+/// 候选文件的gzip压缩版本（如`hparams.yaml.gz`）也会被识别，优先级紧随未压缩的文件之后；
+/// 名称匹配`ignore_dir_patterns`中任一模式的目录及其子目录不会被遍历
+pub fn find_hparams_files<S: AsRef<str>>(
+    log_dir: &str,
+    hparams_files: &[S],
+    ignore_dir_patterns: &[String],
+) -> Result<Vec<PathBuf>> {
     let path = Path::new(log_dir);
 
     // 检查目录是否存在
@@ -15,25 +26,163 @@ pub fn find_hparams_files(log_dir: &str, hparams_file: &str) -> Result<Vec<PathB
         anyhow::bail!("'{}' is not a directory", log_dir);
     }
 
-    let mut hparams_files: Vec<PathBuf> = WalkDir::new(log_dir)
+    // 每个version目录保留优先级最高（候选列表中位置最靠前）的文件
+    let mut best_per_dir: HashMap<PathBuf, (usize, PathBuf)> = HashMap::new();
+    WalkDir::new(log_dir)
         .follow_links(true)
         .max_depth(2)
         .into_iter()
+        .filter_entry(|entry| !is_ignored_dir(entry, ignore_dir_patterns)) // 跳过忽略的目录
         .filter_map(Result::ok) // 过滤掉错误条目
-        .filter(|entry| is_hparams_file(entry, hparams_file)) // 保留符合条件的
-        .map(|entry| entry.path().to_path_buf()) // 提取路径
-        .collect(); // 收集成 Vec
-
-    // 按版本号排序（从目录名中提取）
+        .filter(|entry| is_hparams_file(entry, hparams_files)) // 保留符合条件的
+        .filter_map(|entry| {
+            let rank = hparams_file_rank(&entry, hparams_files)?;
+            let dir = entry.path().parent()?.to_path_buf();
+            Some((dir, rank, entry.path().to_path_buf()))
+        })
+        .for_each(|(dir, rank, path)| {
+            let best = best_per_dir.entry(dir).or_insert((rank, path.clone()));
+            if rank < best.0 {
+                *best = (rank, path);
+            }
+        });
+    let mut hparams_files: Vec<PathBuf> =
+        best_per_dir.into_values().map(|(_, path)| path).collect();
+
+    // 按版本号排序（从目录名中提取），版本号相同时按路径排序保证结果稳定
     hparams_files.sort_by(|a, b| {
         let version_a = extract_version_number(a);
         let version_b = extract_version_number(b);
-        version_a.cmp(&version_b)
+        version_a.cmp(&version_b).then_with(|| a.cmp(b))
     });
 
     Ok(hparams_files)
 }
 
+/// 在日志目录下查找可能是正确日志目录的子目录，即直接包含`version_{number}`目录的子目录
+///
+/// 用于日志目录中没有找到任何版本时提示用户，例如`log_dir`指向了`lightning_logs`的上级目录。
+/// 最多向下查找`MAX_CANDIDATE_DEPTH`层，结果按路径排序
+pub fn find_candidate_log_dirs(log_dir: &str, ignore_dir_patterns: &[String]) -> Vec<PathBuf> {
+    const MAX_CANDIDATE_DEPTH: usize = 4;
+
+    let mut candidates: Vec<PathBuf> = WalkDir::new(log_dir)
+        .follow_links(true)
+        .max_depth(MAX_CANDIDATE_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored_dir(entry, ignore_dir_patterns))
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            // 直接位于log_dir下的版本目录已由find_hparams_files处理，只看更深的层级
+            entry.depth() >= 2
+                && entry.file_type().is_dir()
+                && entry
+                    .file_name()
+                    .to_string_lossy()
+                    .strip_prefix("version_")
+                    .is_some_and(|num| num.parse::<u32>().is_ok())
+        })
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+/// 查找日志目录下最近修改的实验子目录，即直接包含`version_{number}`目录的直接子目录中修改时间最新的一个
+///
+/// 修改时间相同时取路径最大的目录；没有这样的子目录时返回None
+pub fn find_latest_experiment_dir(
+    log_dir: &str,
+    ignore_dir_patterns: &[String],
+) -> Option<PathBuf> {
+    WalkDir::new(log_dir)
+        .follow_links(true)
+        .min_depth(1)
+        .max_depth(1)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored_dir(entry, ignore_dir_patterns))
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_dir() && contains_version_dirs(entry.path()))
+        .filter_map(|entry| {
+            let mtime = entry.metadata().ok()?.modified().ok()?;
+            Some((mtime, entry.into_path()))
+        })
+        .max()
+        .map(|(_, path)| path)
+}
+
+/// 检查目录下是否直接包含`version_{number}`目录
+fn contains_version_dirs(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries.filter_map(Result::ok).any(|entry| {
+            entry.file_type().is_ok_and(|file_type| file_type.is_dir())
+                && entry
+                    .file_name()
+                    .to_string_lossy()
+                    .strip_prefix("version_")
+                    .is_some_and(|num| num.parse::<u32>().is_ok())
+        })
+    })
+}
+
+/// 返回实际扫描的日志目录，以及需要提示给用户的警告
+///
+/// 开启`auto_latest_experiment`时使用最近修改的实验子目录，找不到时回退到`log_dir`并返回警告
+pub fn resolve_scan_dir(general: &GeneralConfig) -> (String, Option<String>) {
+    if !general.auto_latest_experiment {
+        return (general.log_dir.clone(), None);
+    }
+    match find_latest_experiment_dir(&general.log_dir, &general.ignore_dir_patterns) {
+        Some(dir) => (dir.to_string_lossy().into_owned(), None),
+        None => (
+            general.log_dir.clone(),
+            Some(format!(
+                "auto_latest_experiment is enabled but no experiment subdirectory \
+                 with version directories was found in '{}', scanning it directly",
+                general.log_dir
+            )),
+        ),
+    }
+}
+
+/// 检查目录名是否匹配任一忽略模式，日志根目录本身永远不会被忽略
+fn is_ignored_dir(entry: &DirEntry, ignore_dir_patterns: &[String]) -> bool {
+    if entry.depth() == 0 || !entry.file_type().is_dir() {
+        return false;
+    }
+    let name = entry.file_name().to_string_lossy();
+    ignore_dir_patterns
+        .iter()
+        .any(|pattern| matches_wildcard(&name, pattern))
+}
+
+/// 简单通配符匹配，`*`匹配任意长度字符，`?`匹配单个字符
+fn matches_wildcard(name: &str, pattern: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut n, mut p) = (0, 0);
+    // 最近一次`*`的位置及其对应的name位置，用于回溯
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            n += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// 从路径的父目录名中提取 "version_" 后的字符串部分（如 "version_42" → "42"）
 fn extract_version_str_from_path(path: &Path) -> Option<String> {
     path.parent().and_then(|p| p.file_name()).and_then(|name| {
@@ -43,13 +192,110 @@ fn extract_version_str_from_path(path: &Path) -> Option<String> {
     })
 }
 
-/// 检查文件名是否存在且为 hparams_file 文件，且父目录名称为 "version_{number}"
-fn is_hparams_file(entry: &DirEntry, hparams_file: &str) -> bool {
+/// 检查文件是否为候选文件名之一，且父目录名称为 "version_{number}"
+fn is_hparams_file<S: AsRef<str>>(entry: &DirEntry, hparams_files: &[S]) -> bool {
     entry.file_type().is_file()
-        && entry.file_name() == hparams_file
-        && extract_version_str_from_path(&entry.path())
+        && extract_version_str_from_path(entry.path())
             .and_then(|s| s.parse::<u32>().ok())
             .is_some()
+        && hparams_file_rank(entry, hparams_files).is_some()
+}
+
+/// 返回候选文件名的优先级（越小越优先），文件名不是候选文件时返回None
+///
+/// 候选文件排在其压缩版本之前，压缩版本排在下一个候选文件之前
+fn hparams_file_rank<S: AsRef<str>>(entry: &DirEntry, hparams_files: &[S]) -> Option<usize> {
+    let file_name = entry.file_name().to_string_lossy();
+    let (name, compressed) = match file_name.strip_suffix(".gz") {
+        Some(name) => (name, true),
+        None => (file_name.as_ref(), false),
+    };
+    hparams_files
+        .iter()
+        .position(|candidate| candidate.as_ref() == name)
+        .map(|index| index * 2 + usize::from(compressed))
+}
+
+/// 版本选择表达式中的各项，按输入顺序保存为闭区间，单个版本号保存为`n..=n`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionSelector {
+    ranges: Vec<RangeInclusive<u32>>,
+}
+
+impl VersionSelector {
+    /// 版本号是否落在任一区间内
+    pub fn contains(&self, version_num: u32) -> bool {
+        self.ranges.iter().any(|range| range.contains(&version_num))
+    }
+
+    /// 是否没有任何区间，空的选择表达式不过滤版本
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// 按输入顺序返回各项对应的闭区间
+    pub fn ranges(&self) -> &[RangeInclusive<u32>] {
+        &self.ranges
+    }
+}
+
+/// 解析版本选择表达式，如`10-25,30`
+///
+/// 逗号分隔的每一项是单个版本号或闭区间`a-b`（要求`a <= b`），项两侧的空白被忽略
+pub fn parse_version_selector(selector: &str) -> Result<VersionSelector> {
+    let mut ranges = Vec::new();
+    for item in selector.split(',').map(str::trim) {
+        let parse_num = |text: &str| -> Result<u32> {
+            text.trim()
+                .parse()
+                .with_context(|| format!("Invalid version number '{}' in '{}'", text, selector))
+        };
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_num(start)?, parse_num(end)?);
+                if start > end {
+                    anyhow::bail!(
+                        "Invalid version range '{}': start must not be greater than end",
+                        item
+                    );
+                }
+                ranges.push(start..=end);
+            }
+            None => {
+                let num = parse_num(item)?;
+                ranges.push(num..=num);
+            }
+        }
+    }
+    Ok(VersionSelector { ranges })
+}
+
+/// 只保留版本号被`selector`选中的hparams文件，`selector`为空时不过滤
+///
+/// 返回过滤后的文件和没有匹配到任何版本的选择项（按输入顺序），
+/// 区间只要匹配到一个版本就不算未匹配，避免宽区间为每个空缺版本号各给一次警告
+pub fn filter_by_version_allowlist(
+    hparams_files: Vec<PathBuf>,
+    selector: &VersionSelector,
+) -> (Vec<PathBuf>, Vec<RangeInclusive<u32>>) {
+    if selector.is_empty() {
+        return (hparams_files, Vec::new());
+    }
+    let files: Vec<PathBuf> = hparams_files
+        .into_iter()
+        .filter(|path| extract_version_number_safe(path).is_ok_and(|num| selector.contains(num)))
+        .collect();
+    let found: BTreeSet<u32> = files
+        .iter()
+        .filter_map(|path| extract_version_number_safe(path).ok())
+        .collect();
+    let unmatched = selector
+        .ranges()
+        .iter()
+        .filter(|range| found.range((*range).clone()).next().is_none())
+        .cloned()
+        .collect();
+    (files, unmatched)
 }
 
 /// 从文件路径中提取版本号
@@ -76,6 +322,36 @@ pub fn extract_version_number_safe(path: &Path) -> Result<u32> {
     })
 }
 
+/// 目录中的文件列表
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DirectoryListing {
+    pub files: Vec<(String, u64)>, // 相对于目录的文件路径和文件大小（字节），按路径排序
+    pub total_bytes: u64,          // 目录中所有文件的总大小（字节）
+}
+
+/// 递归列出目录中的所有文件及其大小
+pub fn list_directory_files(dir: &Path) -> Result<DirectoryListing> {
+    let mut listing = DirectoryListing::default();
+    for entry in WalkDir::new(dir).min_depth(1) {
+        let entry =
+            entry.with_context(|| format!("Failed to list directory: {}", dir.display()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let size = entry
+            .metadata()
+            .with_context(|| format!("Failed to read metadata: {}", entry.path().display()))?
+            .len();
+        let relative_path = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        listing
+            .files
+            .push((relative_path.to_string_lossy().into_owned(), size));
+        listing.total_bytes += size;
+    }
+    listing.files.sort();
+    Ok(listing)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,16 +438,16 @@ mod tests {
             .find(|e| e.path() == version_other_dir_file)
             .unwrap();
         // 测试正确的hparams文件
-        assert!(is_hparams_file(hparams_entry, "hparams.yaml"));
+        assert!(is_hparams_file(hparams_entry, &["hparams.yaml"]));
 
         // 测试错误的文件名
-        assert!(!is_hparams_file(other_entry, "hparams.yaml"));
+        assert!(!is_hparams_file(other_entry, &["hparams.yaml"]));
 
         // 测试非版本目录中的文件
-        assert!(!is_hparams_file(other_dir_entry, "hparams.yaml"));
+        assert!(!is_hparams_file(other_dir_entry, &["hparams.yaml"]));
 
         // 测试非版本目录中的文件
-        assert!(!is_hparams_file(version_other_dir_entry, "hparams.yaml"));
+        assert!(!is_hparams_file(version_other_dir_entry, &["hparams.yaml"]));
     }
 
     #[test]
@@ -216,7 +492,8 @@ mod tests {
         dbg!(&entries);
 
         // 测试查找hparams文件
-        let result = find_hparams_files(logs_dir.to_str().unwrap(), "hparams.yaml").unwrap();
+        let result =
+            find_hparams_files(logs_dir.to_str().unwrap(), &["hparams.yaml"], &[]).unwrap();
         dbg!(&result);
 
         // 应该找到4个文件（版本0,1,5,10），并且按版本号排序
@@ -243,7 +520,7 @@ mod tests {
     #[test]
     fn test_find_hparams_files_nonexistent_dir() {
         // 测试不存在的目录
-        let result = find_hparams_files("/nonexistent/directory", "hparams.yaml");
+        let result = find_hparams_files("/nonexistent/directory", &["hparams.yaml"], &[]);
         assert!(result.is_err());
     }
 
@@ -257,7 +534,7 @@ mod tests {
         fs::write(&file_path, "test").unwrap();
 
         // 测试文件而不是目录的情况
-        let result = find_hparams_files(file_path.to_str().unwrap(), "hparams.yaml");
+        let result = find_hparams_files(file_path.to_str().unwrap(), &["hparams.yaml"], &[]);
         assert!(result.is_err());
     }
 
@@ -278,7 +555,8 @@ mod tests {
         fs::write(&custom_file, "test").unwrap();
 
         // 测试查找自定义文件名
-        let result = find_hparams_files(logs_dir.to_str().unwrap(), "custom_params.yaml").unwrap();
+        let result =
+            find_hparams_files(logs_dir.to_str().unwrap(), &["custom_params.yaml"], &[]).unwrap();
         dbg!(&result);
         assert_eq!(result.len(), 1);
         assert!(result[0].ends_with("custom_params.yaml"));
@@ -294,7 +572,279 @@ mod tests {
         fs::create_dir(&empty_dir).unwrap();
 
         // 测试空目录
-        let result = find_hparams_files(empty_dir.to_str().unwrap(), "hparams.yaml").unwrap();
+        let result =
+            find_hparams_files(empty_dir.to_str().unwrap(), &["hparams.yaml"], &[]).unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    #[test]
+    fn test_find_candidate_log_dirs() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        // log_dir误指向了项目根目录，版本目录位于lightning_logs和runs/exp1下
+        for dir in [
+            "lightning_logs/version_0",
+            "lightning_logs/version_1",
+            "runs/exp1/version_3",
+            "data/raw",
+            ".cache/lightning_logs/version_9",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let log_dir = root.to_str().unwrap();
+
+        let files = find_hparams_files(log_dir, &["hparams.yaml"], &[]).unwrap();
+        assert!(files.is_empty());
+
+        let candidates = find_candidate_log_dirs(log_dir, &[".*".to_string()]);
+        assert_eq!(
+            candidates,
+            vec![root.join("lightning_logs"), root.join("runs/exp1")]
+        );
+    }
+
+    #[test]
+    fn test_find_latest_experiment_dir() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        for dir in [
+            "exp_a/version_0",
+            "exp_b/version_0",
+            "exp_b/version_1",
+            "exp_c/version_0",
+            "notes/drafts",
+            ".hidden/version_0",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        // 依次设置修改时间，不含版本目录的notes和被忽略的隐藏目录最新
+        let base = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        for (offset, dir) in ["exp_a", "exp_c", "exp_b", "notes", ".hidden"]
+            .iter()
+            .enumerate()
+        {
+            fs::File::open(root.join(dir))
+                .unwrap()
+                .set_modified(base + std::time::Duration::from_secs(offset as u64 * 60))
+                .unwrap();
+        }
+        let log_dir = root.to_str().unwrap();
+        let ignore = [".*".to_string()];
+
+        assert_eq!(
+            find_latest_experiment_dir(log_dir, &ignore),
+            Some(root.join("exp_b"))
+        );
+        assert_eq!(
+            find_latest_experiment_dir(log_dir, &[]),
+            Some(root.join(".hidden"))
+        );
+
+        let mut general = GeneralConfig {
+            log_dir: log_dir.to_string(),
+            ..GeneralConfig::default()
+        };
+        assert_eq!(resolve_scan_dir(&general), (log_dir.to_string(), None));
+        general.auto_latest_experiment = true;
+        assert_eq!(
+            resolve_scan_dir(&general),
+            (root.join("exp_b").to_str().unwrap().to_string(), None)
+        );
+
+        // 没有实验子目录时回退到log_dir本身，并返回警告
+        general.log_dir = root.join("exp_a").to_str().unwrap().to_string();
+        assert_eq!(find_latest_experiment_dir(&general.log_dir, &ignore), None);
+        let (scan_dir, warning) = resolve_scan_dir(&general);
+        assert_eq!(scan_dir, general.log_dir);
+        assert!(
+            warning
+                .unwrap()
+                .starts_with("auto_latest_experiment is enabled")
+        );
+    }
+
+    #[test]
+    fn test_filter_by_version_allowlist() {
+        let files: Vec<PathBuf> = [1, 3, 7, 12]
+            .iter()
+            .map(|num| PathBuf::from(format!("logs/version_{}/hparams.yaml", num)))
+            .collect();
+
+        let selector = parse_version_selector("12,3,5").unwrap();
+        let (filtered, unmatched) = filter_by_version_allowlist(files.clone(), &selector);
+        assert_eq!(filtered, vec![files[1].clone(), files[3].clone()]);
+        assert_eq!(unmatched, vec![5..=5]);
+
+        // 部分匹配的区间不报告缺失的版本号，完全没有匹配的区间作为一项报告
+        let selector = parse_version_selector("2-8,20-4000000000").unwrap();
+        let (filtered, unmatched) = filter_by_version_allowlist(files.clone(), &selector);
+        assert_eq!(filtered, vec![files[1].clone(), files[2].clone()]);
+        assert_eq!(unmatched, vec![20..=4_000_000_000]);
+
+        // 空的选择表达式不过滤
+        let (filtered, unmatched) =
+            filter_by_version_allowlist(files.clone(), &VersionSelector::default());
+        assert_eq!(filtered, files);
+        assert!(unmatched.is_empty());
+    }
+
+    #[test]
+    fn test_parse_version_selector() {
+        let selector = parse_version_selector("10-13,30, 2 ,12-12").unwrap();
+        assert_eq!(selector.ranges(), &[10..=13, 30..=30, 2..=2, 12..=12]);
+        assert!(selector.contains(11) && selector.contains(2) && !selector.contains(14));
+        assert_eq!(parse_version_selector("7").unwrap().ranges(), &[7..=7]);
+
+        // 宽区间不展开为逐个版本号
+        let selector = parse_version_selector("0-4294967295").unwrap();
+        assert_eq!(selector.ranges(), &[0..=u32::MAX]);
+        assert!(selector.contains(u32::MAX));
+
+        for malformed in ["", "3,", "a", "5-", "-5", "1-2-3", "25-10", "-1"] {
+            assert!(
+                parse_version_selector(malformed).is_err(),
+                "'{}' should be rejected",
+                malformed
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_wildcard() {
+        assert!(matches_wildcard(".trash", ".*"));
+        assert!(!matches_wildcard("version_1", ".*"));
+        assert!(matches_wildcard("version_1", "version_?"));
+        assert!(!matches_wildcard("version_10", "version_?"));
+        assert!(matches_wildcard("tmp_run_backup", "tmp*backup"));
+        assert!(matches_wildcard("anything", "*"));
+        assert!(!matches_wildcard("abc", "abd"));
+    }
+
+    #[test]
+    fn test_find_hparams_files_ignore_dir_patterns() {
+        let temp_dir = tempdir().unwrap();
+        let logs_dir = temp_dir.path().join("logs");
+
+        // 正常版本目录
+        let version_dir = logs_dir.join("version_0");
+        fs::create_dir_all(&version_dir).unwrap();
+        fs::write(version_dir.join("hparams.yaml"), "test").unwrap();
+
+        // 隐藏目录中的版本目录
+        let trash_version_dir = logs_dir.join(".trash").join("version_1");
+        fs::create_dir_all(&trash_version_dir).unwrap();
+        fs::write(trash_version_dir.join("hparams.yaml"), "test").unwrap();
+
+        // 版本目录本身匹配忽略模式
+        let backup_dir = logs_dir.join("version_2");
+        fs::create_dir_all(&backup_dir).unwrap();
+        fs::write(backup_dir.join("hparams.yaml"), "test").unwrap();
+
+        let patterns = vec![".*".to_string(), "version_2".to_string()];
+        let result =
+            find_hparams_files(logs_dir.to_str().unwrap(), &["hparams.yaml"], &patterns).unwrap();
+        assert_eq!(result, vec![version_dir.join("hparams.yaml")]);
+
+        // 日志根目录本身不受忽略模式影响
+        let hidden_root = temp_dir.path().join(".hidden_logs");
+        let root_version_dir = hidden_root.join("version_3");
+        fs::create_dir_all(&root_version_dir).unwrap();
+        fs::write(root_version_dir.join("hparams.yaml"), "test").unwrap();
+        let result =
+            find_hparams_files(hidden_root.to_str().unwrap(), &["hparams.yaml"], &patterns)
+                .unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_find_hparams_files_candidate_names() {
+        let temp_dir = tempdir().unwrap();
+        let logs_dir = temp_dir.path().join("logs");
+
+        // version_0只有hparams.yaml，version_1只有config.yaml，version_2两者都有
+        let files = [
+            ("version_0", "hparams.yaml"),
+            ("version_1", "config.yaml"),
+            ("version_2", "hparams.yaml"),
+            ("version_2", "config.yaml"),
+            ("version_3", "other.yaml"),
+        ];
+        for (dir, name) in files {
+            let version_dir = logs_dir.join(dir);
+            fs::create_dir_all(&version_dir).unwrap();
+            fs::write(version_dir.join(name), "test").unwrap();
+        }
+
+        let candidates = ["hparams.yaml", "config.yaml"];
+        let result = find_hparams_files(logs_dir.to_str().unwrap(), &candidates, &[]).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                logs_dir.join("version_0").join("hparams.yaml"),
+                logs_dir.join("version_1").join("config.yaml"),
+                logs_dir.join("version_2").join("hparams.yaml"),
+            ]
+        );
+
+        // 调换优先级后version_2使用config.yaml
+        let candidates = ["config.yaml", "hparams.yaml"];
+        let result = find_hparams_files(logs_dir.to_str().unwrap(), &candidates, &[]).unwrap();
+        assert_eq!(result[2], logs_dir.join("version_2").join("config.yaml"));
+    }
+
+    #[test]
+    fn test_list_directory_files() {
+        let temp_dir = tempdir().unwrap();
+        let version_dir = temp_dir.path().join("version_0");
+        fs::create_dir_all(version_dir.join("checkpoints")).unwrap();
+        fs::write(version_dir.join("hparams.yaml"), "lr: 0.1\n").unwrap();
+        fs::write(version_dir.join("metrics.csv"), "epoch,loss\n0,1.0\n").unwrap();
+        fs::write(
+            version_dir.join("checkpoints").join("last.ckpt"),
+            [0u8; 100],
+        )
+        .unwrap();
+
+        let listing = list_directory_files(&version_dir).unwrap();
+        let checkpoint = Path::new("checkpoints")
+            .join("last.ckpt")
+            .to_string_lossy()
+            .into_owned();
+        assert_eq!(
+            listing.files,
+            vec![
+                (checkpoint, 100),
+                ("hparams.yaml".to_string(), 8),
+                ("metrics.csv".to_string(), 17),
+            ]
+        );
+        assert_eq!(listing.total_bytes, 125);
+
+        // 目录不存在时报错
+        assert!(list_directory_files(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_find_compressed_hparams_files() {
+        let temp_dir = tempdir().unwrap();
+        let logs_dir = temp_dir.path().join("logs");
+        for version in 0..3 {
+            fs::create_dir_all(logs_dir.join(format!("version_{}", version))).unwrap();
+        }
+        // version_0只有压缩文件，version_1同时存在时优先使用未压缩的文件
+        fs::write(logs_dir.join("version_0").join("hparams.yaml.gz"), "").unwrap();
+        fs::write(logs_dir.join("version_1").join("hparams.yaml"), "").unwrap();
+        fs::write(logs_dir.join("version_1").join("hparams.yaml.gz"), "").unwrap();
+        fs::write(logs_dir.join("version_2").join("other.yaml.gz"), "").unwrap();
+
+        let result =
+            find_hparams_files(logs_dir.to_str().unwrap(), &["hparams.yaml"], &[]).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                logs_dir.join("version_0").join("hparams.yaml.gz"),
+                logs_dir.join("version_1").join("hparams.yaml"),
+            ]
+        );
+    }
 }
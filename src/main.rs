@@ -1,47 +1,259 @@
 // src/main.rs
+mod bench;
+mod cli;
 mod config;
 mod experiment_grouping;
+mod expression;
 mod file_utils;
+mod metrics;
 mod models;
+mod tags;
+#[cfg(test)]
+mod test_utils;
 mod tui;
 mod yaml_parser;
 
 use anyhow::Result;
-use config::load_config;
-use experiment_grouping::{create_version_data_list, find_similar_groups, group_versions};
-use file_utils::find_hparams_files;
-use models::AppState;
-use tui::TuiApp;
+use bench::run_bench;
+use clap::Parser;
+use cli::{Cli, ReportFormat};
+use config::{default_config_toml, load_config, resolve_config_path};
+use experiment_grouping::{
+    check_key_presence, compare_main_key_groups, coverage_matrix, create_version_data_list,
+    dedupe_similar_pairs, find_similar_groups, group_versions, minimal_distinguishing_keys,
+    orphan_parameters, rank_groups_by_reference, summarize, type_inconsistent_keys,
+};
+use file_utils::{
+    filter_by_version_allowlist, find_candidate_log_dirs, find_hparams_files, resolve_scan_dir,
+};
+use models::{
+    AppState, AppStateView, Config, ExperimentGroup, VersionData, write_coverage_matrix,
+    write_group_report_jsonl, write_versions_table,
+};
+use std::path::{Path, PathBuf};
+use tags::TagStore;
+use tui::{Rescanner, TuiApp};
+use tui::utils::export_columns;
+use yaml_parser::{ParseOptions, parse_hparams_file_with_options};
 
 fn main() -> Result<()> {
-    // 加载配置文件
-    let config = load_config("lightning_explorer.toml")?;
-    println!("Configuration loaded successfully!");
-    println!("Log directory: {}", config.general.log_dir);
+    // 解析命令行参数
+    let cli = Cli::parse();
+    // 输出机器可读结果时不打印进度信息，保证stdout只包含结果
+    let quiet = cli.is_headless();
 
-    // 查找所有hparams.yaml文件
-    let hparams_files = find_hparams_files(&config.general.log_dir, &config.general.hparams_file)?;
-    println!("Found {} hparams files:", hparams_files.len());
+    // 只打印默认配置，不读取也不创建配置文件
+    if cli.print_default_config {
+        print!("{}", default_config_toml());
+        return Ok(());
+    }
+
+    // 加载配置文件，命令行指定的容差覆盖配置文件中的容差
+    let config_path = resolve_config_path(cli.config.as_deref(), Path::new("."));
+    let mut config = load_config(&config_path)?;
+    cli.apply_tolerance_overrides(&mut config.tolerance);
+    if !quiet {
+        println!("Configuration loaded successfully!");
+        println!("Log directory: {}", config.general.log_dir);
+        if cli.overrides_tolerance() {
+            let tolerance = &config.tolerance;
+            println!(
+                "Tolerance overridden from command line: float_tolerance={}, int_tolerance={}, string_case_sensitive={}",
+                tolerance.float_tolerance, tolerance.int_tolerance, tolerance.string_case_sensitive
+            );
+        }
+    }
+
+    // 只统计各阶段耗时，不启动TUI
+    if cli.bench {
+        println!("{}", run_bench(&config)?);
+        return Ok(());
+    }
+
+    // 查找所有hparams.yaml文件，开启auto_latest_experiment时只扫描最新的实验子目录
+    let (scan_dir, scan_dir_warning) = resolve_scan_dir(&config.general);
+    if let Some(warning) = scan_dir_warning {
+        eprintln!("Warning: {}", warning);
+    }
+    if !quiet && scan_dir != config.general.log_dir {
+        println!("Scanning latest experiment directory: {}", scan_dir);
+    }
+    let hparams_files = find_hparams_files(
+        &scan_dir,
+        &config.general.hparams_file_candidates(),
+        &config.general.ignore_dir_patterns,
+    )?;
+    if hparams_files.is_empty() {
+        check_candidate_log_dirs(&config)?;
+    }
+
+    // 只保留命令行指定的版本
+    let (hparams_files, unmatched) =
+        filter_by_version_allowlist(hparams_files, &cli.versions.clone().unwrap_or_default());
+    for range in unmatched {
+        if range.start() == range.end() {
+            eprintln!(
+                "Warning: Version {} was requested with --versions but not found, ignoring",
+                range.start()
+            );
+        } else {
+            eprintln!(
+                "Warning: No versions in {}-{} were found for --versions, ignoring",
+                range.start(),
+                range.end()
+            );
+        }
+    }
+    if !quiet {
+        println!("Found {} hparams files:", hparams_files.len());
+    }
+
+    // 检查各版本是否包含指定参数后退出，缺少参数时不报错
+    if let Some(key) = &cli.check_key {
+        print_key_presence(key, &hparams_files, &config)?;
+        return Ok(());
+    }
 
     // 创建VersionData列表并获取分组内相同hparams数据
-    let (version_data_list, group_common_hparams) =
+    let (version_data_list, group_common_hparams, mut load_report) =
         create_version_data_list(&config, &hparams_files)?;
-    println!(
-        "Successfully created {} version data entries",
-        version_data_list.len()
-    );
-    if !group_common_hparams.is_empty() {
-        println!(
-            "Found {} main_key groups with common parameters",
-            group_common_hparams.len()
-        );
+    load_report.config_path = Some(config_path.clone());
+    for warning in &load_report.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    if !quiet {
+        println!("{}", load_report.summary());
+        if !group_common_hparams.is_empty() {
+            println!(
+                "Found {} main_key groups with common parameters",
+                group_common_hparams.len()
+            );
+        }
+    }
+
+    // 输出每个版本一行的参数表格后退出
+    if cli.table {
+        print_versions_table(&version_data_list, &config)?;
+        return Ok(());
+    }
+
+    // 输出两个参数的覆盖矩阵后退出
+    if let Some([param_x, param_y]) = cli.coverage.as_deref() {
+        let matrix = coverage_matrix(&version_data_list, param_x, param_y);
+        write_coverage_matrix(&matrix, param_x, param_y, &mut std::io::stdout().lock())?;
+        return Ok(());
     }
 
     // 对版本进行分组（克隆version_data_list以保留所有权）
     let experiment_groups = group_versions(&config, version_data_list.clone())?;
-    println!("Found {} experiment groups", experiment_groups.len());
+    if !quiet {
+        println!("Found {} experiment groups", experiment_groups.len());
+        print_experiment_groups(&config, &experiment_groups);
+
+        // 比较各main_key分组之间共享和特有的参数组合
+        if let Some(main_keys) = &config.grouping.main_key {
+            let cross_group =
+                compare_main_key_groups(&version_data_list, &group_common_hparams, main_keys);
+            println!("\n{}", cross_group);
+        }
+    }
+
+    // 输出分组报告后退出
+    if let Some(ReportFormat::Jsonl) = cli.report {
+        write_group_report_jsonl(&experiment_groups, &config, &mut std::io::stdout().lock())?;
+        return Ok(());
+    }
+
+    // 与参考hparams文件比较，输出排序结果后退出
+    if let Some(reference_path) = &cli.compare_to {
+        print_reference_ranking(reference_path, &experiment_groups, &config)?;
+        return Ok(());
+    }
+
+    // 查找相似组
+    let similar_groups = find_similar_groups(&experiment_groups, &config);
+    if !quiet {
+        let similar_pairs = dedupe_similar_pairs(&similar_groups);
+        print_similar_groups(&experiment_groups, &similar_pairs);
+        let summary = summarize(
+            &experiment_groups,
+            &similar_groups,
+            config.grouping.min_replication,
+        );
+        println!("\n{}", summary);
+        print_orphan_parameters(&version_data_list);
+        print_type_inconsistent_keys(&version_data_list);
+    }
+
+    // 创建AppState实例，保存所有实验数据和配置
+    let app_state = AppState {
+        all_versions: version_data_list,
+        experiment_groups,
+        config,
+        group_common_hparams,
+        load_report,
+    };
+
+    // 输出完整状态的JSON文档后退出
+    if cli.json_dump {
+        let view = AppStateView::new(&app_state, &similar_groups);
+        println!("{}", view.to_json_string()?);
+        return Ok(());
+    }
+
+    // 启动TUI界面
+    let tags = TagStore::load(app_state.config.general.tags_file_path())?;
+    let mut app = tui::App::new(app_state);
+    app.set_tags(tags);
+    app.set_config_path(&config_path);
+    app.set_similar_groups(similar_groups);
+    let keybindings = app.state.config.keybindings.clone();
+    // 只查看命令行指定的版本时不加载新版本
+    let rescanner = cli
+        .versions
+        .is_none()
+        .then(|| {
+            Rescanner::new(
+                &scan_dir,
+                &app.state.config,
+                &hparams_files,
+                &app.state.all_versions,
+            )
+        })
+        .flatten();
+    let mut tui_app = TuiApp::new(app, keybindings)?;
+    if let Some(rescanner) = rescanner {
+        tui_app.set_rescanner(rescanner);
+    }
+    tui_app.run()?;
+
+    Ok(())
+}
+
+/// 日志目录中没有版本时，若其子目录中存在版本目录则报错并列出可能正确的日志目录
+///
+/// 常见原因是`log_dir`指向了`lightning_logs`的上级目录
+fn check_candidate_log_dirs(config: &Config) -> Result<()> {
+    let candidates =
+        find_candidate_log_dirs(&config.general.log_dir, &config.general.ignore_dir_patterns);
+    if candidates.is_empty() {
+        return Ok(());
+    }
+    let candidate_list: Vec<String> = candidates
+        .iter()
+        .map(|dir| format!("  {}", dir.display()))
+        .collect();
+    anyhow::bail!(
+        "No version directories found directly in log_dir '{}', \
+         but these subdirectories contain version_* directories:\n{}\n\
+         Did you mean to set log_dir to one of them?",
+        config.general.log_dir,
+        candidate_list.join("\n")
+    )
+}
 
-    // 打印分组结果
+/// 打印分组结果
+fn print_experiment_groups(config: &Config, experiment_groups: &[ExperimentGroup]) {
     for (i, group) in experiment_groups.iter().enumerate() {
         let version_nums: Vec<_> = group
             .member_versions
@@ -49,8 +261,9 @@ fn main() -> Result<()> {
             .map(|v| v.version_num)
             .collect();
         println!(
-            "Group {} ({} versions): {:?}",
+            "Group {} [{}] ({} versions): {:?}",
             i + 1,
+            group.label(config),
             group.member_versions.len(),
             version_nums
         );
@@ -63,7 +276,14 @@ fn main() -> Result<()> {
             for (key, value) in &group.base_parameters {
                 if param_count < 10 {
                     // 限制打印参数数量
-                    println!("    {}: {}", key, value.to_simple_string());
+                    println!(
+                        "    {}: {}",
+                        key,
+                        value.to_display_string(
+                            config.tui.float_display_precision,
+                            config.tui.trim_trailing_zeros
+                        )
+                    );
                     param_count += 1;
                 } else {
                     println!(
@@ -76,59 +296,127 @@ fn main() -> Result<()> {
         }
     }
 
-    // 查找相似组
-    let similar_groups = find_similar_groups(&experiment_groups, &config);
-
-    // 打印相似组信息
-    let mut has_similar_groups = false;
-    for (group_id, similar_ids) in similar_groups {
-        if !similar_ids.is_empty() {
-            if !has_similar_groups {
-                println!("\nSimilar experiment groups:");
-                has_similar_groups = true;
-            }
+    // 提示能区分所有实验组的最小参数集合，可作为label_parameters的参考
+    let distinguishing_keys = minimal_distinguishing_keys(experiment_groups, config);
+    if !distinguishing_keys.is_empty() {
+        println!(
+            "Parameters distinguishing all groups: {}",
+            distinguishing_keys.join(", ")
+        );
+    }
+}
 
-            // 找到对应的组索引
-            if let Some(group_idx) = experiment_groups
-                .iter()
-                .position(|g| g.group_id == group_id)
-            {
-                let similar_indices: Vec<_> = similar_ids
-                    .iter()
-                    .filter_map(|id| experiment_groups.iter().position(|g| g.group_id == *id))
-                    .collect();
-
-                if !similar_indices.is_empty() {
-                    print!("  Group {} is similar to: ", group_idx + 1);
-                    for (i, idx) in similar_indices.iter().enumerate() {
-                        if i > 0 {
-                            print!(", ");
-                        }
-                        print!("Group {}", idx + 1);
-                    }
-                    println!();
-                }
-            }
+/// 与参考hparams文件比较，按差异数打印排序后的实验组
+fn print_reference_ranking(
+    reference_path: &Path,
+    experiment_groups: &[ExperimentGroup],
+    config: &Config,
+) -> Result<()> {
+    let reference =
+        parse_hparams_file_with_options(reference_path, &ParseOptions::from(config))?;
+    println!(
+        "\nGroups ranked by distance to {}:",
+        reference_path.display()
+    );
+    for distance in rank_groups_by_reference(&reference, experiment_groups, config) {
+        let group = &experiment_groups[distance.group_index];
+        let version_nums: Vec<_> = group
+            .member_versions
+            .iter()
+            .map(|v| v.version_num)
+            .collect();
+        println!(
+            "  Group {} {:?}: {} different parameters",
+            distance.group_index + 1,
+            version_nums,
+            distance.distance
+        );
+        if !distance.differing_keys.is_empty() {
+            println!("    {}", distance.differing_keys.join(", "));
         }
     }
+    Ok(())
+}
+
+/// 打印包含和缺少指定参数的版本号
+fn print_key_presence(key: &str, hparams_files: &[PathBuf], config: &Config) -> Result<()> {
+    let presence = check_key_presence(hparams_files, key, &ParseOptions::from(config))?;
+    println!(
+        "Versions with '{}' ({}): {:?}",
+        key,
+        presence.with_key.len(),
+        presence.with_key
+    );
+    println!(
+        "Versions missing '{}' ({}): {:?}",
+        key,
+        presence.without_key.len(),
+        presence.without_key
+    );
+    for path in &presence.failed_files {
+        eprintln!("Warning: Failed to parse {}", path.display());
+    }
+    Ok(())
+}
+
+/// 按版本号顺序打印每个版本一行的参数表格，列为所有版本区分参数的并集
+fn print_versions_table(versions: &[VersionData], config: &Config) -> Result<()> {
+    let mut versions: Vec<&VersionData> = versions.iter().collect();
+    versions.sort_by_key(|version| version.version_num);
+    let columns = export_columns(&versions, config.tui.detail_sort_mode);
+    write_versions_table(&versions, &columns, config, &mut std::io::stdout().lock())?;
+    Ok(())
+}
+
+/// 打印只在一个版本中出现的参数，便于发现参数名的拼写错误
+fn print_orphan_parameters(versions: &[VersionData]) {
+    let orphans = orphan_parameters(versions);
+    if orphans.is_empty() {
+        return;
+    }
+    println!("\nParameters present in only one version (possible typos):");
+    for (key, version_num) in orphans {
+        println!("  {} (version_{})", key, version_num);
+    }
+}
+
+/// 打印在不同版本中值类型不一致的参数
+fn print_type_inconsistent_keys(versions: &[VersionData]) {
+    let inconsistent = type_inconsistent_keys(versions);
+    if inconsistent.is_empty() {
+        return;
+    }
+    println!("\nParameters with inconsistent types across versions:");
+    for (key, type_names) in inconsistent {
+        println!("  {} ({})", key, type_names.join(", "));
+    }
+}
 
-    if !has_similar_groups {
+/// 打印相似组信息
+fn print_similar_groups(experiment_groups: &[ExperimentGroup], similar_pairs: &[(String, String)]) {
+    if similar_pairs.is_empty() {
         println!("\nNo similar experiment groups found within the similarity threshold");
+        return;
     }
 
-    // 创建AppState实例，保存所有实验数据和配置
-    let app_state = AppState {
-        all_versions: version_data_list,
-        experiment_groups,
-        config,
-        group_common_hparams,
+    // 组ID转换为从1开始的组序号
+    let group_number = |group_id: &str| {
+        experiment_groups
+            .iter()
+            .position(|g| g.group_id == group_id)
+            .map(|idx| idx + 1)
     };
 
-    // 启动TUI界面
-    let app = tui::App::new(app_state);
-    let keybindings = app.state.config.keybindings.clone();
-    let mut tui_app = TuiApp::new(app, keybindings)?;
-    tui_app.run()?;
-
-    Ok(())
+    println!("\nSimilar experiment groups:");
+    let mut numbered_pairs: Vec<_> = similar_pairs
+        .iter()
+        .filter_map(|(a, b)| {
+            let (a, b) = (group_number(a)?, group_number(b)?);
+            Some((a.min(b), a.max(b)))
+        })
+        .collect();
+    numbered_pairs.sort();
+    for (a, b) in numbered_pairs {
+        println!("  Group {} <-> Group {}", a, b);
+    }
 }
@@ -0,0 +1,369 @@
+// src/metrics.rs
+use crate::models::{BasicParameterValue, MetricAggregation, MetricsConfig, ParameterValue};
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// 从版本的指标文件中读取的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VersionMetrics {
+    pub metrics: BTreeMap<String, f64>, // 按配置汇总后的各指标
+    pub best_epoch: Option<BestEpoch>,  // 监控指标的最佳记录，没有监控指标或找不到对应列时为None
+}
+
+/// 读取版本目录下的指标文件，按配置将每个指标列汇总为单个值，并查找`monitor`指标的最佳记录
+///
+/// 文件不存在时返回空结果
+pub fn load_version_metrics(
+    version_dir: &Path,
+    config: &MetricsConfig,
+    monitor: Option<&str>,
+) -> Result<VersionMetrics> {
+    let path = version_dir.join(&config.metrics_file);
+    if !path.is_file() {
+        return Ok(VersionMetrics::default());
+    }
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read metrics file: {}", path.display()))?;
+    let columns = parse_metrics_csv(&contents)
+        .with_context(|| format!("Failed to parse metrics file: {}", path.display()))?;
+    let best_epoch = match monitor {
+        Some(metric) => find_best_epoch(&contents, metric, config)
+            .with_context(|| format!("Failed to parse metrics file: {}", path.display()))?,
+        None => None,
+    };
+    Ok(VersionMetrics {
+        metrics: aggregate_metrics(&columns, config),
+        best_epoch,
+    })
+}
+
+/// 版本监控的指标名，取自`monitor_parameter`指定的超参数，参数不存在或不是字符串时返回None
+pub fn monitored_metric<'a>(
+    hparams: &'a HashMap<String, ParameterValue>,
+    config: &MetricsConfig,
+) -> Option<&'a str> {
+    match hparams.get(&config.monitor_parameter) {
+        Some(ParameterValue::Basic(BasicParameterValue::String(monitor))) => Some(monitor),
+        _ => None,
+    }
+}
+
+/// 解析CSVLogger写出的指标文件，返回每一列的列名和按行顺序排列的数值
+///
+/// 不同指标通常记录在不同的行中，空单元格和无法解析为数值的单元格被跳过
+pub fn parse_metrics_csv(contents: &str) -> Result<Vec<(String, Vec<f64>)>> {
+    let (header, rows) = parse_metrics_rows(contents)?;
+    Ok(header
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let values = rows.iter().filter_map(|row| row[index]).collect();
+            (name, values)
+        })
+        .collect())
+}
+
+/// 指标文件中的一行，空单元格和无法解析为数值的单元格为None
+pub type MetricsRow = Vec<Option<f64>>;
+
+/// 按行解析指标文件，返回列名和每一行的单元格，空单元格和无法解析为数值的单元格为None
+///
+/// 列名和数值都不包含逗号，因此按逗号直接拆分；单元格少于列数的行用None补齐
+pub fn parse_metrics_rows(contents: &str) -> Result<(Vec<String>, Vec<MetricsRow>)> {
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<String> = lines
+        .next()
+        .context("Metrics file is empty")?
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .collect();
+
+    let mut rows = Vec::new();
+    for (row_index, line) in lines.enumerate() {
+        let cells: Vec<&str> = line.split(',').collect();
+        if cells.len() > header.len() {
+            anyhow::bail!(
+                "Row {} has {} cells but the header has {} columns",
+                row_index + 1,
+                cells.len(),
+                header.len()
+            );
+        }
+        let mut row: MetricsRow = cells
+            .iter()
+            .map(|cell| cell.trim().parse::<f64>().ok())
+            .collect();
+        row.resize(header.len(), None);
+        rows.push(row);
+    }
+    Ok((header, rows))
+}
+
+/// 指标取得最佳值的记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct BestEpoch {
+    pub metric: String,     // 指标文件中的列名
+    pub epoch: Option<u64>, // 最佳值所在行的epoch，指标文件没有epoch列时为None
+    pub value: f64,         // 最佳值
+}
+
+/// 在列名中查找指标：优先精确匹配，其次忽略大小写和非字母数字字符匹配，
+/// 使`VAL--acc`这样的监控名也能对应到`val_acc`列
+fn find_metric_column(header: &[String], metric: &str) -> Option<usize> {
+    let normalize = |name: &str| -> String {
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    };
+    header.iter().position(|name| name == metric).or_else(|| {
+        let target = normalize(metric);
+        header.iter().position(|name| normalize(name) == target)
+    })
+}
+
+/// 在指标文件内容中查找指标取得最佳值的行，相同的最佳值取最早的一行
+///
+/// 优化方向按匹配到的列名从`config`中获取；指标列不存在或没有任何数值时返回None
+pub fn find_best_epoch(
+    contents: &str,
+    metric: &str,
+    config: &MetricsConfig,
+) -> Result<Option<BestEpoch>> {
+    let (header, rows) = parse_metrics_rows(contents)?;
+    let Some(column) = find_metric_column(&header, metric) else {
+        return Ok(None);
+    };
+    let higher_is_better = config.higher_is_better(&header[column]);
+    let epoch_column = header.iter().position(|name| name == "epoch");
+
+    let mut best: Option<(&MetricsRow, f64)> = None;
+    for row in &rows {
+        let Some(value) = row[column].filter(|value| !value.is_nan()) else {
+            continue;
+        };
+        let is_better = best.is_none_or(|(_, best_value)| {
+            if higher_is_better {
+                value > best_value
+            } else {
+                value < best_value
+            }
+        });
+        if is_better {
+            best = Some((row, value));
+        }
+    }
+    Ok(best.map(|(row, value)| BestEpoch {
+        metric: header[column].clone(),
+        epoch: epoch_column
+            .and_then(|index| row[index])
+            .map(|epoch| epoch as u64),
+        value,
+    }))
+}
+
+/// 按每个指标配置的汇总方式汇总各列，没有任何数值的列被省略
+pub fn aggregate_metrics(
+    columns: &[(String, Vec<f64>)],
+    config: &MetricsConfig,
+) -> BTreeMap<String, f64> {
+    columns
+        .iter()
+        .filter_map(|(name, values)| {
+            aggregate(values, config.aggregation_for(name)).map(|value| (name.clone(), value))
+        })
+        .collect()
+}
+
+/// 将一列数值汇总为单个值，列为空时返回None
+pub fn aggregate(values: &[f64], aggregation: MetricAggregation) -> Option<f64> {
+    match aggregation {
+        MetricAggregation::Last => values.last().copied(),
+        MetricAggregation::Max => values.iter().copied().reduce(f64::max),
+        MetricAggregation::Min => values.iter().copied().reduce(f64::min),
+        MetricAggregation::Mean => {
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::config::MetricDirection;
+    use std::collections::HashMap;
+
+    const METRICS_CSV: &str = "\
+epoch,step,train_loss,val_acc,val_loss
+0,99,0.9,,
+0,99,,0.70,0.60
+1,199,0.5,,
+1,199,,0.80,0.40
+2,299,0.3,,
+2,299,,0.75,0.45
+";
+
+    #[test]
+    fn test_parse_metrics_csv() {
+        let columns = parse_metrics_csv(METRICS_CSV).unwrap();
+        let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["epoch", "step", "train_loss", "val_acc", "val_loss"]
+        );
+        assert_eq!(columns[2].1, vec![0.9, 0.5, 0.3]);
+        assert_eq!(columns[3].1, vec![0.70, 0.80, 0.75]);
+
+        assert!(parse_metrics_csv("").is_err());
+        assert!(parse_metrics_csv("epoch,loss\n0,1.0,extra\n").is_err());
+    }
+
+    #[test]
+    fn test_each_aggregation() {
+        let columns = parse_metrics_csv(METRICS_CSV).unwrap();
+        let val_acc = &columns[3].1;
+        assert_eq!(aggregate(val_acc, MetricAggregation::Last), Some(0.75));
+        assert_eq!(aggregate(val_acc, MetricAggregation::Max), Some(0.80));
+        assert_eq!(aggregate(val_acc, MetricAggregation::Min), Some(0.70));
+        let mean = aggregate(val_acc, MetricAggregation::Mean).unwrap();
+        assert!((mean - 0.75).abs() < 1e-12);
+        for aggregation in [
+            MetricAggregation::Last,
+            MetricAggregation::Max,
+            MetricAggregation::Min,
+            MetricAggregation::Mean,
+        ] {
+            assert_eq!(aggregate(&[], aggregation), None);
+        }
+    }
+
+    #[test]
+    fn test_per_metric_aggregation() {
+        let config = MetricsConfig {
+            default_aggregation: MetricAggregation::Last,
+            metric_aggregation: HashMap::from([
+                ("val_acc".to_string(), MetricAggregation::Max),
+                ("val_loss".to_string(), MetricAggregation::Min),
+            ]),
+            ..MetricsConfig::default()
+        };
+        let metrics = aggregate_metrics(&parse_metrics_csv(METRICS_CSV).unwrap(), &config);
+        assert_eq!(metrics["val_acc"], 0.80);
+        assert_eq!(metrics["val_loss"], 0.40);
+        assert_eq!(metrics["train_loss"], 0.3);
+        assert_eq!(metrics["epoch"], 2.0);
+    }
+
+    #[test]
+    fn test_find_best_epoch() {
+        let config = MetricsConfig::default();
+        // val_acc在epoch 1最佳，val_loss在epoch 1最佳
+        let best = find_best_epoch(METRICS_CSV, "val_acc", &config)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            best,
+            BestEpoch {
+                metric: "val_acc".to_string(),
+                epoch: Some(1),
+                value: 0.80,
+            }
+        );
+        let best = find_best_epoch(METRICS_CSV, "val_loss", &config)
+            .unwrap()
+            .unwrap();
+        assert_eq!((best.epoch, best.value), (Some(1), 0.40));
+
+        // 监控名与列名的大小写和分隔符不同
+        let best = find_best_epoch(METRICS_CSV, "VAL--acc", &config)
+            .unwrap()
+            .unwrap();
+        assert_eq!(best.metric, "val_acc");
+        assert_eq!(best.epoch, Some(1));
+
+        assert_eq!(
+            find_best_epoch(METRICS_CSV, "missing", &config).unwrap(),
+            None
+        );
+        // 相同的最佳值取最早的一行
+        let tied = "epoch,val_acc\n0,0.5\n1,0.9\n2,0.9\n";
+        let best = find_best_epoch(tied, "val_acc", &config).unwrap().unwrap();
+        assert_eq!(best.epoch, Some(1));
+
+        // 显式配置的方向优先于推断
+        let config = MetricsConfig {
+            metric_directions: HashMap::from([("val_loss".to_string(), MetricDirection::Higher)]),
+            ..MetricsConfig::default()
+        };
+        let best = find_best_epoch(METRICS_CSV, "val_loss", &config)
+            .unwrap()
+            .unwrap();
+        assert_eq!((best.epoch, best.value), (Some(0), 0.60));
+    }
+
+    #[test]
+    fn test_metric_directions() {
+        // 按名称推断
+        let config = MetricsConfig::default();
+        assert!(config.higher_is_better("val_acc"));
+        assert!(config.higher_is_better("VAL--f1"));
+        assert!(!config.higher_is_better("val_loss"));
+        assert!(!config.higher_is_better("test/Error_rate"));
+
+        // 显式配置覆盖推断结果
+        let config = MetricsConfig {
+            metric_directions: HashMap::from([
+                ("val_acc".to_string(), MetricDirection::Lower),
+                ("neg_loss".to_string(), MetricDirection::Higher),
+            ]),
+            ..MetricsConfig::default()
+        };
+        assert!(!config.higher_is_better("val_acc"));
+        assert!(config.higher_is_better("neg_loss"));
+        assert!(!config.higher_is_better("train_loss"));
+        assert_eq!(config.direction_for("val_f1"), MetricDirection::Higher);
+    }
+
+    #[test]
+    fn test_load_version_metrics() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = MetricsConfig::default();
+
+        // 没有指标文件时返回空结果
+        assert_eq!(
+            load_version_metrics(temp_dir.path(), &config, Some("val_acc")).unwrap(),
+            VersionMetrics::default()
+        );
+
+        fs::write(temp_dir.path().join("metrics.csv"), METRICS_CSV).unwrap();
+        let loaded = load_version_metrics(temp_dir.path(), &config, None).unwrap();
+        assert_eq!(loaded.metrics["val_acc"], 0.75);
+        assert_eq!(loaded.metrics.len(), 5);
+        assert_eq!(loaded.best_epoch, None);
+
+        // 同时查找监控指标的最佳记录
+        let loaded = load_version_metrics(temp_dir.path(), &config, Some("val_acc")).unwrap();
+        assert_eq!(loaded.best_epoch.map(|best| best.epoch), Some(Some(1)));
+    }
+
+    #[test]
+    fn test_monitored_metric() {
+        let config = MetricsConfig::default();
+        let mut hparams = HashMap::new();
+        assert_eq!(monitored_metric(&hparams, &config), None);
+
+        hparams.insert(
+            config.monitor_parameter.clone(),
+            ParameterValue::Basic(BasicParameterValue::Int(1)),
+        );
+        assert_eq!(monitored_metric(&hparams, &config), None);
+
+        hparams.insert(
+            config.monitor_parameter.clone(),
+            ParameterValue::Basic(BasicParameterValue::String("val_acc".to_string())),
+        );
+        assert_eq!(monitored_metric(&hparams, &config), Some("val_acc"));
+    }
+}
@@ -5,13 +5,20 @@ pub mod models;
 pub mod parameter_value;
 pub mod state;
 pub mod utils;
+pub mod view;
 
 // 重新导出常用类型，保持API一致性
 pub use config::{
-    ColorConfig, Config, DefaultArgsConfig, DiffConfig, GroupingConfig, IgnoredConfig,
-    KeybindingsConfig, TestScriptConfig, ToleranceConfig, TuiConfig,
+    ColorConfig, Config, DefaultArgsConfig, DetailSortMode, DiffConfig,
+    GroupingConfig, GroupingStrategy, IgnoreRule, IgnoredConfig, KeySpec, KeybindingsConfig,
+    MainKeyMissingPolicy, MetricAggregation, MetricsConfig, SimilarityMode, TestScriptConfig, ToleranceConfig, TuiConfig, VersionSortMode,
 };
-pub use models::{ExperimentGroup, VersionData};
+pub use models::{ExperimentGroup, GroupDiff, ParameterDifference, VersionData, group_as_flags};
 pub use parameter_value::{BasicParameterValue, ParameterValue, print_hparams_pretty};
-pub use state::AppState;
+pub use state::{AppState, LoadReport};
 pub use utils::deserialize_optional_string;
+pub use view::{
+    AppStateView, ExportMetadata, write_coverage_matrix, write_group_diff_json,
+    write_group_diff_markdown, write_group_report_jsonl, write_versions_csv, write_versions_json,
+    write_versions_table,
+};
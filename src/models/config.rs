@@ -1,7 +1,14 @@
-use serde::{Deserialize, Deserializer};
+use crate::expression::Expression;
+use crate::models::parameter_value::ParameterValue;
+use anyhow::Context;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// 应用程序配置结构
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     // ————————————————————————————————————————————————————————————————————————
     // 通用配置，包含日志目录、超参数文件等基本设置
@@ -35,10 +42,158 @@ pub struct Config {
     // 测试脚本配置，定义测试脚本的执行参数
     // ————————————————————————————————————————————————————————————————————————
     pub test_script: TestScriptConfig,
+    // ————————————————————————————————————————————————————————————————————————
+    // 指标配置，定义指标文件名和各指标的汇总方式
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    // ————————————————————————————————————————————————————————————————————————
+    // 派生参数配置，由已有数值参数计算得到并注入到超参数中
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub derived_parameters: DerivedParametersConfig,
+}
+
+impl Config {
+    /// 创建配置构建器，用于在代码中构造配置
+    #[cfg(test)]
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// 检查配置是否有效
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.grouping.validate().context("Invalid [grouping] section")?;
+        self.keybindings
+            .validate()
+            .context("Invalid [keybindings] section")?;
+        self.derived_parameters
+            .validate()
+            .context("Invalid [derived_parameters] section")?;
+        self.ignored_parameters
+            .validate()
+            .context("Invalid [ignored_parameters] section")
+    }
+}
+
+/// 配置构建器
+///
+/// 在`Config::default()`的基础上使用所有参数分组、`hparams.yaml`作为超参数文件名，
+/// 其余字段保持默认值，可通过链式调用逐项修改
+#[cfg(test)]
+#[derive(Debug)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+#[cfg(test)]
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        let mut config = Config::default();
+        config.general.log_dir = "lightning_logs".to_string();
+        config.general.hparams_file = "hparams.yaml".to_string();
+        config.grouping.group_by_all_parameters = true;
+        config.grouping.similarity_threshold = 1;
+        Self { config }
+    }
+}
+
+#[cfg(test)]
+impl ConfigBuilder {
+    /// 设置日志目录
+    pub fn log_dir(mut self, log_dir: impl Into<String>) -> Self {
+        self.config.general.log_dir = log_dir.into();
+        self
+    }
+
+    /// 设置超参数文件名
+    pub fn hparams_file(mut self, hparams_file: impl Into<String>) -> Self {
+        self.config.general.hparams_file = hparams_file.into();
+        self
+    }
+
+    /// 设置比较和分组时忽略的参数
+    pub fn ignored_parameters<S: Into<String>>(
+        mut self,
+        parameters: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.config.ignored_parameters.parameters = parameters.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 设置浮点数容差
+    pub fn float_tolerance(mut self, tolerance: f64) -> Self {
+        self.config.tolerance.float_tolerance = tolerance;
+        self
+    }
+
+    /// 设置整数容差
+    pub fn int_tolerance(mut self, tolerance: i64) -> Self {
+        self.config.tolerance.int_tolerance = tolerance;
+        self
+    }
+
+    /// 设置字符串比较是否区分大小写
+    pub fn string_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.config.tolerance.string_case_sensitive = case_sensitive;
+        self
+    }
+
+    /// 设置是否使用所有参数分组
+    pub fn group_by_all_parameters(mut self, group_by_all: bool) -> Self {
+        self.config.grouping.group_by_all_parameters = group_by_all;
+        self
+    }
+
+    /// 设置分组参数
+    pub fn grouping_parameters<S: Into<String>>(
+        mut self,
+        parameters: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.config.grouping.grouping_parameters =
+            Some(parameters.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// 设置相似度阈值
+    pub fn similarity_threshold(mut self, threshold: usize) -> Self {
+        self.config.grouping.similarity_threshold = threshold;
+        self
+    }
+
+    /// 设置主键参数
+    pub fn main_key<S: Into<String>>(mut self, keys: impl IntoIterator<Item = S>) -> Self {
+        self.config.grouping.main_key = Some(keys.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// 设置缺少主键参数时的处理方式
+    pub fn main_key_missing_policy(mut self, policy: MainKeyMissingPolicy) -> Self {
+        self.config.grouping.main_key_missing_policy = policy;
+        self
+    }
+
+    /// 设置相似度阈值的含义
+    pub fn similarity_mode(mut self, mode: SimilarityMode) -> Self {
+        self.config.grouping.similarity_mode = mode;
+        self
+    }
+
+    /// 设置分组策略
+    pub fn grouping_strategy(mut self, strategy: GroupingStrategy) -> Self {
+        self.config.grouping.grouping_strategy = strategy;
+        self
+    }
+
+    /// 生成配置
+    pub fn build(self) -> Config {
+        self.config
+    }
 }
 
 /// 通用配置
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct GeneralConfig {
     // ————————————————————————————————————————————————————————————————————————
     // 日志文件存储目录路径
@@ -46,60 +201,383 @@ pub struct GeneralConfig {
     pub log_dir: String,
     // ————————————————————————————————————————————————————————————————————————
     // 超参数文件名，用于存储实验参数配置
+    // 配置了hparams_files时被忽略
     // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
     pub hparams_file: String,
     // ————————————————————————————————————————————————————————————————————————
+    // 候选超参数文件名列表，按优先级排列
+    // 每个version目录使用其中第一个存在的文件，适用于混合了不同命名的日志集合
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub hparams_files: Vec<String>,
+    // ————————————————————————————————————————————————————————————————————————
     // 是否启用缓存功能，提高程序运行效率
     // ————————————————————————————————————————————————————————————————————————
     pub cache_enabled: bool,
+    // ————————————————————————————————————————————————————————————————————————
+    // 扫描日志目录时跳过的目录名模式，支持`*`和`?`通配符
+    // 默认跳过所有以`.`开头的隐藏目录（如.git、.ipynb_checkpoints）
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default = "default_ignore_dir_patterns")]
+    pub ignore_dir_patterns: Vec<String>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 版本标注的保存路径，未指定时保存到日志目录下的tags.json
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub tags_file: Option<String>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 是否计算每个超参数文件内容的SHA-256，显示在详情面板底部，用于复现性审计
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub compute_content_hash: bool,
+    // ————————————————————————————————————————————————————————————————————————
+    // 超参数文件的最大字节数，超过时跳过该文件并给出警告，避免损坏的超大文件耗尽内存
+    // 设为0表示不限制
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default = "default_max_hparams_file_bytes")]
+    pub max_hparams_file_bytes: u64,
+    // ————————————————————————————————————————————————————————————————————————
+    // 版本目录下的备注文件名（如notes.md），存在时在详情面板中显示其内容
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub notes_file: Option<String>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 是否容忍YAML映射中的重复键：保留最后一次出现的值，并对每个重复键给出警告
+    // 关闭时含重复键的文件解析失败并被跳过
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub warn_duplicate_keys: bool,
+    // ————————————————————————————————————————————————————————————————————————
+    // 是否只扫描log_dir下最近修改的实验子目录（直接包含version_*目录的直接子目录）
+    // 适用于log_dir下有多个实验文件夹、只关心最新一次实验的情况
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub auto_latest_experiment: bool,
+}
+
+fn default_max_hparams_file_bytes() -> u64 {
+    8 * 1024 * 1024
+}
+
+fn default_ignore_dir_patterns() -> Vec<String> {
+    vec![".*".to_string()]
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            log_dir: String::new(),
+            hparams_file: String::new(),
+            hparams_files: Vec::new(),
+            cache_enabled: false,
+            ignore_dir_patterns: default_ignore_dir_patterns(),
+            tags_file: None,
+            compute_content_hash: false,
+            max_hparams_file_bytes: default_max_hparams_file_bytes(),
+            notes_file: None,
+            warn_duplicate_keys: false,
+            auto_latest_experiment: false,
+        }
+    }
+}
+
+impl GeneralConfig {
+    /// 返回按优先级排列的候选超参数文件名
+    ///
+    /// 配置了非空的`hparams_files`时使用该列表，否则只使用`hparams_file`
+    pub fn hparams_file_candidates(&self) -> Vec<String> {
+        if self.hparams_files.is_empty() {
+            vec![self.hparams_file.clone()]
+        } else {
+            self.hparams_files.clone()
+        }
+    }
+
+    /// 返回版本标注文件的路径
+    pub fn tags_file_path(&self) -> PathBuf {
+        match &self.tags_file {
+            Some(tags_file) => PathBuf::from(tags_file),
+            None => Path::new(&self.log_dir).join("tags.json"),
+        }
+    }
 }
 
 /// 忽略参数配置
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct IgnoredConfig {
     // ————————————————————————————————————————————————————————————————————————
     // 需要忽略的参数名称列表，这些参数在比较和分组时将被排除
+    // 写作`name=re:pattern`时，只有值匹配正则表达式的参数才被忽略
     // ————————————————————————————————————————————————————————————————————————
     pub parameters: Vec<String>,
 }
 
+impl IgnoredConfig {
+    /// 检查所有值模式能否编译为正则表达式
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for entry in &self.parameters {
+            IgnoreRule::parse(entry)?;
+        }
+        Ok(())
+    }
+}
+
+/// 派生参数配置
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DerivedParametersConfig {
+    // ————————————————————————————————————————————————————————————————————————
+    // 派生参数，键为注入到超参数中的参数名，值为引用已有数值参数的算术表达式
+    // 与超参数文件中已有的参数同名时不覆盖文件中的值
+    // 如 effective_batch_size = "batch_size * num_gpus"，语法见expression模块
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+}
+
+impl DerivedParametersConfig {
+    /// 检查所有表达式能否解析
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (name, source) in &self.parameters {
+            Expression::parse(source)
+                .with_context(|| format!("Invalid expression for derived parameter '{}'", name))?;
+        }
+        Ok(())
+    }
+
+    /// 解析所有派生参数，按参数名排序，跳过无法解析的表达式（配置加载时已校验过）
+    pub fn expressions(&self) -> Vec<(&str, Expression)> {
+        self.parameters
+            .iter()
+            .filter_map(|(name, source)| {
+                Expression::parse(source)
+                    .ok()
+                    .map(|expression| (name.as_str(), expression))
+            })
+            .collect()
+    }
+}
+
+/// 值模式忽略规则的分隔符，如`output_dir=re:^/tmp/`
+const VALUE_PATTERN_SEPARATOR: &str = "=re:";
+
+/// 一条忽略规则，由`ignored_parameters.parameters`中的一项解析而来
+#[derive(Debug, Clone)]
+pub struct IgnoreRule {
+    pub name: String,                  // 参数名
+    pub value_pattern: Option<Regex>, // 值模式，None表示只按名称忽略
+}
+
+impl IgnoreRule {
+    /// 解析`name`或`name=re:pattern`形式的忽略项
+    pub fn parse(entry: &str) -> anyhow::Result<Self> {
+        match entry.split_once(VALUE_PATTERN_SEPARATOR) {
+            Some((name, pattern)) => Ok(Self {
+                name: name.to_string(),
+                value_pattern: Some(Regex::new(pattern).with_context(|| {
+                    format!("Invalid value pattern for ignored parameter '{}'", name)
+                })?),
+            }),
+            None => Ok(Self {
+                name: entry.to_string(),
+                value_pattern: None,
+            }),
+        }
+    }
+
+    /// 解析所有忽略项，跳过无法编译的值模式（配置加载时已校验过）
+    pub fn parse_all(entries: &[String]) -> Vec<Self> {
+        entries
+            .iter()
+            .filter_map(|entry| Self::parse(entry).ok())
+            .collect()
+    }
+
+    /// 参数名相同且（如有值模式）值匹配时返回true
+    pub fn matches(&self, key: &str, value: &ParameterValue) -> bool {
+        self.name == key
+            && self
+                .value_pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&value.to_simple_string()))
+    }
+}
+
 /// 容差配置
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct ToleranceConfig {
     pub float_tolerance: f64, // 浮点数比较的容差范围，两个浮点数差值小于此值时视为相等
     pub int_tolerance: i64,   // 整数比较的容差范围，两个整数差值小于此值时视为相等
     pub string_case_sensitive: bool, // 字符串比较时是否区分大小写，true为区分大小写，false为不区分
+    // ————————————————————————————————————————————————————————————————————————
+    // 按参数名覆盖的数值容差，键为参数名，值为该参数使用的容差
+    // 浮点数参数直接使用该值，整数参数使用其整数部分
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub per_parameter_tolerance: HashMap<String, f64>,
+    #[serde(default)]
+    pub coerce_boolean_strings: bool, // 是否将YAML中的yes/no/on/off字符串解析为布尔值，默认保持字符串
+}
+
+impl ToleranceConfig {
+    /// 获取指定参数的浮点数容差，未单独配置时使用全局容差
+    pub fn float_tolerance_for(&self, key: &str) -> f64 {
+        self.per_parameter_tolerance
+            .get(key)
+            .copied()
+            .unwrap_or(self.float_tolerance)
+    }
+
+    /// 获取指定参数的整数容差，未单独配置时使用全局容差
+    pub fn int_tolerance_for(&self, key: &str) -> i64 {
+        self.per_parameter_tolerance
+            .get(key)
+            .map(|tolerance| tolerance.trunc() as i64)
+            .unwrap_or(self.int_tolerance)
+    }
 }
 
 /// 分组配置
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct GroupingConfig {
     // ————————————————————————————————————————————————————————————————————————
     // 是否使用所有参数进行分组，true时使用所有参数，false时只使用指定参数
+    // false且未指定grouping_parameters时回退到只使用main_key进行分组
     // ————————————————————————————————————————————————————————————————————————
     pub group_by_all_parameters: bool, // 是否使用所有参数进行分组，true时使用所有参数，false时只使用指定参数
     // ————————————————————————————————————————————————————————————————————————
-    // 分组参数列表，当group_by_all_parameters为false时使用这些参数进行分组
+    // 分组参数列表，指定且非空时优先于group_by_all_parameters使用这些参数进行分组
     // ————————————————————————————————————————————————————————————————————————
     pub grouping_parameters: Option<Vec<String>>,
     pub similarity_threshold: usize, // 相似度阈值，用于判断实验是否属于同一组
     #[serde(default)]
+    pub similarity_mode: SimilarityMode, // similarity_threshold的含义：不同参数的个数或百分比
+    #[serde(default)]
     pub main_key: Option<Vec<String>>, // 主键参数列表，用于定义实验的主要标识参数
+    #[serde(default)]
+    pub main_key_missing_policy: MainKeyMissingPolicy, // 版本缺少main_key时的处理方式
+    #[serde(default)]
+    pub unordered_map_lists: bool, // 映射列表是否作为无序集合比较，false时按索引展平为`key-0-field`
+    #[serde(default)]
+    pub grouping_strategy: GroupingStrategy, // 分组策略
+    #[serde(default)]
+    pub label_parameters: Option<Vec<String>>, // 用于生成实验组可读标签的参数，如 model=cnn, lr=0.001
+    // ————————————————————————————————————————————————————————————————————————
+    // 比较参数集时是否忽略只存在于其中一方的参数，true时只比较双方都有的参数
+    // 适用于代码版本新增了参数、其余参数都相同的运行
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub ignore_missing_keys: bool,
+    // ————————————————————————————————————————————————————————————————————————
+    // 计算实验组ID时使用的参数子集，指定且非空时只对这些参数计算哈希，
+    // 之后新增其他参数不会改变已有实验组的ID；未指定时使用实际生效的分组参数
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub id_parameters: Option<Vec<String>>,
+    #[serde(default)]
+    pub min_replication: usize, // 每个参数配置至少应运行的次数，成员数少于该值的实验组被标记为重复次数不足，0表示不检查
+}
+
+/// 相似组判定时`similarity_threshold`的含义
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SimilarityMode {
+    #[default]
+    Count, // 不同参数的个数不超过阈值时视为相似
+    Percent, // 不同参数占两组参数并集的百分比不超过阈值时视为相似
+}
+
+/// 实验分组策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupingStrategy {
+    #[default]
+    Hash, // 依次将版本加入第一个匹配的组，与组的基准参数比较
+    Cluster, // 两两比较，容差内相等的版本传递性地合并为同一组，速度较慢但不会拆分容差内相等的版本
+}
+
+/// 版本缺少main_key时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MainKeyMissingPolicy {
+    #[default]
+    Error, // 报错并停止加载
+    Skip,      // 跳过缺少main_key的版本
+    Ungrouped, // 保留这些版本，统一放入一个未分组的实验组
+}
+
+impl GroupingConfig {
+    /// 返回实际生效的分组参数
+    ///
+    /// - 指定了非空的`grouping_parameters`时，始终使用这些参数
+    /// - 否则`group_by_all_parameters`为true时返回None，表示使用所有参数进行分组
+    /// - 否则回退到只使用`main_key`进行分组，未配置`main_key`时同样返回None，
+    ///   这种配置会在加载时被`validate`拒绝
+    pub fn effective_grouping_parameters(&self) -> Option<&[String]> {
+        match &self.grouping_parameters {
+            Some(params) if !params.is_empty() => Some(params),
+            _ if self.group_by_all_parameters => None,
+            _ => self.main_key.as_deref(),
+        }
+    }
+
+    /// 返回计算实验组ID时使用的参数，未指定`id_parameters`时与实际生效的分组参数相同
+    pub fn effective_id_parameters(&self) -> Option<&[String]> {
+        match &self.id_parameters {
+            Some(params) if !params.is_empty() => Some(params),
+            _ => self.effective_grouping_parameters(),
+        }
+    }
+
+    /// 检查分组配置是否有效
+    ///
+    /// `group_by_all_parameters`为false时必须提供`grouping_parameters`或`main_key`
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let has_grouping_parameters = self
+            .grouping_parameters
+            .as_ref()
+            .is_some_and(|params| !params.is_empty());
+        let has_main_key = self.main_key.as_ref().is_some_and(|keys| !keys.is_empty());
+
+        if !self.group_by_all_parameters && !has_grouping_parameters && !has_main_key {
+            anyhow::bail!(
+                "group_by_all_parameters is false, but neither grouping_parameters nor main_key is set; \
+                 please specify the parameters to group by"
+            );
+        }
+        if self.similarity_mode == SimilarityMode::Percent && self.similarity_threshold > 100 {
+            anyhow::bail!(
+                "similarity_threshold is {} but similarity_mode is \"percent\"; \
+                 the threshold must be between 0 and 100",
+                self.similarity_threshold
+            );
+        }
+        Ok(())
+    }
 }
 
 /// 差异比较配置
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct DiffConfig {
     pub show_detailed_diff: bool, // 是否显示详细的差异信息，true时显示所有差异，false时只显示关键差异
     pub diff_format: String,      // 差异显示格式，定义差异信息的展示方式
     pub highlight_diff_keys: bool, // 是否高亮显示差异键名，true时突出显示有差异的参数名
+    #[serde(default)]
+    pub show_numeric_delta: bool, // 是否在数值参数差异后附加差值或倍数，如 (+0.009) 或 (×10)
 }
 
 /// TUI界面配置
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct TuiConfig {
-    pub color_theme: String,      // 颜色主题名称，定义界面的整体配色方案
-    pub colors: ColorConfig,      // 颜色配置，定义各种界面元素的具体颜色
+    pub color_theme: String,      // 颜色主题名称（dark、light、solarized），定义界面的整体配色方案
+    #[serde(default)]
+    pub colors: ColorOverrides,   // 颜色覆盖配置，单独指定的颜色优先于主题颜色
     pub layout: String,           // 界面布局方式，定义界面的整体排列结构
     pub show_help_bar: bool,      // 是否显示帮助栏，true时在界面底部显示操作提示
     pub auto_expand_groups: bool, // 是否自动展开实验组，true时默认展开所有分组
@@ -108,13 +586,59 @@ pub struct TuiConfig {
     pub version_panel_proportion: u16, // 版本面板占比（%），控制版本列表和详情面板的高度比例
     pub status_bar_height: u16,   // 状态栏高度（行数）
     pub scroll_indicators: bool,  // 是否显示滚动指示器
+    #[serde(default = "default_float_display_precision")]
+    pub float_display_precision: usize, // 浮点数显示精度（小数位数），仅影响显示，不影响比较和哈希
+    #[serde(default = "default_true")]
+    pub trim_trailing_zeros: bool, // 是否去除浮点数显示末尾多余的0，如 0.100000 显示为 0.1
+    #[serde(default)]
+    pub wrap_navigation: bool, // 是否循环导航，true时在列表边界处移动会跳到另一端，false时停在边界
+    #[serde(default)]
+    pub version_list_max_columns: usize, // 版本列表最大列数，0表示根据宽度自动计算
+    #[serde(default)]
+    pub version_list_min_column_width: usize, // 版本列表最小列宽，名称不足此宽度时用空格填充
+    #[serde(default)]
+    pub pinned_parameters: Vec<String>, // 固定显示在详情面板顶部的参数，按列表顺序显示，其余参数按名称排序
+    #[serde(default = "default_true")]
+    pub detail_wrap: bool, // 详情面板是否自动换行，false时超长行被截断
+    #[serde(default = "default_true")]
+    pub detail_trim: bool, // 自动换行时是否去除行首空白，false时保留缩进
+    #[serde(default)]
+    pub detail_sort_mode: DetailSortMode, // 详情面板中非固定参数的排列顺序
+    #[serde(default)]
+    pub version_sort_mode: VersionSortMode, // 版本列表的排列顺序
+    #[serde(default = "default_flag_key_separator")]
+    pub flag_key_separator: String, // 复制命令行参数时展平参数名的层级分隔符，如 "." 或 "_"
+    #[serde(default)]
+    pub sort_metric: Option<String>, // 排序和渐变着色使用的指标名，也可以是数值型超参数名，None表示不使用；方向由[metrics]中的metric_directions决定
+    #[serde(default = "default_true")]
+    pub show_member_varying_params: bool, // 是否在实验组成员列表中附加各成员取值不同的参数，如 version_3 (lr=0.01)
+    #[serde(default)]
+    pub max_versions_displayed: usize, // 版本列表每页最多显示的版本数，超出时分页显示，0表示不限制；分组和导出仍使用全部版本
+    #[serde(default)]
+    pub collapse_group_versions: bool, // 是否将版本列表中同一实验组的多个版本折叠为一行，按确认键展开或折叠
+    #[serde(default)]
+    pub version_name_template: Option<String>, // 版本列表中显示名称的模板，如 "v{num}"、"{num}: {model}"，None表示使用版本目录名
+    #[serde(default)]
+    pub rescan_interval_ms: u64, // TUI运行时后台重新扫描日志目录、加载新版本的间隔（毫秒），0表示不重新扫描
+}
+
+fn default_float_display_precision() -> usize {
+    6
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_flag_key_separator() -> String {
+    ".".to_string()
 }
 
 impl Default for TuiConfig {
     fn default() -> Self {
         Self {
-            color_theme: "default".to_string(),
-            colors: ColorConfig::default(),
+            color_theme: "dark".to_string(),
+            colors: ColorOverrides::default(),
             layout: "vertical".to_string(),
             show_help_bar: true,
             auto_expand_groups: false,
@@ -123,12 +647,37 @@ impl Default for TuiConfig {
             version_panel_proportion: 70,  // 版本面板默认高度 70 %
             status_bar_height: 3,    // 状态栏默认高度3行
             scroll_indicators: true, // 默认显示滚动指示器
+            float_display_precision: default_float_display_precision(), // 默认保留6位小数
+            trim_trailing_zeros: true, // 默认去除末尾的0，与配置模板一致
+            wrap_navigation: false, // 默认在边界处停止
+            version_list_max_columns: 0, // 默认自动计算列数
+            version_list_min_column_width: 0, // 默认不填充
+            pinned_parameters: Vec::new(), // 默认不固定任何参数
+            detail_wrap: true, // 默认自动换行
+            detail_trim: true, // 默认去除行首空白
+            detail_sort_mode: DetailSortMode::default(), // 默认按名称排序
+            version_sort_mode: VersionSortMode::default(), // 默认按名称排序
+            flag_key_separator: default_flag_key_separator(), // 默认使用点分路径
+            sort_metric: None, // 默认不按指标排序和着色
+            show_member_varying_params: true, // 默认显示成员的变化参数
+            max_versions_displayed: 0, // 默认不限制
+            collapse_group_versions: false, // 默认逐个显示版本
+            version_name_template: None, // 默认显示版本目录名
+            rescan_interval_ms: 0,       // 默认不重新扫描
         }
     }
 }
 
+impl TuiConfig {
+    /// 解析最终使用的颜色：显式配置的`colors` > `color_theme`主题 > 默认颜色
+    pub fn resolved_colors(&self) -> ColorConfig {
+        self.colors.apply_to(theme_colors(&self.color_theme))
+    }
+}
+
 /// 颜色配置
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct ColorConfig {
     pub same_experiment: String, // 相同实验的颜色标识，用于标记完全相同的实验
     pub similar_experiment: String, // 相似实验的颜色标识，用于标记相似的实验
@@ -157,8 +706,82 @@ impl Default for ColorConfig {
     }
 }
 
+/// 获取内置主题的颜色配置，未知主题名称返回默认颜色
+///
+/// 支持的主题：`dark`（与默认颜色相同）、`light`、`solarized`，名称不区分大小写
+pub fn theme_colors(name: &str) -> ColorConfig {
+    let palette = |colors: [&str; 9]| {
+        let [
+            same_experiment,
+            similar_experiment,
+            selected,
+            background,
+            text,
+            border,
+            highlight,
+            status_bar_bg,
+            status_bar_text,
+        ] = colors.map(str::to_string);
+        ColorConfig {
+            same_experiment,
+            similar_experiment,
+            selected,
+            background,
+            text,
+            border,
+            highlight,
+            status_bar_bg,
+            status_bar_text,
+        }
+    };
+
+    match name.to_ascii_lowercase().as_str() {
+        "light" => palette([
+            "green", "magenta", "blue", "white", "black", "dark_gray", "black", "gray", "black",
+        ]),
+        "solarized" => palette([
+            "#859900", "#b58900", "#268bd2", "#002b36", "#839496", "#2aa198", "#93a1a1",
+            "#073642", "#93a1a1",
+        ]),
+        _ => ColorConfig::default(),
+    }
+}
+
+/// 颜色覆盖配置，未指定的颜色使用主题颜色
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColorOverrides {
+    pub same_experiment: Option<String>, // 相同实验的颜色标识
+    pub similar_experiment: Option<String>, // 相似实验的颜色标识
+    pub selected: Option<String>,        // 选中状态的颜色标识
+    pub background: Option<String>,      // 背景颜色
+    pub text: Option<String>,            // 文本颜色
+    pub border: Option<String>,          // 边框颜色
+    pub highlight: Option<String>,       // 高亮颜色
+    pub status_bar_bg: Option<String>,   // 状态栏背景色
+    pub status_bar_text: Option<String>, // 状态栏文本色
+}
+
+impl ColorOverrides {
+    /// 用已指定的颜色覆盖基础颜色配置
+    pub fn apply_to(&self, base: ColorConfig) -> ColorConfig {
+        let pick = |value: &Option<String>, fallback: String| value.clone().unwrap_or(fallback);
+        ColorConfig {
+            same_experiment: pick(&self.same_experiment, base.same_experiment),
+            similar_experiment: pick(&self.similar_experiment, base.similar_experiment),
+            selected: pick(&self.selected, base.selected),
+            background: pick(&self.background, base.background),
+            text: pick(&self.text, base.text),
+            border: pick(&self.border, base.border),
+            highlight: pick(&self.highlight, base.highlight),
+            status_bar_bg: pick(&self.status_bar_bg, base.status_bar_bg),
+            status_bar_text: pick(&self.status_bar_text, base.status_bar_text),
+        }
+    }
+}
+
 /// 详细信息面板位置配置
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub enum DetailPanelPosition {
     Top,
     Bottom,
@@ -172,8 +795,29 @@ impl Default for DetailPanelPosition {
     }
 }
 
+/// 详情面板中参数的排列顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetailSortMode {
+    Insertion, // 按参数在超参数文件中出现的顺序
+    #[default]
+    Alpha, // 按参数名排序
+    ByType, // 按值的类型分组（字符串、数字、布尔值、列表、映射），组内按参数名排序
+}
+
+/// 版本列表的排列顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionSortMode {
+    #[default]
+    Name, // 按显示名称的自然顺序
+    RecentlyModified, // 按版本目录的修改时间从新到旧，无法读取修改时间的版本排在最后
+    Metric, // 按`sort_metric`指定的指标值排列，缺少该指标的版本排在最后
+}
+
 /// 键盘绑定配置
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct KeybindingsConfig {
     pub up: String,                 // 向上移动键，用于在列表中向上选择
     pub down: String,               // 向下移动键，用于在列表中向下选择
@@ -187,6 +831,235 @@ pub struct KeybindingsConfig {
     pub switch_view: String,        // 切换视图键，用于在版本列表和实验组视图间切换
     pub scroll_detail_up: String,   // 详情向上滚动键
     pub scroll_detail_down: String, // 详情向下滚动键
+    #[serde(default = "default_diff_previous_key")]
+    pub diff_previous: String, // 与上一个版本（版本号更小的最近版本）比较差异的快捷键
+    #[serde(default = "default_scroll_detail_left_key")]
+    pub scroll_detail_left: String, // 详情向左滚动键（关闭自动换行时有效）
+    #[serde(default = "default_scroll_detail_right_key")]
+    pub scroll_detail_right: String, // 详情向右滚动键（关闭自动换行时有效）
+    #[serde(default = "default_tag_key")]
+    pub tag: String, // 为当前版本添加或编辑备注的快捷键
+    #[serde(default = "default_toggle_hidden_params_key")]
+    pub toggle_hidden_params: String, // 切换是否在详情面板中显示被忽略参数和共同参数的快捷键
+    #[serde(default = "default_select_all_key")]
+    pub select_all: String, // 选中所有可见版本的快捷键
+    #[serde(default = "default_invert_selection_key")]
+    pub invert_selection: String, // 反转可见版本选中状态的快捷键
+    #[serde(default = "default_grow_panel_key")]
+    pub grow_panel: String, // 增大版本面板占比的快捷键
+    #[serde(default = "default_shrink_panel_key")]
+    pub shrink_panel: String, // 减小版本面板占比的快捷键
+    #[serde(default = "default_export_view_key")]
+    pub export_view: String, // 导出当前视图的快捷键
+    #[serde(default = "default_toggle_ignore_key")]
+    pub toggle_ignore: String, // 运行时添加或移除忽略参数的快捷键
+    #[serde(default = "default_save_config_key")]
+    pub save_config: String, // 将当前生效的配置写回配置文件的快捷键
+    #[serde(default = "default_copy_group_flags_key")]
+    pub copy_group_flags: String, // 复制当前实验组命令行参数的快捷键
+    #[serde(default = "default_next_page_key")]
+    pub next_page: String, // 版本列表分页显示时翻到下一页的快捷键
+    #[serde(default = "default_previous_page_key")]
+    pub previous_page: String, // 版本列表分页显示时翻到上一页的快捷键
+    #[serde(default = "default_info_key")]
+    pub info: String, // 显示已加载数据集概要信息的快捷键
+    #[serde(default = "default_export_group_diff_key")]
+    pub export_group_diff: String, // 导出多选版本所属两个实验组之间差异报告的快捷键
+    #[serde(default = "default_pin_version_key")]
+    pub pin_version: String, // 固定或取消固定当前版本、在详情面板中并排对照的快捷键
+    #[serde(default = "default_next_similar_group_key")]
+    pub next_similar_group: String, // 跳转到与当前实验组相似的下一个实验组的快捷键
+    #[serde(default = "default_toggle_case_sensitivity_key")]
+    pub toggle_case_sensitivity: String, // 运行时切换字符串比较是否区分大小写并重新分组的快捷键
+}
+
+fn default_diff_previous_key() -> String {
+    "p".to_string()
+}
+
+fn default_scroll_detail_left_key() -> String {
+    "[".to_string()
+}
+
+fn default_scroll_detail_right_key() -> String {
+    "]".to_string()
+}
+
+fn default_tag_key() -> String {
+    "t".to_string()
+}
+
+fn default_toggle_hidden_params_key() -> String {
+    "i".to_string()
+}
+
+fn default_select_all_key() -> String {
+    "a".to_string()
+}
+
+fn default_invert_selection_key() -> String {
+    "x".to_string()
+}
+
+fn default_grow_panel_key() -> String {
+    "+".to_string()
+}
+
+fn default_shrink_panel_key() -> String {
+    "-".to_string()
+}
+
+fn default_export_view_key() -> String {
+    "e".to_string()
+}
+
+fn default_toggle_ignore_key() -> String {
+    "I".to_string()
+}
+
+fn default_save_config_key() -> String {
+    "S".to_string()
+}
+
+fn default_copy_group_flags_key() -> String {
+    "c".to_string()
+}
+
+fn default_next_page_key() -> String {
+    "n".to_string()
+}
+
+fn default_previous_page_key() -> String {
+    "N".to_string()
+}
+
+fn default_info_key() -> String {
+    "o".to_string()
+}
+
+fn default_export_group_diff_key() -> String {
+    "g".to_string()
+}
+
+fn default_pin_version_key() -> String {
+    "f".to_string()
+}
+
+fn default_next_similar_group_key() -> String {
+    "s".to_string()
+}
+
+fn default_toggle_case_sensitivity_key() -> String {
+    "C".to_string()
+}
+
+/// 除单个可打印字符外，按键绑定可以使用的命名按键
+const NAMED_KEYS: [&str; 6] = ["up", "down", "left", "right", "enter", "space"];
+
+/// 解析后的按键绑定，如`ctrl+r`解析为带Ctrl修饰的`r`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySpec {
+    pub key: String, // 单个可打印字符或命名按键（见NAMED_KEYS）
+    pub ctrl: bool,  // 是否需要按住Ctrl
+    pub alt: bool,   // 是否需要按住Alt
+}
+
+impl KeySpec {
+    /// 解析`k`、`space`、`ctrl+r`、`alt+f`、`ctrl+alt+x`形式的按键绑定
+    ///
+    /// 修饰键前缀不区分大小写；`+`本身也可以作为按键，如`+`或`ctrl++`
+    pub fn parse(binding: &str) -> anyhow::Result<Self> {
+        let mut spec = Self {
+            key: binding.to_string(),
+            ctrl: false,
+            alt: false,
+        };
+        loop {
+            let lower = spec.key.to_lowercase();
+            if lower.starts_with("ctrl+") && spec.key.len() > "ctrl+".len() {
+                spec.ctrl = true;
+                spec.key = spec.key["ctrl+".len()..].to_string();
+            } else if lower.starts_with("alt+") && spec.key.len() > "alt+".len() {
+                spec.alt = true;
+                spec.key = spec.key["alt+".len()..].to_string();
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = spec.key.chars();
+        let is_single_char = matches!(
+            (chars.next(), chars.next()),
+            (Some(c), None) if !c.is_control() && !c.is_whitespace()
+        );
+        if !is_single_char && !NAMED_KEYS.contains(&spec.key.as_str()) {
+            anyhow::bail!(
+                "Invalid key {:?}: expected a single printable character or one of {}, \
+                 optionally prefixed by ctrl+ or alt+",
+                binding,
+                NAMED_KEYS.join(", ")
+            );
+        }
+        Ok(spec)
+    }
+}
+
+impl KeybindingsConfig {
+    /// 所有按键绑定及其配置项名称，按配置文件中的顺序排列
+    pub fn bindings(&self) -> Vec<(&'static str, &str)> {
+        vec![
+            ("up", &self.up),
+            ("down", &self.down),
+            ("left", &self.left),
+            ("right", &self.right),
+            ("select", &self.select),
+            ("confirm", &self.confirm),
+            ("quit", &self.quit),
+            ("help", &self.help),
+            ("filter", &self.filter),
+            ("switch_view", &self.switch_view),
+            ("scroll_detail_up", &self.scroll_detail_up),
+            ("scroll_detail_down", &self.scroll_detail_down),
+            ("diff_previous", &self.diff_previous),
+            ("scroll_detail_left", &self.scroll_detail_left),
+            ("scroll_detail_right", &self.scroll_detail_right),
+            ("tag", &self.tag),
+            ("toggle_hidden_params", &self.toggle_hidden_params),
+            ("select_all", &self.select_all),
+            ("invert_selection", &self.invert_selection),
+            ("grow_panel", &self.grow_panel),
+            ("shrink_panel", &self.shrink_panel),
+            ("export_view", &self.export_view),
+            ("toggle_ignore", &self.toggle_ignore),
+            ("save_config", &self.save_config),
+            ("copy_group_flags", &self.copy_group_flags),
+            ("next_page", &self.next_page),
+            ("previous_page", &self.previous_page),
+            ("info", &self.info),
+            ("export_group_diff", &self.export_group_diff),
+            ("pin_version", &self.pin_version),
+            ("next_similar_group", &self.next_similar_group),
+            ("toggle_case_sensitivity", &self.toggle_case_sensitivity),
+        ]
+    }
+
+    /// 检查每个按键都能被识别（见[`KeySpec::parse`]），并且没有两个操作绑定到同一个按键
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut seen: HashMap<KeySpec, &str> = HashMap::new();
+        for (action, key) in self.bindings() {
+            let spec =
+                KeySpec::parse(key).with_context(|| format!("Invalid binding for '{}'", action))?;
+            if let Some(previous) = seen.insert(spec, action) {
+                anyhow::bail!(
+                    "Key {:?} is bound to both '{}' and '{}'",
+                    key,
+                    previous,
+                    action
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for KeybindingsConfig {
@@ -204,12 +1077,142 @@ impl Default for KeybindingsConfig {
             switch_view: "v".to_string(),
             scroll_detail_up: "u".to_string(),
             scroll_detail_down: "d".to_string(),
+            diff_previous: default_diff_previous_key(),
+            scroll_detail_left: default_scroll_detail_left_key(),
+            scroll_detail_right: default_scroll_detail_right_key(),
+            tag: default_tag_key(),
+            toggle_hidden_params: default_toggle_hidden_params_key(),
+            select_all: default_select_all_key(),
+            invert_selection: default_invert_selection_key(),
+            grow_panel: default_grow_panel_key(),
+            shrink_panel: default_shrink_panel_key(),
+            export_view: default_export_view_key(),
+            toggle_ignore: default_toggle_ignore_key(),
+            save_config: default_save_config_key(),
+            copy_group_flags: default_copy_group_flags_key(),
+            next_page: default_next_page_key(),
+            previous_page: default_previous_page_key(),
+            info: default_info_key(),
+            export_group_diff: default_export_group_diff_key(),
+            pin_version: default_pin_version_key(),
+            next_similar_group: default_next_similar_group_key(),
+            toggle_case_sensitivity: default_toggle_case_sensitivity_key(),
+        }
+    }
+}
+
+/// 指标配置
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    // ————————————————————————————————————————————————————————————————————————
+    // 版本目录下的指标文件名，CSVLogger默认写出metrics.csv
+    // 文件不存在时该版本没有指标
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default = "default_metrics_file")]
+    pub metrics_file: String,
+    // ————————————————————————————————————————————————————————————————————————
+    // 将指标的多行记录汇总为单个值的默认方式
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub default_aggregation: MetricAggregation,
+    // ————————————————————————————————————————————————————————————————————————
+    // 按指标名覆盖汇总方式，如 { val_acc = "max", val_loss = "min" }
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub metric_aggregation: HashMap<String, MetricAggregation>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 记录监控指标名的超参数，如 call_back_monitor: VAL--acc
+    // 详情面板显示该指标取得最佳值的epoch
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default = "default_monitor_parameter")]
+    pub monitor_parameter: String,
+    // ————————————————————————————————————————————————————————————————————————
+    // 按指标名配置优化方向，如 { val_acc = "higher", val_loss = "lower" }
+    // 未配置的指标按名称推断：包含loss、err等字样的越小越好，其余越大越好
+    // ————————————————————————————————————————————————————————————————————————
+    #[serde(default)]
+    pub metric_directions: HashMap<String, MetricDirection>,
+}
+
+fn default_metrics_file() -> String {
+    "metrics.csv".to_string()
+}
+
+fn default_monitor_parameter() -> String {
+    "call_back_monitor".to_string()
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            metrics_file: default_metrics_file(),
+            default_aggregation: MetricAggregation::default(),
+            metric_aggregation: HashMap::new(),
+            monitor_parameter: default_monitor_parameter(),
+            metric_directions: HashMap::new(),
+        }
+    }
+}
+
+impl MetricsConfig {
+    /// 获取指标的汇总方式，未单独配置的指标使用默认方式
+    pub fn aggregation_for(&self, metric: &str) -> MetricAggregation {
+        self.metric_aggregation
+            .get(metric)
+            .copied()
+            .unwrap_or(self.default_aggregation)
+    }
+
+    /// 获取指标的优化方向，未单独配置的指标按名称推断
+    pub fn direction_for(&self, metric: &str) -> MetricDirection {
+        self.metric_directions
+            .get(metric)
+            .copied()
+            .unwrap_or_else(|| MetricDirection::infer(metric))
+    }
+
+    /// 指标是否越大越好
+    pub fn higher_is_better(&self, metric: &str) -> bool {
+        self.direction_for(metric) == MetricDirection::Higher
+    }
+}
+
+/// 指标的优化方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricDirection {
+    Higher, // 越大越好，如准确率
+    Lower,  // 越小越好，如损失
+}
+
+impl MetricDirection {
+    /// 根据指标名推断方向：名称包含loss、err等字样的指标越小越好，其余越大越好
+    pub fn infer(metric: &str) -> Self {
+        const LOWER_IS_BETTER: [&str; 6] = ["loss", "err", "mse", "mae", "perplexity", "ppl"];
+        let metric = metric.to_lowercase();
+        if LOWER_IS_BETTER.iter().any(|word| metric.contains(word)) {
+            Self::Lower
+        } else {
+            Self::Higher
         }
     }
 }
 
+/// 指标的汇总方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricAggregation {
+    #[default]
+    Last, // 最后一条记录，通常对应最后一个epoch
+    Max,  // 最大值
+    Min,  // 最小值
+    Mean, // 所有记录的平均值
+}
+
 /// 测试脚本配置
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct TestScriptConfig {
     pub path: String,                    // 测试脚本文件路径，指定要执行的测试脚本位置
     pub default_args: DefaultArgsConfig, // 默认参数配置，定义脚本的默认执行参数
@@ -218,19 +1221,22 @@ pub struct TestScriptConfig {
 }
 
 /// 默认参数配置
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct DefaultArgsConfig {
     // ————————————————————————————————————————————————————————————————————————
     // 默认过滤条件，用于筛选实验数据
     // ————————————————————————————————————————————————————————————————————————
     #[serde(
         default,
-        deserialize_with = "crate::models::utils::deserialize_optional_string"
+        deserialize_with = "crate::models::utils::deserialize_optional_string",
+        serialize_with = "crate::models::utils::serialize_optional_string"
     )]
     pub filter: Option<String>, // 默认过滤条件，用于筛选实验数据
     #[serde(
         default,
-        deserialize_with = "crate::models::utils::deserialize_optional_string"
+        deserialize_with = "crate::models::utils::deserialize_optional_string",
+        serialize_with = "crate::models::utils::serialize_optional_string"
     )]
     pub sort_key: Option<String>, // 默认排序键，用于对实验结果进行排序
 }
@@ -263,7 +1269,7 @@ diff:
   diff_format: unified
   highlight_diff_keys: true
 tui:
-  color_theme: default
+  color_theme: dark
   colors:
     same_experiment: green
     similar_experiment: yellow
@@ -311,4 +1317,290 @@ test_script:
         assert_eq!(config.general.cache_enabled, true);
         assert_eq!(config.tolerance.float_tolerance, 0.001);
     }
+
+    #[test]
+    fn test_unknown_config_field_rejected() {
+        let toml_content = include_str!("../../lightning_explorer.toml")
+            .replace("similarity_threshold", "simularity_threshold");
+
+        let error = toml::from_str::<Config>(&toml_content)
+            .expect_err("Misspelled field should be rejected")
+            .to_string();
+        assert!(
+            error.contains("simularity_threshold"),
+            "Error should name the unknown field: {}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_bundled_config_has_no_unknown_fields() {
+        let config: Config = toml::from_str(include_str!("../../lightning_explorer.toml"))
+            .expect("Bundled config should deserialize");
+        assert_eq!(config.general.hparams_file, "hparams.yaml");
+    }
+
+    #[test]
+    fn test_effective_grouping_parameters() {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        // group_by_all_parameters=true且未指定分组参数时使用所有参数
+        let mut grouping = GroupingConfig {
+            group_by_all_parameters: true,
+            grouping_parameters: None,
+            similarity_threshold: 1,
+            similarity_mode: SimilarityMode::default(),
+            main_key: Some(strings(&["model"])),
+            main_key_missing_policy: MainKeyMissingPolicy::default(),
+            unordered_map_lists: false,
+            grouping_strategy: GroupingStrategy::default(),
+            label_parameters: None,
+            ignore_missing_keys: false,
+            id_parameters: None,
+            min_replication: 0,
+        };
+        assert_eq!(grouping.effective_grouping_parameters(), None);
+        assert_eq!(grouping.effective_id_parameters(), None);
+        assert!(grouping.validate().is_ok());
+
+        // 指定了分组参数时始终优先使用
+        grouping.grouping_parameters = Some(strings(&["lr"]));
+        assert_eq!(
+            grouping.effective_grouping_parameters(),
+            Some(&strings(&["lr"])[..])
+        );
+        assert_eq!(
+            grouping.effective_id_parameters(),
+            Some(&strings(&["lr"])[..])
+        );
+
+        // 指定了非空的id_parameters时只用于计算组ID，不影响分组参数
+        grouping.id_parameters = Some(strings(&["model"]));
+        assert_eq!(
+            grouping.effective_id_parameters(),
+            Some(&strings(&["model"])[..])
+        );
+        assert_eq!(
+            grouping.effective_grouping_parameters(),
+            Some(&strings(&["lr"])[..])
+        );
+        grouping.id_parameters = Some(Vec::new());
+        assert_eq!(
+            grouping.effective_id_parameters(),
+            Some(&strings(&["lr"])[..])
+        );
+
+        // group_by_all_parameters=false且分组参数为空时回退到main_key
+        grouping.group_by_all_parameters = false;
+        grouping.grouping_parameters = Some(Vec::new());
+        assert_eq!(
+            grouping.effective_grouping_parameters(),
+            Some(&strings(&["model"])[..])
+        );
+        assert!(grouping.validate().is_ok());
+
+        // 两者都未配置时报错
+        grouping.main_key = None;
+        assert!(grouping.validate().is_err());
+
+        // 百分比模式下阈值不能超过100
+        grouping.main_key = Some(strings(&["model"]));
+        grouping.similarity_mode = SimilarityMode::Percent;
+        grouping.similarity_threshold = 80;
+        assert!(grouping.validate().is_ok());
+        grouping.similarity_threshold = 120;
+        assert!(grouping.validate().is_err());
+    }
+
+    #[test]
+    fn test_ignore_rule_parse() {
+        let rule = IgnoreRule::parse("seed").unwrap();
+        assert_eq!(rule.name, "seed");
+        assert!(rule.value_pattern.is_none());
+
+        let rule = IgnoreRule::parse("output_dir=re:^/tmp/").unwrap();
+        assert_eq!(rule.name, "output_dir");
+        assert_eq!(rule.value_pattern.unwrap().as_str(), "^/tmp/");
+
+        // 嵌套量词在长输入上也不会出现回溯爆炸
+        let rule = IgnoreRule::parse("run_name=re:^(a+)+$").unwrap();
+        let value = ParameterValue::Basic(crate::models::BasicParameterValue::String(format!(
+            "{}b",
+            "a".repeat(10_000)
+        )));
+        assert!(!rule.matches("run_name", &value));
+
+        let ignored = IgnoredConfig {
+            parameters: vec!["seed".to_string(), "output_dir=re:(unclosed".to_string()],
+        };
+        assert!(ignored.validate().is_err());
+    }
+
+    #[test]
+    fn test_key_spec_parse() {
+        let spec = |key: &str, ctrl: bool, alt: bool| KeySpec {
+            key: key.to_string(),
+            ctrl,
+            alt,
+        };
+        assert_eq!(KeySpec::parse("k").unwrap(), spec("k", false, false));
+        assert_eq!(
+            KeySpec::parse("space").unwrap(),
+            spec("space", false, false)
+        );
+        assert_eq!(KeySpec::parse("ctrl+r").unwrap(), spec("r", true, false));
+        assert_eq!(KeySpec::parse("ALT+f").unwrap(), spec("f", false, true));
+        assert_eq!(
+            KeySpec::parse("ctrl+alt+up").unwrap(),
+            spec("up", true, true)
+        );
+        assert_eq!(KeySpec::parse("+").unwrap(), spec("+", false, false));
+        assert_eq!(KeySpec::parse("ctrl++").unwrap(), spec("+", true, false));
+
+        assert!(KeySpec::parse("ctrl+").is_err());
+        assert!(KeySpec::parse("ctrl+rr").is_err());
+        assert!(KeySpec::parse("meta+r").is_err());
+    }
+
+    #[test]
+    fn test_keybindings_validation() {
+        assert!(KeybindingsConfig::default().validate().is_ok());
+
+        // 多字符的绑定无法被识别
+        let keybindings = KeybindingsConfig {
+            up: "shift+k".to_string(),
+            ..KeybindingsConfig::default()
+        };
+        let err = format!("{:#}", keybindings.validate().unwrap_err());
+        assert!(err.contains("\"shift+k\"") && err.contains("'up'"), "{}", err);
+        let keybindings = KeybindingsConfig {
+            tag: "tt".to_string(),
+            ..KeybindingsConfig::default()
+        };
+        assert!(keybindings.validate().is_err());
+        let keybindings = KeybindingsConfig {
+            tag: String::new(),
+            ..KeybindingsConfig::default()
+        };
+        assert!(keybindings.validate().is_err());
+
+        // 两个操作绑定到同一个按键
+        let keybindings = KeybindingsConfig {
+            left: "h".to_string(),
+            ..KeybindingsConfig::default()
+        };
+        let err = keybindings.validate().unwrap_err().to_string();
+        assert_eq!(err, "Key \"h\" is bound to both 'left' and 'help'");
+        // 修饰键前缀不区分大小写，写法不同的同一组合键也算重复
+        let keybindings = KeybindingsConfig {
+            tag: "ctrl+r".to_string(),
+            save_config: "Ctrl+r".to_string(),
+            ..KeybindingsConfig::default()
+        };
+        assert!(keybindings.validate().is_err());
+
+        // 配置加载时报告所在的配置段
+        let mut config = Config::builder().build();
+        config.keybindings.quit = "c".to_string();
+        let err = format!("{:#}", config.validate().unwrap_err());
+        assert!(err.starts_with("Invalid [keybindings] section"), "{}", err);
+    }
+
+    #[test]
+    fn test_config_toml_round_trip() {
+        let mut config = Config::builder()
+            .ignored_parameters(["seed", "output_dir=re:^/tmp/"])
+            .grouping_strategy(GroupingStrategy::Cluster)
+            .similarity_mode(SimilarityMode::Percent)
+            .build();
+        config.grouping.main_key = Some(vec!["model".to_string()]);
+        config.tolerance.per_parameter_tolerance.insert("lr".to_string(), 0.1);
+        config.tui.colors.border = Some("red".to_string());
+        config.tui.detail_sort_mode = DetailSortMode::ByType;
+        config.test_script.default_args.sort_key = Some("fold".to_string());
+
+        let toml_text = toml::to_string(&config).expect("Failed to serialize config");
+        let parsed: Config = toml::from_str(&toml_text).expect("Failed to parse serialized config");
+        assert_eq!(parsed, config);
+
+        // 空的可选字符串写为空字符串，读回仍为None
+        assert!(toml_text.contains("filter = \"\""));
+        assert_eq!(parsed.test_script.default_args.filter, None);
+    }
+
+    #[test]
+    fn test_theme_colors() {
+        let light = theme_colors("light");
+        assert_eq!(light.background, "white");
+        assert_eq!(light.text, "black");
+        assert_eq!(theme_colors("Light"), light);
+        assert_eq!(theme_colors("solarized").background, "#002b36");
+        assert_eq!(theme_colors("dark"), ColorConfig::default());
+        assert_eq!(theme_colors("unknown"), ColorConfig::default());
+    }
+
+    #[test]
+    fn test_resolved_colors_precedence() {
+        let mut tui = TuiConfig {
+            color_theme: "light".to_string(),
+            ..TuiConfig::default()
+        };
+        assert_eq!(tui.resolved_colors(), theme_colors("light"));
+
+        // 显式指定的颜色优先于主题
+        tui.colors = toml::from_str(r##"selected = "red"
+border = "#ff0000""##).unwrap();
+        let colors = tui.resolved_colors();
+        assert_eq!(colors.selected, "red");
+        assert_eq!(colors.border, "#ff0000");
+        assert_eq!(colors.background, "white");
+
+        // 未知主题回退到默认颜色，覆盖仍然生效
+        tui.color_theme = "unknown".to_string();
+        let colors = tui.resolved_colors();
+        assert_eq!(colors.selected, "red");
+        assert_eq!(colors.background, ColorConfig::default().background);
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let config = Config::builder()
+            .log_dir("logs")
+            .hparams_file("config.yaml")
+            .ignored_parameters(["seed", "devices"])
+            .float_tolerance(1e-4)
+            .int_tolerance(2)
+            .string_case_sensitive(true)
+            .grouping_parameters(["model", "lr"])
+            .similarity_threshold(3)
+            .main_key(["dataset"])
+            .main_key_missing_policy(MainKeyMissingPolicy::Skip)
+            .build();
+
+        assert_eq!(config.general.log_dir, "logs");
+        assert_eq!(config.general.hparams_file, "config.yaml");
+        assert_eq!(config.ignored_parameters.parameters, vec!["seed", "devices"]);
+        assert_eq!(config.tolerance.float_tolerance, 1e-4);
+        assert_eq!(config.tolerance.int_tolerance, 2);
+        assert!(config.tolerance.string_case_sensitive);
+        assert_eq!(
+            config.grouping.grouping_parameters,
+            Some(vec!["model".to_string(), "lr".to_string()])
+        );
+        assert_eq!(config.grouping.similarity_threshold, 3);
+        assert_eq!(config.grouping.main_key, Some(vec!["dataset".to_string()]));
+        assert_eq!(
+            config.grouping.main_key_missing_policy,
+            MainKeyMissingPolicy::Skip
+        );
+        assert!(config.grouping.validate().is_ok());
+
+        // 默认值：使用所有参数分组，配置有效
+        let config = Config::builder().group_by_all_parameters(true).build();
+        assert_eq!(config.general.log_dir, "lightning_logs");
+        assert_eq!(config.general.hparams_file, "hparams.yaml");
+        assert!(config.grouping.group_by_all_parameters);
+        assert_eq!(config.grouping.main_key, None);
+        assert!(config.grouping.validate().is_ok());
+    }
 }
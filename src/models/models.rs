@@ -1,6 +1,9 @@
-use crate::models::parameter_value::ParameterValue;
-use std::collections::HashMap;
+use crate::metrics::BestEpoch;
+use crate::models::config::Config;
+use crate::models::parameter_value::{BasicParameterValue, ParameterValue};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// 版本数据结构，包含实验版本的相关信息
 #[derive(Debug, Clone, PartialEq)]
@@ -11,6 +14,108 @@ pub struct VersionData {
     // 超参数集合，键为参数名，值为参数值
     // ————————————————————————————————————————————————————————————————————————
     pub hparams: HashMap<String, ParameterValue>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 实际读取的超参数文件名，目录中存在多个候选文件名时用于区分来源
+    // ————————————————————————————————————————————————————————————————————————
+    pub hparams_file: Option<String>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 超参数文件原始内容的SHA-256，启用compute_content_hash时计算，用于发现运行后被修改的文件
+    // ————————————————————————————————————————————————————————————————————————
+    pub content_hash: Option<String>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 解析得到的完整超参数，未过滤忽略参数、也未删除共同参数，用于在详情面板中查看被隐藏的参数
+    // ————————————————————————————————————————————————————————————————————————
+    pub full_hparams: HashMap<String, ParameterValue>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 展平后的参数名在超参数文件中出现的顺序，用于在详情面板中按文件顺序显示参数
+    // ————————————————————————————————————————————————————————————————————————
+    pub param_order: Vec<String>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 版本目录的修改时间，加载时读取，用于按最近修改排序版本列表；读取失败时为None
+    // ————————————————————————————————————————————————————————————————————————
+    pub mtime: Option<SystemTime>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 从指标文件（如metrics.csv）汇总得到的指标，每个指标按配置的方式汇总为单个值
+    // 没有指标文件时为空
+    // ————————————————————————————————————————————————————————————————————————
+    pub metrics: BTreeMap<String, f64>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 监控指标（由monitor_parameter指定）在指标文件中的最佳记录，与metrics一起读取
+    // 没有监控参数、没有指标文件或找不到对应列时为None
+    // ————————————————————————————————————————————————————————————————————————
+    pub best_epoch: Option<BestEpoch>,
+}
+
+impl VersionData {
+    /// 按参数路径获取参数值
+    ///
+    /// 嵌套参数在解析时以`-`连接展平，这里同时接受以`.`分隔的路径，如`trainer.lr`等价于`trainer-lr`
+    pub fn get(&self, path: &str) -> Option<&ParameterValue> {
+        self.hparams
+            .get(path)
+            .or_else(|| self.hparams.get(&path.replace('.', "-")))
+    }
+
+    /// 获取基本类型的参数值，列表等结构化值返回None
+    fn get_basic(&self, path: &str) -> Option<&BasicParameterValue> {
+        match self.get(path)? {
+            ParameterValue::Basic(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// 获取浮点数参数，整数参数会被提升为浮点数
+    pub fn get_f64(&self, path: &str) -> Option<f64> {
+        match self.get_basic(path)? {
+            BasicParameterValue::Float(f) => Some(*f),
+            BasicParameterValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// 获取整数参数
+    #[allow(dead_code)] // 库接口，与get_f64配套的类型化访问方法
+    pub fn get_i64(&self, path: &str) -> Option<i64> {
+        match self.get_basic(path)? {
+            BasicParameterValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// 获取布尔参数
+    #[allow(dead_code)] // 库接口，与get_f64配套的类型化访问方法
+    pub fn get_bool(&self, path: &str) -> Option<bool> {
+        match self.get_basic(path)? {
+            BasicParameterValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// 获取字符串参数
+    #[allow(dead_code)] // 库接口，与get_f64配套的类型化访问方法
+    pub fn get_str(&self, path: &str) -> Option<&str> {
+        match self.get_basic(path)? {
+            BasicParameterValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// 测试用的版本数据，路径为`version_N`，其余字段为空，配合结构体更新语法只写出关心的字段
+    #[cfg(test)]
+    pub fn for_test(version_num: u32) -> Self {
+        Self {
+            version_num,
+            path: PathBuf::from(format!("version_{}", version_num)),
+            hparams: HashMap::new(),
+            hparams_file: None,
+            content_hash: None,
+            full_hparams: HashMap::new(),
+            param_order: Vec::new(),
+            mtime: None,
+            metrics: BTreeMap::new(),
+            best_epoch: None,
+        }
+    }
 }
 
 /// 实验组结构，包含一组相关的实验版本
@@ -28,6 +133,185 @@ pub struct ExperimentGroup {
     pub member_versions: Vec<VersionData>,
 }
 
+impl ExperimentGroup {
+    /// 获取代表版本，即版本号最小（最早运行）的成员
+    ///
+    /// # Panics
+    /// 实验组没有任何成员时panic，正常分组流程创建的组至少包含一个成员
+    pub fn representative(&self) -> &VersionData {
+        self.member_versions
+            .iter()
+            .min_by_key(|v| v.version_num)
+            .expect("ExperimentGroup must have at least one member version")
+    }
+
+    /// 生成实验组的可读标签，如`model=cnn, lr=0.001`
+    ///
+    /// 按`label_parameters`的顺序格式化基础参数中存在的参数，
+    /// 未配置标签参数或这些参数都不存在时返回`group_id`
+    pub fn label(&self, config: &Config) -> String {
+        let parts: Vec<String> = config
+            .grouping
+            .label_parameters
+            .iter()
+            .flatten()
+            .filter_map(|key| {
+                self.base_parameters.get(key).map(|value| {
+                    format!(
+                        "{}={}",
+                        key,
+                        value.to_display_string(
+                            config.tui.float_display_precision,
+                            config.tui.trim_trailing_zeros
+                        )
+                    )
+                })
+            })
+            .collect();
+
+        if parts.is_empty() {
+            self.group_id.clone()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// 获取最新版本，即版本号最大的成员
+    ///
+    /// # Panics
+    /// 实验组没有任何成员时panic
+    pub fn latest(&self) -> &VersionData {
+        self.member_versions
+            .iter()
+            .max_by_key(|v| v.version_num)
+            .expect("ExperimentGroup must have at least one member version")
+    }
+
+    /// 获取成员之间取值不同的参数名（按名称排序）
+    ///
+    /// 以代表版本为基准按容差比较，只在部分成员中存在的参数也视为不同
+    pub fn varying_parameters(&self, config: &Config) -> Vec<String> {
+        if self.member_versions.is_empty() {
+            return Vec::new();
+        }
+        let reference = &self.representative().hparams;
+        let mut keys: Vec<String> = self
+            .member_versions
+            .iter()
+            .flat_map(|version| version.hparams.keys())
+            .filter(|key| {
+                self.member_versions.iter().any(|version| {
+                    match (version.hparams.get(*key), reference.get(*key)) {
+                        (Some(value), Some(reference_value)) => !value
+                            .equals_with_tolerance_for_key(reference_value, key, &config.tolerance),
+                        _ => true,
+                    }
+                })
+            })
+            .cloned()
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    /// 生成成员版本在变化参数上的取值后缀，如`(lr=0.01, seed=2)`，缺失的参数显示为missing
+    ///
+    /// `varying`通常来自[`Self::varying_parameters`]，为空时返回空字符串
+    pub fn member_suffix(version: &VersionData, varying: &[String], config: &Config) -> String {
+        if varying.is_empty() {
+            return String::new();
+        }
+        let parts: Vec<String> = varying
+            .iter()
+            .map(|key| {
+                let value = version.hparams.get(key).map_or_else(
+                    || "missing".to_string(),
+                    |value| {
+                        value.to_display_string(
+                            config.tui.float_display_precision,
+                            config.tui.trim_trailing_zeros,
+                        )
+                    },
+                );
+                format!("{}={}", key, value)
+            })
+            .collect();
+        format!("({})", parts.join(", "))
+    }
+}
+
+/// 两个实验组基础参数之间的差异，由`diff_groups`生成
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupDiff {
+    pub left_group_id: String,                 // 左侧实验组ID
+    pub left_label: String,                    // 左侧实验组的可读标签
+    pub right_group_id: String,                // 右侧实验组ID
+    pub right_label: String,                   // 右侧实验组的可读标签
+    pub differences: Vec<ParameterDifference>, // 取值不同的参数，按参数名排序
+}
+
+/// 单个参数在两个实验组中的取值
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterDifference {
+    pub key: String,                   // 参数名
+    pub left: Option<ParameterValue>,  // 左侧实验组的取值，None表示该组没有此参数
+    pub right: Option<ParameterValue>, // 右侧实验组的取值，None表示该组没有此参数
+}
+
+/// 将实验组的基础参数格式化为命令行参数，如`--model cnn --lr 0.001 --batch_size 32`
+///
+/// 参数按名称排序，展平键中的`-`替换为`key_separator`（如`.`或`_`）；
+/// 布尔值输出为`true`/`false`，列表输出为`[a,b]`，包含空白或shell特殊字符（含列表的方括号）的值用单引号包裹
+pub fn group_as_flags(group: &ExperimentGroup, flag_prefix: &str, key_separator: &str) -> String {
+    let mut keys: Vec<&String> = group.base_parameters.keys().collect();
+    keys.sort();
+    keys.iter()
+        .map(|key| {
+            format!(
+                "{}{} {}",
+                flag_prefix,
+                key.replace('-', key_separator),
+                shell_quote(&flag_value_string(&group.base_parameters[*key]))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 生成命令行参数值，浮点数使用最短的可还原表示而不是固定的6位小数
+fn flag_value_string(value: &ParameterValue) -> String {
+    match value {
+        ParameterValue::Basic(BasicParameterValue::Float(n)) => format!("{:?}", n),
+        ParameterValue::Basic(basic_value) => basic_value.to_string_repr(),
+        ParameterValue::List(list) => {
+            let items: Vec<String> = list.iter().map(flag_value_string).collect();
+            format!("[{}]", items.join(","))
+        }
+        ParameterValue::Map(map) => {
+            let mut items: Vec<String> = map
+                .iter()
+                .map(|(key, value)| format!("{}:{}", key, flag_value_string(value)))
+                .collect();
+            items.sort();
+            format!("{{{}}}", items.join(","))
+        }
+    }
+}
+
+/// 值为空或包含空白、shell特殊字符时用单引号包裹，内部的单引号转义为`'\''`
+fn shell_quote(value: &str) -> String {
+    let is_safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.,:/=+@%".contains(c));
+    if is_safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::models::BasicParameterValue;
@@ -43,9 +327,9 @@ mod tests {
         );
 
         let version = VersionData {
-            version_num: 1,
             path: PathBuf::from("logs/version_1"),
             hparams,
+            ..VersionData::for_test(1)
         };
 
         assert_eq!(version.version_num, 1);
@@ -68,9 +352,9 @@ mod tests {
         );
 
         let version = VersionData {
-            version_num: 1,
             path: PathBuf::from("logs/version_1"),
             hparams,
+            ..VersionData::for_test(1)
         };
 
         let group = ExperimentGroup {
@@ -83,4 +367,218 @@ mod tests {
         assert!(group.base_parameters.contains_key("model_type"));
         assert_eq!(group.member_versions.len(), 1);
     }
+
+    #[test]
+    fn test_representative_and_latest() {
+        let make_version = |version_num: u32| VersionData {
+            path: PathBuf::from(format!("logs/version_{}", version_num)),
+            ..VersionData::for_test(version_num)
+        };
+
+        // 成员顺序与版本号无关
+        let group = ExperimentGroup {
+            group_id: "group_1".to_string(),
+            base_parameters: HashMap::new(),
+            member_versions: vec![
+                make_version(7),
+                make_version(2),
+                make_version(12),
+                make_version(5),
+            ],
+        };
+
+        assert_eq!(group.representative().version_num, 2);
+        assert_eq!(group.latest().version_num, 12);
+    }
+
+    #[test]
+    fn test_version_data_typed_accessors() {
+        let hparams = HashMap::from([
+            (
+                "trainer-lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(0.001)),
+            ),
+            (
+                "batch_size".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(32)),
+            ),
+            (
+                "shuffle".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Bool(true)),
+            ),
+            (
+                "model".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+            ),
+            (
+                "layers".to_string(),
+                ParameterValue::List(vec![ParameterValue::Basic(BasicParameterValue::Int(64))]),
+            ),
+        ]);
+        let version = VersionData {
+            path: PathBuf::from("logs/version_1"),
+            hparams,
+            ..VersionData::for_test(1)
+        };
+
+        // 点分路径与展平后的键名等价
+        assert_eq!(version.get_f64("trainer.lr"), Some(0.001));
+        assert_eq!(version.get_f64("trainer-lr"), Some(0.001));
+        // 整数提升为浮点数
+        assert_eq!(version.get_f64("batch_size"), Some(32.0));
+        assert_eq!(version.get_i64("batch_size"), Some(32));
+        assert_eq!(version.get_bool("shuffle"), Some(true));
+        assert_eq!(version.get_str("model"), Some("cnn"));
+
+        // 类型不匹配或不存在时返回None
+        assert_eq!(version.get_i64("trainer.lr"), None);
+        assert_eq!(version.get_bool("model"), None);
+        assert_eq!(version.get_str("batch_size"), None);
+        assert_eq!(version.get_f64("layers"), None);
+        assert_eq!(version.get_f64("missing"), None);
+        assert!(version.get("layers").is_some());
+    }
+
+    #[test]
+    fn test_group_label() {
+        let mut base_parameters = HashMap::new();
+        base_parameters.insert(
+            "model".to_string(),
+            ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+        );
+        base_parameters.insert(
+            "lr".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.001)),
+        );
+        let group = ExperimentGroup {
+            group_id: "a1b2c3".to_string(),
+            base_parameters,
+            member_versions: Vec::new(),
+        };
+
+        // 未配置标签参数时使用组ID
+        let mut config = Config::default();
+        config.tui.trim_trailing_zeros = true;
+        assert_eq!(group.label(&config), "a1b2c3");
+
+        // 按配置顺序格式化，缺失的参数被跳过
+        config.grouping.label_parameters = Some(vec![
+            "model".to_string(),
+            "dropout".to_string(),
+            "lr".to_string(),
+        ]);
+        assert_eq!(group.label(&config), "model=cnn, lr=0.001");
+
+        // 标签参数都缺失时回退到组ID
+        config.grouping.label_parameters = Some(vec!["dropout".to_string()]);
+        assert_eq!(group.label(&config), "a1b2c3");
+    }
+
+    #[test]
+    fn test_group_as_flags() {
+        let base_parameters = HashMap::from([
+            (
+                "model".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+            ),
+            (
+                "optimizer-lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(0.001)),
+            ),
+            (
+                "batch_size".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(32)),
+            ),
+            (
+                "shuffle".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Bool(false)),
+            ),
+            (
+                "layers".to_string(),
+                ParameterValue::List(vec![
+                    ParameterValue::Basic(BasicParameterValue::Int(64)),
+                    ParameterValue::Basic(BasicParameterValue::Int(128)),
+                ]),
+            ),
+            (
+                "name".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String("it's a run".to_string())),
+            ),
+        ]);
+        let group = ExperimentGroup {
+            group_id: "group_1".to_string(),
+            base_parameters,
+            member_versions: Vec::new(),
+        };
+
+        assert_eq!(
+            group_as_flags(&group, "--", "."),
+            "--batch_size 32 --layers '[64,128]' --model cnn --name 'it'\\''s a run' \
+             --optimizer.lr 0.001 --shuffle false"
+        );
+        // 分隔符与前缀可配置
+        assert!(group_as_flags(&group, "-", "_").contains("-optimizer_lr 0.001"));
+
+        let empty = ExperimentGroup {
+            group_id: "group_2".to_string(),
+            base_parameters: HashMap::new(),
+            member_versions: Vec::new(),
+        };
+        assert_eq!(group_as_flags(&empty, "--", "."), "");
+    }
+
+    fn member(version_num: u32, params: &[(&str, ParameterValue)]) -> VersionData {
+        VersionData {
+            path: PathBuf::from(format!("logs/version_{}", version_num)),
+            hparams: params
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+            ..VersionData::for_test(version_num)
+        }
+    }
+
+    #[test]
+    fn test_member_suffix() {
+        let model = ParameterValue::Basic(BasicParameterValue::String("cnn".to_string()));
+        let lr = |n: f64| ParameterValue::Basic(BasicParameterValue::Float(n));
+        let seed = ParameterValue::Basic(BasicParameterValue::Int(2));
+        let group = ExperimentGroup {
+            group_id: "group_1".to_string(),
+            base_parameters: HashMap::from([("model".to_string(), model.clone())]),
+            member_versions: vec![
+                member(1, &[("model", model.clone()), ("lr", lr(0.001))]),
+                member(
+                    3,
+                    &[("model", model.clone()), ("lr", lr(0.01)), ("seed", seed)],
+                ),
+            ],
+        };
+        let mut config = Config::default();
+        config.tui.trim_trailing_zeros = true;
+
+        let varying = group.varying_parameters(&config);
+        assert_eq!(varying, vec!["lr", "seed"]);
+        assert_eq!(
+            ExperimentGroup::member_suffix(&group.member_versions[1], &varying, &config),
+            "(lr=0.01, seed=2)"
+        );
+        assert_eq!(
+            ExperimentGroup::member_suffix(&group.member_versions[0], &varying, &config),
+            "(lr=0.001, seed=missing)"
+        );
+
+        // 成员完全相同时没有后缀
+        let same = ExperimentGroup {
+            group_id: "group_2".to_string(),
+            base_parameters: HashMap::new(),
+            member_versions: vec![member(1, &[("lr", lr(0.1))]), member(2, &[("lr", lr(0.1))])],
+        };
+        let varying = same.varying_parameters(&config);
+        assert!(varying.is_empty());
+        assert_eq!(
+            ExperimentGroup::member_suffix(&same.member_versions[0], &varying, &config),
+            ""
+        );
+    }
 }
@@ -1,7 +1,7 @@
 // use std::fmt;
 use crate::models::config::ToleranceConfig;
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 /// 参数值类型枚举，支持递归结构
@@ -15,6 +15,10 @@ pub enum ParameterValue {
     // 参数值列表类型，支持嵌套的参数值数组
     // ————————————————————————————————————————————————————————————————————————
     List(Vec<ParameterValue>),
+    // ————————————————————————————————————————————————————————————————————————
+    // 结构化映射类型，用于不展平的列表元素（如无序的映射列表），按键名排序
+    // ————————————————————————————————————————————————————————————————————————
+    Map(BTreeMap<String, ParameterValue>),
 }
 
 /// 基本参数值类型，用于List中，只包含基本类型
@@ -41,6 +45,30 @@ impl BasicParameterValue {
             BasicParameterValue::Bool(b) => b.to_string(),
         }
     }
+
+    /// 按指定精度生成显示用字符串
+    ///
+    /// 仅影响浮点数的显示，其他类型与`to_string_repr`一致
+    ///
+    /// # 参数
+    /// * `precision` - 浮点数保留的小数位数
+    /// * `trim_trailing_zeros` - 是否去除小数部分末尾多余的0（如 0.100000 → 0.1）
+    pub fn to_display_string(&self, precision: usize, trim_trailing_zeros: bool) -> String {
+        match self {
+            BasicParameterValue::Float(n) => {
+                let formatted = format!("{:.*}", precision, n);
+                if trim_trailing_zeros && formatted.contains('.') {
+                    formatted
+                        .trim_end_matches('0')
+                        .trim_end_matches('.')
+                        .to_string()
+                } else {
+                    formatted
+                }
+            }
+            _ => self.to_string_repr(),
+        }
+    }
 }
 
 /// 为BasicParameterValue实现Display trait，支持format!("{}", value)语法
@@ -51,6 +79,18 @@ impl fmt::Display for BasicParameterValue {
 }
 
 impl ParameterValue {
+    /// 值类型的名称，用于诊断输出
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ParameterValue::Basic(BasicParameterValue::String(_)) => "string",
+            ParameterValue::Basic(BasicParameterValue::Int(_)) => "int",
+            ParameterValue::Basic(BasicParameterValue::Float(_)) => "float",
+            ParameterValue::Basic(BasicParameterValue::Bool(_)) => "bool",
+            ParameterValue::List(_) => "list",
+            ParameterValue::Map(_) => "map",
+        }
+    }
+
     pub fn to_simple_string(&self) -> String {
         match self {
             ParameterValue::Basic(basic_value) => basic_value.to_string_repr(),
@@ -58,6 +98,42 @@ impl ParameterValue {
                 let items: Vec<String> = list.iter().map(|item| item.to_simple_string()).collect();
                 format!("[{}]", items.join(", "))
             }
+            ParameterValue::Map(map) => {
+                let items: Vec<String> = map
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value.to_simple_string()))
+                    .collect();
+                format!("{{{}}}", items.join(", "))
+            }
+        }
+    }
+
+    /// 按指定的浮点数精度生成显示用字符串，列表会递归处理每个元素
+    pub fn to_display_string(&self, precision: usize, trim_trailing_zeros: bool) -> String {
+        match self {
+            ParameterValue::Basic(basic_value) => {
+                basic_value.to_display_string(precision, trim_trailing_zeros)
+            }
+            ParameterValue::List(list) => {
+                let items: Vec<String> = list
+                    .iter()
+                    .map(|item| item.to_display_string(precision, trim_trailing_zeros))
+                    .collect();
+                format!("[{}]", items.join(", "))
+            }
+            ParameterValue::Map(map) => {
+                let items: Vec<String> = map
+                    .iter()
+                    .map(|(key, value)| {
+                        format!(
+                            "{}: {}",
+                            key,
+                            value.to_display_string(precision, trim_trailing_zeros)
+                        )
+                    })
+                    .collect();
+                format!("{{{}}}", items.join(", "))
+            }
         }
     }
 }
@@ -77,6 +153,13 @@ impl fmt::Display for ParameterValue {
                 let items: Vec<String> = list.iter().map(|item| item.to_string()).collect();
                 write!(f, "[{}]", items.join(", "))
             }
+            ParameterValue::Map(map) => {
+                let items: Vec<String> = map
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect();
+                write!(f, "{{{}}}", items.join(", "))
+            }
         }
     }
 }
@@ -101,6 +184,11 @@ impl From<&ParameterValue> for JsonValue {
             ParameterValue::List(list) => {
                 JsonValue::Array(list.iter().map(|item| item.into()).collect())
             }
+            ParameterValue::Map(map) => JsonValue::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), value.into()))
+                    .collect(),
+            ),
         }
     }
 }
@@ -141,9 +229,39 @@ pub fn print_hparams_pretty(
 impl BasicParameterValue {
     /// 考虑容差的相等性比较
     pub fn equals_with_tolerance(&self, other: &Self, tolerance: &ToleranceConfig) -> bool {
+        self.equals_with_numeric_tolerance(
+            other,
+            tolerance.float_tolerance,
+            tolerance.int_tolerance,
+            tolerance.string_case_sensitive,
+        )
+    }
+
+    /// 考虑容差的相等性比较，使用指定参数的容差（支持按参数名覆盖）
+    pub fn equals_with_tolerance_for_key(
+        &self,
+        other: &Self,
+        key: &str,
+        tolerance: &ToleranceConfig,
+    ) -> bool {
+        self.equals_with_numeric_tolerance(
+            other,
+            tolerance.float_tolerance_for(key),
+            tolerance.int_tolerance_for(key),
+            tolerance.string_case_sensitive,
+        )
+    }
+
+    fn equals_with_numeric_tolerance(
+        &self,
+        other: &Self,
+        float_tolerance: f64,
+        int_tolerance: i64,
+        string_case_sensitive: bool,
+    ) -> bool {
         match (self, other) {
             (BasicParameterValue::String(a), BasicParameterValue::String(b)) => {
-                if tolerance.string_case_sensitive {
+                if string_case_sensitive {
                     a == b
                 } else {
                     a.to_lowercase() == b.to_lowercase()
@@ -154,10 +272,14 @@ impl BasicParameterValue {
                 if a.is_nan() || b.is_nan() {
                     return a.is_nan() && b.is_nan();
                 }
-                (a - b).abs() <= tolerance.float_tolerance
+                // 处理无穷大: inf - inf为NaN，只有同号的无穷大才相等
+                if a.is_infinite() || b.is_infinite() {
+                    return a == b;
+                }
+                (a - b).abs() <= float_tolerance
             }
             (BasicParameterValue::Int(a), BasicParameterValue::Int(b)) => {
-                (a - b).abs() <= tolerance.int_tolerance
+                (a - b).abs() <= int_tolerance
             }
             (BasicParameterValue::Bool(a), BasicParameterValue::Bool(b)) => a == b,
             _ => false,
@@ -183,6 +305,44 @@ impl ParameterValue {
                 }
                 true
             }
+            (ParameterValue::Map(a), ParameterValue::Map(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(key, value_a)| {
+                        b.get(key)
+                            .is_some_and(|value_b| value_a.equals_with_tolerance(value_b, tolerance))
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    /// 考虑容差的相等性比较，使用指定参数的容差（支持按参数名覆盖）
+    ///
+    /// 列表中的每个元素都使用该参数的容差进行比较
+    pub fn equals_with_tolerance_for_key(
+        &self,
+        other: &Self,
+        key: &str,
+        tolerance: &ToleranceConfig,
+    ) -> bool {
+        match (self, other) {
+            (ParameterValue::Basic(a), ParameterValue::Basic(b)) => {
+                a.equals_with_tolerance_for_key(b, key, tolerance)
+            }
+            (ParameterValue::List(a), ParameterValue::List(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b.iter()).all(|(item_a, item_b)| {
+                        item_a.equals_with_tolerance_for_key(item_b, key, tolerance)
+                    })
+            }
+            (ParameterValue::Map(a), ParameterValue::Map(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(map_key, value_a)| {
+                        b.get(map_key).is_some_and(|value_b| {
+                            value_a.equals_with_tolerance_for_key(value_b, key, tolerance)
+                        })
+                    })
+            }
             _ => false,
         }
     }
@@ -259,6 +419,112 @@ mod tests {
         assert_eq!(format!("{:?}", nested_list), "[[1, 2], nested]");
     }
 
+    #[test]
+    fn test_float_display_precision() {
+        let value = BasicParameterValue::Float(0.1);
+        assert_eq!(value.to_display_string(6, false), "0.100000");
+        assert_eq!(value.to_display_string(6, true), "0.1");
+
+        let value = BasicParameterValue::Float(0.123456789);
+        assert_eq!(value.to_display_string(3, false), "0.123");
+        assert_eq!(value.to_display_string(8, true), "0.12345679");
+
+        // 整数值的浮点数去除末尾0后不应留下小数点
+        let value = BasicParameterValue::Float(3.0);
+        assert_eq!(value.to_display_string(6, true), "3");
+
+        // 非浮点数不受影响
+        let value = BasicParameterValue::Int(100);
+        assert_eq!(value.to_display_string(2, true), "100");
+
+        let list = ParameterValue::List(vec![
+            ParameterValue::Basic(BasicParameterValue::Float(0.5)),
+            ParameterValue::Basic(BasicParameterValue::Float(0.25)),
+        ]);
+        assert_eq!(list.to_display_string(4, true), "[0.5, 0.25]");
+        assert_eq!(list.to_display_string(2, false), "[0.50, 0.25]");
+    }
+
+    #[test]
+    fn test_non_finite_floats() {
+        let inf = BasicParameterValue::Float(f64::INFINITY);
+        let neg_inf = BasicParameterValue::Float(f64::NEG_INFINITY);
+        let nan = BasicParameterValue::Float(f64::NAN);
+
+        assert_eq!(inf.to_string_repr(), "inf");
+        assert_eq!(neg_inf.to_string_repr(), "-inf");
+        assert_eq!(nan.to_string_repr(), "NaN");
+        // 去除末尾0不影响非有限值
+        assert_eq!(inf.to_display_string(2, true), "inf");
+        assert_eq!(nan.to_display_string(2, true), "NaN");
+
+        let tolerance = ToleranceConfig::default();
+        assert!(inf.equals_with_tolerance(&inf, &tolerance));
+        assert!(neg_inf.equals_with_tolerance(&neg_inf, &tolerance));
+        assert!(!inf.equals_with_tolerance(&neg_inf, &tolerance));
+        assert!(!inf.equals_with_tolerance(&BasicParameterValue::Float(f64::MAX), &tolerance));
+        assert!(nan.equals_with_tolerance(&nan, &tolerance));
+        assert!(!nan.equals_with_tolerance(&inf, &tolerance));
+    }
+
+    #[test]
+    fn test_equals_with_tolerance_for_key() {
+        let mut tolerance = ToleranceConfig {
+            float_tolerance: 0.001,
+            int_tolerance: 0,
+            string_case_sensitive: false,
+            per_parameter_tolerance: HashMap::new(),
+            coerce_boolean_strings: false,
+        };
+        tolerance
+            .per_parameter_tolerance
+            .insert("lr".to_string(), 1e-6);
+        tolerance
+            .per_parameter_tolerance
+            .insert("dropout".to_string(), 0.01);
+        tolerance
+            .per_parameter_tolerance
+            .insert("epochs".to_string(), 5.0);
+
+        let float = |f: f64| ParameterValue::Basic(BasicParameterValue::Float(f));
+        let int = |i: i64| ParameterValue::Basic(BasicParameterValue::Int(i));
+
+        // lr使用更严格的容差：全局容差下相等，按参数容差不相等
+        assert!(float(0.0010).equals_with_tolerance(&float(0.0015), &tolerance));
+        assert!(!float(0.0010).equals_with_tolerance_for_key(&float(0.0015), "lr", &tolerance));
+
+        // dropout使用更宽松的容差
+        assert!(!float(0.10).equals_with_tolerance(&float(0.105), &tolerance));
+        assert!(float(0.10).equals_with_tolerance_for_key(&float(0.105), "dropout", &tolerance));
+
+        // 整数参数使用容差的整数部分
+        assert!(int(100).equals_with_tolerance_for_key(&int(104), "epochs", &tolerance));
+        assert!(!int(100).equals_with_tolerance_for_key(&int(106), "epochs", &tolerance));
+
+        // 未单独配置的参数回退到全局容差
+        assert!(float(0.10).equals_with_tolerance_for_key(&float(0.1005), "other", &tolerance));
+        assert!(!int(1).equals_with_tolerance_for_key(&int(2), "other", &tolerance));
+
+        // 列表元素同样使用参数容差
+        let list_a = ParameterValue::List(vec![float(0.1), float(0.2)]);
+        let list_b = ParameterValue::List(vec![float(0.105), float(0.2)]);
+        assert!(list_a.equals_with_tolerance_for_key(&list_b, "dropout", &tolerance));
+        assert!(!list_a.equals_with_tolerance_for_key(&list_b, "lr", &tolerance));
+    }
+
+    #[test]
+    fn test_empty_list_equality() {
+        let tolerance = ToleranceConfig::default();
+        let empty = ParameterValue::List(Vec::new());
+        let single = ParameterValue::List(vec![ParameterValue::Basic(BasicParameterValue::Int(0))]);
+
+        assert!(empty.equals_with_tolerance(&ParameterValue::List(Vec::new()), &tolerance));
+        assert!(empty.equals_with_tolerance_for_key(&empty, "devices", &tolerance));
+        assert!(!empty.equals_with_tolerance(&single, &tolerance));
+        assert!(!empty.equals_with_tolerance(&ParameterValue::Map(BTreeMap::new()), &tolerance));
+        assert_eq!(empty.to_string(), "[]");
+    }
+
     #[test]
     fn test_debug_equals_display() {
         // 测试 Debug 和 Display 的输出是否相同
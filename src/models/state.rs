@@ -1,7 +1,8 @@
 use crate::models::config::Config;
 use crate::models::models::{ExperimentGroup, VersionData};
 use crate::models::parameter_value::ParameterValue;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::PathBuf;
 
 /// 应用程序状态结构，包含所有实验数据和配置
 #[derive(Debug)]
@@ -23,6 +24,68 @@ pub struct AppState {
     // 键为分组键（由main_key值组合而成），值为该分组内所有版本共有的参数
     // ————————————————————————————————————————————————————————————————————————
     pub group_common_hparams: HashMap<String, HashMap<String, ParameterValue>>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 加载过程的诊断信息，用于信息面板
+    // ————————————————————————————————————————————————————————————————————————
+    pub load_report: LoadReport,
+}
+
+/// 加载流程的诊断信息，由create_version_data_list生成
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadReport {
+    pub config_path: Option<String>, // 加载的配置文件路径，None表示未从文件加载
+    pub files_found: usize,          // 找到的超参数文件数量
+    pub files_parsed: usize,         // 成功解析的超参数文件数量
+    pub failed_files: Vec<PathBuf>,  // 解析失败而被跳过的超参数文件
+    pub oversized_files: Vec<PathBuf>, // 超过max_hparams_file_bytes而被跳过的文件，同时计入failed_files
+    pub skipped_versions: Vec<u32>,    // 因缺少main_key按skip策略丢弃的版本号
+    pub duplicate_versions: Vec<u32>,  // 在多个目录中重复出现的版本号
+    pub versions_loaded: usize,        // 最终保留的版本数量
+    pub warnings: Vec<String>,         // 加载过程中的警告，由调用方决定输出方式，避免干扰TUI
+    // ————————————————————————————————————————————————————————————————————————
+    // 无法计算的派生参数，值为引用参数缺失或不是数值的版本数量
+    // ————————————————————————————————————————————————————————————————————————
+    pub derived_unavailable: BTreeMap<String, usize>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 与超参数文件中已有参数同名的派生参数，这些版本保留文件中的值
+    // ————————————————————————————————————————————————————————————————————————
+    pub derived_collisions: BTreeSet<String>,
+}
+
+impl LoadReport {
+    /// 单行概要，如`Loaded 3 versions from 5 hparams files (1 failed to parse)`
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Loaded {} versions from {} hparams files",
+            self.versions_loaded, self.files_found
+        );
+        let mut details = Vec::new();
+        if !self.failed_files.is_empty() {
+            details.push(format!("{} failed to parse", self.failed_files.len()));
+        }
+        if !self.oversized_files.is_empty() {
+            details.push(format!(
+                "{} over max_hparams_file_bytes",
+                self.oversized_files.len()
+            ));
+        }
+        if !self.skipped_versions.is_empty() {
+            details.push(format!(
+                "{} skipped for missing main_key",
+                self.skipped_versions.len()
+            ));
+        }
+        if !self.duplicate_versions.is_empty() {
+            details.push(format!(
+                "{} duplicate version numbers",
+                self.duplicate_versions.len()
+            ));
+        }
+        if !details.is_empty() {
+            summary.push_str(&format!(" ({})", details.join(", ")));
+        }
+        summary
+    }
 }
 
 #[cfg(test)]
@@ -46,9 +109,9 @@ mod tests {
         );
 
         let version = VersionData {
-            version_num: 1,
             path: PathBuf::from("logs/version_1"),
             hparams,
+            ..VersionData::for_test(1)
         };
         versions.push(version);
 
@@ -82,6 +145,7 @@ mod tests {
             experiment_groups: groups,
             config,
             group_common_hparams,
+            load_report: LoadReport::default(),
         };
 
         assert_eq!(app_state.all_versions.len(), 1);
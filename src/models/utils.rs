@@ -1,4 +1,4 @@
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serializer};
 
 /// 反序列化可选字符串，将空字符串转换为None
 ///
@@ -17,16 +17,33 @@ where
     Ok(s.filter(|s| !s.is_empty()))
 }
 
+/// 序列化可选字符串，将None写为空字符串，与`deserialize_optional_string`对应
+///
+/// TOML无法表示空值，写为空字符串可以保留配置项，读回时仍为None
+pub fn serialize_optional_string<S>(
+    value: &Option<String>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(value.as_deref().unwrap_or_default())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
     use serde_yaml;
 
     // 测试结构体，用于测试deserialize_optional_string函数
-    #[derive(Debug, Deserialize)]
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
     struct TestStruct {
-        #[serde(default, deserialize_with = "deserialize_optional_string")]
+        #[serde(
+            default,
+            deserialize_with = "deserialize_optional_string",
+            serialize_with = "serialize_optional_string"
+        )]
         field: Option<String>,
     }
 
@@ -50,4 +67,15 @@ mod tests {
         let test: TestStruct = serde_yaml::from_str(yaml).unwrap();
         assert_eq!(test.field, None);
     }
+
+    #[test]
+    fn test_serialize_optional_string_round_trip() {
+        for field in [None, Some("fold".to_string())] {
+            let test = TestStruct { field };
+            let toml_text = toml::to_string(&test).unwrap();
+            assert_eq!(toml::from_str::<TestStruct>(&toml_text).unwrap(), test);
+        }
+        let empty = toml::to_string(&TestStruct { field: None }).unwrap();
+        assert_eq!(empty.trim(), "field = \"\"");
+    }
 }
@@ -0,0 +1,786 @@
+use crate::models::config::Config;
+use crate::models::models::{ExperimentGroup, GroupDiff, VersionData};
+use crate::models::parameter_value::ParameterValue;
+use crate::models::state::AppState;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{self, Write};
+
+/// AppState的可序列化投影，用于无界面模式下输出完整的JSON文档
+///
+/// 所有映射都使用BTreeMap，保证输出的键顺序稳定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppStateView {
+    // ————————————————————————————————————————————————————————————————————————
+    // 导出元数据，记录产生此结果所用的过滤、分组和容差配置
+    // ————————————————————————————————————————————————————————————————————————
+    pub metadata: ExportMetadata,
+    // ————————————————————————————————————————————————————————————————————————
+    // 所有版本（已删除共有参数和忽略参数）
+    // ————————————————————————————————————————————————————————————————————————
+    pub versions: Vec<VersionView>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 实验分组列表，成员只记录版本号
+    // ————————————————————————————————————————————————————————————————————————
+    pub experiment_groups: Vec<ExperimentGroupView>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 每个main_key分组内所有版本共有的参数
+    // ————————————————————————————————————————————————————————————————————————
+    pub group_common_hparams: BTreeMap<String, BTreeMap<String, JsonValue>>,
+    // ————————————————————————————————————————————————————————————————————————
+    // 相似组关系，键为组ID，值为与之相似的组ID列表
+    // ————————————————————————————————————————————————————————————————————————
+    pub similar_groups: BTreeMap<String, Vec<String>>,
+}
+
+/// 导出元数据，使导出结果可以自我描述
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportMetadata {
+    pub ignored_parameters: Vec<String>,          // 被忽略的参数
+    pub group_by_all_parameters: bool,            // 是否使用所有参数进行分组
+    pub grouping_parameters: Option<Vec<String>>, // 实际生效的分组参数，None表示使用所有参数
+    pub main_key: Option<Vec<String>>,            // 主键参数列表
+    pub tolerance: ToleranceView,                 // 容差设置
+}
+
+/// 容差配置的可序列化投影
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToleranceView {
+    pub float_tolerance: f64,
+    pub int_tolerance: i64,
+    pub string_case_sensitive: bool,
+    pub per_parameter_tolerance: BTreeMap<String, f64>,
+}
+
+impl From<&Config> for ExportMetadata {
+    fn from(config: &Config) -> Self {
+        let tolerance = &config.tolerance;
+        Self {
+            ignored_parameters: config.ignored_parameters.parameters.clone(),
+            group_by_all_parameters: config.grouping.group_by_all_parameters,
+            grouping_parameters: config
+                .grouping
+                .effective_grouping_parameters()
+                .map(|params| params.to_vec()),
+            main_key: config.grouping.main_key.clone(),
+            tolerance: ToleranceView {
+                float_tolerance: tolerance.float_tolerance,
+                int_tolerance: tolerance.int_tolerance,
+                string_case_sensitive: tolerance.string_case_sensitive,
+                per_parameter_tolerance: tolerance
+                    .per_parameter_tolerance
+                    .iter()
+                    .map(|(key, value)| (key.clone(), *value))
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// 单个版本的可序列化投影
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionView {
+    pub version_num: u32,                     // 版本号
+    pub path: String,                         // 版本目录路径
+    pub hparams: BTreeMap<String, JsonValue>, // 超参数
+}
+
+/// 实验组的可序列化投影
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentGroupView {
+    pub group_id: String, // 实验组ID
+    #[serde(default)]
+    pub label: String, // 实验组的可读标签
+    pub base_parameters: BTreeMap<String, JsonValue>, // 基础参数
+    pub member_versions: Vec<u32>, // 成员版本号列表
+}
+
+impl AppStateView {
+    /// 从AppState和相似组查找结果创建投影
+    pub fn new(state: &AppState, similar_groups: &HashMap<String, Vec<String>>) -> Self {
+        Self {
+            metadata: ExportMetadata::from(&state.config),
+            versions: state.all_versions.iter().map(VersionView::from).collect(),
+            experiment_groups: state
+                .experiment_groups
+                .iter()
+                .map(|group| ExperimentGroupView::new(group, &state.config))
+                .collect(),
+            group_common_hparams: state
+                .group_common_hparams
+                .iter()
+                .map(|(group_key, params)| (group_key.clone(), params_to_json(params)))
+                .collect(),
+            similar_groups: similar_groups
+                .iter()
+                .map(|(group_id, similar_ids)| (group_id.clone(), similar_ids.clone()))
+                .collect(),
+        }
+    }
+
+    /// 序列化为格式化的JSON字符串
+    pub fn to_json_string(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// 分组报告中的一行，对应一个实验组
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupReportLine {
+    pub group_id: String, // 实验组ID
+    #[serde(default)]
+    pub label: String, // 实验组的可读标签
+    pub size: usize,      // 成员版本数量
+    pub base_parameters: BTreeMap<String, JsonValue>, // 基础参数
+    pub member_versions: Vec<u32>, // 成员版本号列表
+}
+
+impl GroupReportLine {
+    /// 从实验组创建报告行，标签按配置的`label_parameters`生成
+    pub fn new(group: &ExperimentGroup, config: &Config) -> Self {
+        let group_view = ExperimentGroupView::new(group, config);
+        Self {
+            group_id: group_view.group_id,
+            label: group_view.label,
+            size: group_view.member_versions.len(),
+            base_parameters: group_view.base_parameters,
+            member_versions: group_view.member_versions,
+        }
+    }
+}
+
+/// 以JSONL格式写出分组报告，每个实验组占一行，便于jq等工具流式处理
+pub fn write_group_report_jsonl<W: Write>(
+    groups: &[ExperimentGroup],
+    config: &Config,
+    writer: &mut W,
+) -> io::Result<()> {
+    for group in groups {
+        serde_json::to_writer(&mut *writer, &GroupReportLine::new(group, config))?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// 实验组差异报告的可序列化投影
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupDiffView {
+    pub left_group_id: String,                     // 左侧实验组ID
+    pub left_label: String,                        // 左侧实验组的可读标签
+    pub right_group_id: String,                    // 右侧实验组ID
+    pub right_label: String,                       // 右侧实验组的可读标签
+    pub differences: Vec<ParameterDifferenceView>, // 取值不同的参数，按参数名排序
+}
+
+/// 单个参数的差异，缺失的一侧为null
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterDifferenceView {
+    pub key: String,
+    pub left: Option<JsonValue>,
+    pub right: Option<JsonValue>,
+}
+
+impl From<&GroupDiff> for GroupDiffView {
+    fn from(diff: &GroupDiff) -> Self {
+        Self {
+            left_group_id: diff.left_group_id.clone(),
+            left_label: diff.left_label.clone(),
+            right_group_id: diff.right_group_id.clone(),
+            right_label: diff.right_label.clone(),
+            differences: diff
+                .differences
+                .iter()
+                .map(|difference| ParameterDifferenceView {
+                    key: difference.key.clone(),
+                    left: difference.left.as_ref().map(JsonValue::from),
+                    right: difference.right.as_ref().map(JsonValue::from),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 以JSON格式写出两个实验组的差异报告
+pub fn write_group_diff_json<W: Write>(diff: &GroupDiff, writer: &mut W) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, &GroupDiffView::from(diff))?;
+    writeln!(writer)
+}
+
+/// 以Markdown表格写出两个实验组的差异报告，缺失的参数显示为*missing*
+pub fn write_group_diff_markdown<W: Write>(diff: &GroupDiff, writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "# Group diff")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "- A: `{}` ({})",
+        diff.left_group_id, diff.left_label
+    )?;
+    writeln!(
+        writer,
+        "- B: `{}` ({})",
+        diff.right_group_id, diff.right_label
+    )?;
+    writeln!(writer)?;
+    if diff.differences.is_empty() {
+        return writeln!(writer, "No differences in base parameters.");
+    }
+    writeln!(writer, "| Parameter | A | B |")?;
+    writeln!(writer, "| --- | --- | --- |")?;
+    let cell = |value: &Option<ParameterValue>| {
+        value.as_ref().map_or_else(
+            || "*missing*".to_string(),
+            |value| format!("`{}`", value.to_simple_string().replace('|', "\\|")),
+        )
+    };
+    for difference in &diff.differences {
+        writeln!(
+            writer,
+            "| {} | {} | {} |",
+            difference.key.replace('|', "\\|"),
+            cell(&difference.left),
+            cell(&difference.right)
+        )?;
+    }
+    Ok(())
+}
+
+/// 按给定顺序导出的版本子集，`columns`记录参数列的顺序
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionsExportView {
+    pub metadata: ExportMetadata,   // 导出元数据
+    pub columns: Vec<String>,       // 参数列顺序
+    pub versions: Vec<VersionView>, // 按导出顺序排列的版本
+}
+
+/// 以CSV格式导出版本，行顺序与`versions`一致，参数列顺序与`columns`一致
+///
+/// 第一行是以`#`开头的注释，内容为JSON格式的导出元数据；
+/// 表头前两列固定为`version`和`path`，版本缺少的参数留空
+pub fn write_versions_csv<W: Write>(
+    versions: &[&VersionData],
+    columns: &[String],
+    metadata: &ExportMetadata,
+    writer: &mut W,
+) -> io::Result<()> {
+    writeln!(writer, "# {}", serde_json::to_string(metadata)?)?;
+    let header: Vec<String> = ["version", "path"]
+        .into_iter()
+        .map(str::to_string)
+        .chain(columns.iter().cloned())
+        .collect();
+    write_csv_record(writer, &header)?;
+
+    for version in versions {
+        let mut record = vec![
+            version.version_num.to_string(),
+            version.path.display().to_string(),
+        ];
+        record.extend(columns.iter().map(|column| {
+            version
+                .hparams
+                .get(column)
+                .map(csv_cell)
+                .unwrap_or_default()
+        }));
+        write_csv_record(writer, &record)?;
+    }
+    Ok(())
+}
+
+/// 以JSON格式导出版本和导出元数据，版本顺序与`versions`一致
+pub fn write_versions_json<W: Write>(
+    versions: &[&VersionData],
+    columns: &[String],
+    metadata: &ExportMetadata,
+    writer: &mut W,
+) -> io::Result<()> {
+    let view = VersionsExportView {
+        metadata: metadata.clone(),
+        columns: columns.to_vec(),
+        versions: versions
+            .iter()
+            .map(|version| VersionView::from(*version))
+            .collect(),
+    };
+    serde_json::to_writer_pretty(&mut *writer, &view)?;
+    writeln!(writer)
+}
+
+/// 将参数值转换为CSV单元格文本，字符串不加引号，其余值使用JSON表示
+fn csv_cell(value: &ParameterValue) -> String {
+    match JsonValue::from(value) {
+        JsonValue::String(text) => text,
+        other => other.to_string(),
+    }
+}
+
+/// 写出一行CSV记录，包含逗号、引号或换行的字段加引号转义
+fn write_csv_record<W: Write>(writer: &mut W, fields: &[String]) -> io::Result<()> {
+    let escaped: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+    writeln!(writer, "{}", escaped.join(","))
+}
+
+/// 以对齐的纯文本表格输出版本，每个版本一行，首列为版本号
+///
+/// 参数值按配置的浮点数显示精度格式化，版本缺少的参数显示为`-`
+pub fn write_versions_table<W: Write>(
+    versions: &[&VersionData],
+    columns: &[String],
+    config: &Config,
+    writer: &mut W,
+) -> io::Result<()> {
+    let header: Vec<String> = std::iter::once("version".to_string())
+        .chain(columns.iter().cloned())
+        .collect();
+    let rows: Vec<Vec<String>> = versions
+        .iter()
+        .map(|version| {
+            std::iter::once(version.version_num.to_string())
+                .chain(columns.iter().map(|column| {
+                    version.hparams.get(column).map_or_else(
+                        || "-".to_string(),
+                        |value| {
+                            value.to_display_string(
+                                config.tui.float_display_precision,
+                                config.tui.trim_trailing_zeros,
+                            )
+                        },
+                    )
+                }))
+                .collect()
+        })
+        .collect();
+
+    let widths = table_column_widths(&header, &rows);
+    writeln!(writer, "{}", format_table_row(&header, &widths))?;
+    for row in &rows {
+        writeln!(writer, "{}", format_table_row(row, &widths))?;
+    }
+    Ok(())
+}
+
+/// 以对齐的纯文本表格输出两个参数的覆盖矩阵
+///
+/// 每列对应`param_x`的一个取值，每行对应`param_y`的一个取值，取值按字符串排序；
+/// 单元格为该组合的版本数量，没有版本的组合显示为`0`
+pub fn write_coverage_matrix<W: Write>(
+    matrix: &HashMap<(String, String), usize>,
+    param_x: &str,
+    param_y: &str,
+    writer: &mut W,
+) -> io::Result<()> {
+    let x_values: BTreeSet<&String> = matrix.keys().map(|(x, _)| x).collect();
+    let y_values: BTreeSet<&String> = matrix.keys().map(|(_, y)| y).collect();
+
+    let header: Vec<String> = std::iter::once(format!("{} \\ {}", param_y, param_x))
+        .chain(x_values.iter().map(|x| x.to_string()))
+        .collect();
+    let rows: Vec<Vec<String>> = y_values
+        .iter()
+        .map(|y| {
+            std::iter::once(y.to_string())
+                .chain(x_values.iter().map(|x| {
+                    let key = (x.to_string(), y.to_string());
+                    matrix.get(&key).copied().unwrap_or(0).to_string()
+                }))
+                .collect()
+        })
+        .collect();
+
+    let widths = table_column_widths(&header, &rows);
+    writeln!(writer, "{}", format_table_row(&header, &widths))?;
+    for row in &rows {
+        writeln!(writer, "{}", format_table_row(row, &widths))?;
+    }
+    Ok(())
+}
+
+/// 计算表格各列的宽度，取表头和所有单元格中最长的字符数
+pub fn table_column_widths(header: &[String], rows: &[Vec<String>]) -> Vec<usize> {
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    widths
+}
+
+/// 按列宽左对齐格式化一行，列之间用两个空格分隔，并去掉行尾空白
+pub fn format_table_row(cells: &[String], widths: &[usize]) -> String {
+    let padded: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+        .collect();
+    padded.join("  ").trim_end().to_string()
+}
+
+impl From<&VersionData> for VersionView {
+    fn from(version: &VersionData) -> Self {
+        Self {
+            version_num: version.version_num,
+            path: version.path.display().to_string(),
+            hparams: params_to_json(&version.hparams),
+        }
+    }
+}
+
+impl ExperimentGroupView {
+    /// 从实验组创建投影，标签按配置的`label_parameters`生成
+    pub fn new(group: &ExperimentGroup, config: &Config) -> Self {
+        Self {
+            group_id: group.group_id.clone(),
+            label: group.label(config),
+            base_parameters: params_to_json(&group.base_parameters),
+            member_versions: group
+                .member_versions
+                .iter()
+                .map(|v| v.version_num)
+                .collect(),
+        }
+    }
+}
+
+/// 将参数映射转换为键有序的JSON值映射
+fn params_to_json(params: &HashMap<String, ParameterValue>) -> BTreeMap<String, JsonValue> {
+    params
+        .iter()
+        .map(|(key, value)| (key.clone(), value.into()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::config::Config;
+    use crate::models::parameter_value::BasicParameterValue;
+    use std::path::PathBuf;
+
+    fn create_test_app_state() -> AppState {
+        let mut hparams = HashMap::new();
+        hparams.insert(
+            "learning_rate".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.01)),
+        );
+        hparams.insert(
+            "layers".to_string(),
+            ParameterValue::List(vec![
+                ParameterValue::Basic(BasicParameterValue::Int(64)),
+                ParameterValue::Basic(BasicParameterValue::Int(32)),
+            ]),
+        );
+
+        let version = VersionData {
+            path: PathBuf::from("logs/version_3"),
+            hparams: hparams.clone(),
+            ..VersionData::for_test(3)
+        };
+
+        let group = ExperimentGroup {
+            group_id: "abc123".to_string(),
+            base_parameters: hparams,
+            member_versions: vec![version.clone()],
+        };
+
+        let mut common_params = HashMap::new();
+        common_params.insert(
+            "optimizer".to_string(),
+            ParameterValue::Basic(BasicParameterValue::String("adam".to_string())),
+        );
+        let mut group_common_hparams = HashMap::new();
+        group_common_hparams.insert("model=cnn".to_string(), common_params);
+
+        AppState {
+            all_versions: vec![version],
+            experiment_groups: vec![group],
+            config: Config::default(),
+            group_common_hparams,
+            load_report: crate::models::LoadReport::default(),
+        }
+    }
+
+    #[test]
+    fn test_json_dump_top_level_keys() {
+        let state = create_test_app_state();
+        let mut similar_groups = HashMap::new();
+        similar_groups.insert("abc123".to_string(), Vec::new());
+
+        let json = AppStateView::new(&state, &similar_groups)
+            .to_json_string()
+            .expect("Failed to serialize view");
+        let value: JsonValue = serde_json::from_str(&json).expect("Dump should be valid JSON");
+
+        let object = value.as_object().expect("Dump should be a JSON object");
+        for key in [
+            "metadata",
+            "versions",
+            "experiment_groups",
+            "group_common_hparams",
+            "similar_groups",
+        ] {
+            assert!(object.contains_key(key), "Missing top-level key '{}'", key);
+        }
+
+        assert_eq!(value["versions"][0]["version_num"], 3);
+        assert_eq!(value["versions"][0]["hparams"]["learning_rate"], 0.01);
+        assert_eq!(
+            value["versions"][0]["hparams"]["layers"],
+            serde_json::json!([64, 32])
+        );
+        assert_eq!(
+            value["experiment_groups"][0]["member_versions"],
+            serde_json::json!([3])
+        );
+        assert_eq!(
+            value["group_common_hparams"]["model=cnn"]["optimizer"],
+            "adam"
+        );
+    }
+
+    #[test]
+    fn test_json_dump_metadata() {
+        let mut state = create_test_app_state();
+        state.config.ignored_parameters.parameters = vec!["seed".to_string(), "fold".to_string()];
+        state.config.grouping.grouping_parameters = Some(vec!["model".to_string()]);
+        state.config.grouping.main_key = Some(vec!["dataset".to_string()]);
+        state.config.tolerance.float_tolerance = 0.001;
+        state.config.tolerance.int_tolerance = 2;
+        state
+            .config
+            .tolerance
+            .per_parameter_tolerance
+            .insert("lr".to_string(), 0.1);
+
+        let json = AppStateView::new(&state, &HashMap::new())
+            .to_json_string()
+            .expect("Failed to serialize view");
+        let value: JsonValue = serde_json::from_str(&json).expect("Dump should be valid JSON");
+        let metadata = &value["metadata"];
+
+        assert_eq!(
+            metadata["ignored_parameters"],
+            serde_json::json!(["seed", "fold"])
+        );
+        assert_eq!(
+            metadata["grouping_parameters"],
+            serde_json::json!(["model"])
+        );
+        assert_eq!(metadata["main_key"], serde_json::json!(["dataset"]));
+        assert_eq!(metadata["tolerance"]["float_tolerance"], 0.001);
+        assert_eq!(metadata["tolerance"]["int_tolerance"], 2);
+        assert_eq!(metadata["tolerance"]["per_parameter_tolerance"]["lr"], 0.1);
+
+        // 使用所有参数分组时，生效的分组参数为null
+        state.config.grouping.grouping_parameters = None;
+        state.config.grouping.group_by_all_parameters = true;
+        let metadata = ExportMetadata::from(&state.config);
+        assert_eq!(metadata.grouping_parameters, None);
+        assert!(metadata.group_by_all_parameters);
+    }
+
+    #[test]
+    fn test_json_dump_round_trip() {
+        let state = create_test_app_state();
+        let mut similar_groups = HashMap::new();
+        similar_groups.insert("abc123".to_string(), vec!["def456".to_string()]);
+
+        let view = AppStateView::new(&state, &similar_groups);
+        let json = view.to_json_string().expect("Failed to serialize view");
+        let parsed: AppStateView = serde_json::from_str(&json).expect("Failed to parse dump");
+
+        assert_eq!(parsed, view);
+    }
+
+    #[test]
+    fn test_group_report_jsonl() {
+        let mut state = create_test_app_state();
+        let first = &state.experiment_groups[0];
+        let group = ExperimentGroup {
+            group_id: "def456".to_string(),
+            base_parameters: first.base_parameters.clone(),
+            member_versions: vec![
+                first.member_versions[0].clone(),
+                VersionData {
+                    path: PathBuf::from("logs/version_7"),
+                    ..VersionData::for_test(7)
+                },
+            ],
+        };
+        state.experiment_groups.push(group);
+
+        let mut output = Vec::new();
+        state.config.grouping.label_parameters = Some(vec!["learning_rate".to_string()]);
+        write_group_report_jsonl(&state.experiment_groups, &state.config, &mut output)
+            .expect("Failed to write report");
+        let output = String::from_utf8(output).expect("Report should be UTF-8");
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), state.experiment_groups.len());
+        for line in &lines {
+            let value: JsonValue = serde_json::from_str(line).expect("Line should be valid JSON");
+            assert!(value.is_object());
+        }
+
+        let second: GroupReportLine = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second.group_id, "def456");
+        assert_eq!(second.label, "learning_rate=0.01");
+        assert_eq!(second.size, 2);
+        assert_eq!(second.member_versions, vec![3, 7]);
+        assert_eq!(second.base_parameters["learning_rate"], 0.01);
+    }
+
+    fn export_version(version_num: u32, lr: f64, name: &str) -> VersionData {
+        let mut hparams = HashMap::new();
+        hparams.insert(
+            "lr".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(lr)),
+        );
+        hparams.insert(
+            "name".to_string(),
+            ParameterValue::Basic(BasicParameterValue::String(name.to_string())),
+        );
+        VersionData {
+            path: PathBuf::from(format!("logs/version_{}", version_num)),
+            hparams,
+            ..VersionData::for_test(version_num)
+        }
+    }
+
+    #[test]
+    fn test_group_diff_export() {
+        let diff = GroupDiff {
+            left_group_id: "a1".to_string(),
+            left_label: "model=cnn".to_string(),
+            right_group_id: "b2".to_string(),
+            right_label: "model=rnn".to_string(),
+            differences: vec![
+                crate::models::ParameterDifference {
+                    key: "lr".to_string(),
+                    left: Some(ParameterValue::Basic(BasicParameterValue::Float(0.01))),
+                    right: Some(ParameterValue::Basic(BasicParameterValue::Float(0.1))),
+                },
+                crate::models::ParameterDifference {
+                    key: "note".to_string(),
+                    left: None,
+                    right: Some(ParameterValue::Basic(BasicParameterValue::String(
+                        "a|b".to_string(),
+                    ))),
+                },
+            ],
+        };
+
+        let mut output = Vec::new();
+        write_group_diff_markdown(&diff, &mut output).expect("Failed to write markdown");
+        let markdown = String::from_utf8(output).unwrap();
+        assert!(markdown.contains("- A: `a1` (model=cnn)"));
+        assert!(markdown.contains("| lr | `0.010000` | `0.100000` |"));
+        assert!(markdown.contains("| note | *missing* | `a\\|b` |"));
+
+        let mut output = Vec::new();
+        write_group_diff_json(&diff, &mut output).expect("Failed to write JSON");
+        let value: JsonValue = serde_json::from_slice(&output).unwrap();
+        assert_eq!(value["right_label"], "model=rnn");
+        assert_eq!(value["differences"][0]["right"], 0.1);
+        assert_eq!(value["differences"][1]["left"], JsonValue::Null);
+    }
+
+    #[test]
+    fn test_export_respects_order_and_subset() {
+        let all = [
+            export_version(1, 0.1, "a"),
+            export_version(2, 0.2, "b,c"),
+            export_version(3, 0.3, "d"),
+        ];
+        // 只导出版本3和版本2，且版本3在前
+        let subset = vec![&all[2], &all[1]];
+        let columns = vec!["name".to_string(), "lr".to_string()];
+        let mut state = create_test_app_state();
+        state.config.ignored_parameters.parameters = vec!["seed".to_string()];
+        let metadata = ExportMetadata::from(&state.config);
+
+        let mut csv = Vec::new();
+        write_versions_csv(&subset, &columns, &metadata, &mut csv).expect("Failed to write CSV");
+        let csv = String::from_utf8(csv).unwrap();
+        let (comment, table) = csv.split_once('\n').unwrap();
+        let header: ExportMetadata =
+            serde_json::from_str(comment.strip_prefix("# ").unwrap()).unwrap();
+        assert_eq!(header, metadata);
+        assert_eq!(
+            table,
+            "version,path,name,lr\n\
+             3,logs/version_3,d,0.3\n\
+             2,logs/version_2,\"b,c\",0.2\n"
+        );
+
+        let mut json = Vec::new();
+        write_versions_json(&subset, &columns, &metadata, &mut json)
+            .expect("Failed to write JSON");
+        let parsed: VersionsExportView = serde_json::from_slice(&json).unwrap();
+        assert_eq!(parsed.metadata.ignored_parameters, vec!["seed".to_string()]);
+        assert_eq!(parsed.columns, columns);
+        let order: Vec<u32> = parsed.versions.iter().map(|v| v.version_num).collect();
+        assert_eq!(order, vec![3, 2]);
+    }
+
+    #[test]
+    fn test_versions_table() {
+        let header = vec!["version".to_string(), "name".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "resnet152".to_string()],
+            vec!["10".to_string(), "vit".to_string()],
+        ];
+        assert_eq!(table_column_widths(&header, &rows), vec![7, 9]);
+        assert_eq!(format_table_row(&rows[1], &[7, 9]), "10       vit");
+
+        let mut all = [
+            export_version(1, 0.1, "resnet152"),
+            export_version(10, 0.25, "vit"),
+        ];
+        all[1].hparams.remove("lr");
+        let versions: Vec<&VersionData> = all.iter().collect();
+        let columns = vec!["name".to_string(), "lr".to_string()];
+        let mut config = Config::default();
+        config.tui.trim_trailing_zeros = true;
+
+        let mut output = Vec::new();
+        write_versions_table(&versions, &columns, &config, &mut output)
+            .expect("Failed to write table");
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "version  name       lr\n\
+             1        resnet152  0.1\n\
+             10       vit        -\n"
+        );
+    }
+
+    #[test]
+    fn test_coverage_matrix_output() {
+        let key = |x: &str, y: &str| (x.to_string(), y.to_string());
+        let matrix = HashMap::from([
+            (key("cnn", "mnist"), 2),
+            (key("rnn", "cifar"), 1),
+            (key("transformer", "cifar"), 12),
+        ]);
+
+        let mut output = Vec::new();
+        write_coverage_matrix(&matrix, "model", "dataset", &mut output)
+            .expect("Failed to write coverage matrix");
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "dataset \\ model  cnn  rnn  transformer\n\
+             cifar            0    1    12\n\
+             mnist            2    0    0\n"
+        );
+    }
+}
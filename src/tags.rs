@@ -0,0 +1,113 @@
+// src/tags.rs
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// 版本标注存储，按版本号保存用户添加的简短备注，并持久化为JSON文件
+#[derive(Debug, Default)]
+pub struct TagStore {
+    path: Option<PathBuf>,       // 持久化文件路径，None时只保存在内存中
+    tags: BTreeMap<u32, String>, // 版本号到备注的映射
+}
+
+impl TagStore {
+    /// 从指定路径加载标注，文件不存在时返回空的存储
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let tags = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read tags file: {}", path.display()))?;
+            serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse tags file: {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            tags,
+        })
+    }
+
+    /// 将标注写回文件，未指定路径时不做任何操作
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let contents = serde_json::to_string_pretty(&self.tags)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write tags file: {}", path.display()))
+    }
+
+    /// 获取版本的备注
+    pub fn get(&self, version_num: u32) -> Option<&str> {
+        self.tags.get(&version_num).map(String::as_str)
+    }
+
+    /// 设置版本的备注，备注为空（去除首尾空白后）时删除该版本的备注
+    pub fn set(&mut self, version_num: u32, note: &str) {
+        let note = note.trim();
+        if note.is_empty() {
+            self.tags.remove(&version_num);
+        } else {
+            self.tags.insert(version_num, note.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_tags_save_and_load() {
+        let temp_dir = tempdir().unwrap();
+        let tags_path = temp_dir.path().join("tags.json");
+
+        let mut tags = TagStore::load(&tags_path).unwrap();
+        tags.set(3, "best so far");
+        tags.set(10, "  diverged  ");
+        tags.save().unwrap();
+
+        let loaded = TagStore::load(&tags_path).unwrap();
+        assert_eq!(loaded.get(3), Some("best so far"));
+        assert_eq!(loaded.get(10), Some("diverged"));
+        assert_eq!(loaded.get(4), None);
+
+        // 文件内容按版本号记录
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&tags_path).unwrap()).unwrap();
+        assert_eq!(json["3"], "best so far");
+    }
+
+    #[test]
+    fn test_tags_load_missing_file() {
+        let temp_dir = tempdir().unwrap();
+        let tags = TagStore::load(temp_dir.path().join("tags.json")).unwrap();
+        assert_eq!(tags.get(1), None);
+    }
+
+    #[test]
+    fn test_tags_clear_with_empty_note() {
+        let temp_dir = tempdir().unwrap();
+        let tags_path = temp_dir.path().join("tags.json");
+
+        let mut tags = TagStore::load(&tags_path).unwrap();
+        tags.set(1, "note");
+        tags.set(1, "   ");
+        tags.save().unwrap();
+
+        let loaded = TagStore::load(&tags_path).unwrap();
+        assert_eq!(loaded.get(1), None);
+    }
+
+    #[test]
+    fn test_tags_load_invalid_file() {
+        let temp_dir = tempdir().unwrap();
+        let tags_path = temp_dir.path().join("tags.json");
+        fs::write(&tags_path, "not json").unwrap();
+        assert!(TagStore::load(&tags_path).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+// src/test_utils.rs
+//! 测试共用的辅助函数
+
+use std::path::{Path, PathBuf};
+
+/// 在`dir`下为每个`(版本号, 内容)`创建`version_N/hparams.yaml`，按给定顺序返回文件路径
+pub fn write_version_fixtures(dir: &Path, fixtures: &[(u32, &str)]) -> Vec<PathBuf> {
+    fixtures
+        .iter()
+        .map(|(version, content)| {
+            let file = dir.join(format!("version_{}/hparams.yaml", version));
+            std::fs::create_dir_all(file.parent().unwrap()).expect("Failed to create directory");
+            std::fs::write(&file, content).expect("Failed to write file");
+            file
+        })
+        .collect()
+}
@@ -3,11 +3,13 @@ pub mod controller;
 pub mod event;
 pub mod input;
 pub mod renderer;
+pub mod rescan;
 pub mod utils;
 
-pub use app::{App, ViewMode};
+pub use app::{App, Popup, ViewMode};
 pub use controller::TuiApp;
 pub use event::{Event, EventHandler};
 pub use input::{InputHandler, UserAction};
 pub use renderer::Renderer;
+pub use rescan::Rescanner;
 // pub use utils::*;
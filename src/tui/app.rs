@@ -1,7 +1,32 @@
-use crate::models::AppState;
+use crate::experiment_grouping::{
+    diff_groups, find_different_parameters, find_similar_groups, group_versions,
+    is_under_replicated, refilter_versions,
+};
+use crate::file_utils::{DirectoryListing, list_directory_files};
+use crate::metrics::VersionMetrics;
+use crate::models::{
+    AppState, BasicParameterValue, ExperimentGroup, ExportMetadata, GroupDiff, IgnoreRule,
+    LoadReport, ParameterValue, VersionData, group_as_flags, write_group_diff_json,
+    write_group_diff_markdown, write_versions_csv, write_versions_json,
+};
+use crate::config::save_config;
+use crate::tags::TagStore;
+use anyhow::{Context, Result};
+use crossterm::event::KeyCode;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use crate::tui::input::UserAction;
+use crate::tui::rescan::MetricsUpdate;
+use crate::tui::utils::{
+    UNDER_REPLICATED_COLOR, export_columns, format_diff_line, format_file_size,
+    sort_parameter_entries, sort_versions, collapsed_group_label, displayed_version_range,
+    version_display_name,
+};
 
 /// TUI应用主结构
 pub struct App {
@@ -15,6 +40,64 @@ pub struct App {
     pub detail_content_cache: Vec<Line<'static>>, // 详情面板内容缓存
     pub detail_content_version: Option<u32>, // 缓存对应的版本号，用于判断是否需要更新
     pub detail_scroll_offset: usize,       // 详情面板滚动偏移（用于渲染器）
+    pub detail_h_scroll_offset: usize,     // 详情面板水平滚动偏移（关闭自动换行时使用）
+    pub popup: Option<Popup>,              // 当前显示的弹出窗口，None表示不显示
+    pub tags: TagStore,                    // 版本备注
+    pub tag_input: Option<String>,         // 正在编辑的备注内容，None表示未处于输入状态
+    pub ignore_input: Option<String>,      // 正在输入的忽略参数名，None表示未处于输入状态
+    pub needs_redraw: bool,                // 界面状态是否变化、需要重新绘制
+    pub show_hidden_params: bool,          // 详情面板是否显示被忽略参数和共同参数
+    pub selected_versions: BTreeSet<u32>,  // 多选中的版本号
+    pub version_files_cache: Option<(PathBuf, Option<DirectoryListing>)>, // 版本目录的文件列表缓存，列出失败时为None
+    pub version_panel_proportion: u16, // 运行时的版本面板占比（%），初始值来自配置
+    pub config_path: Option<String>,   // 保存配置时写入的配置文件路径，None表示不支持保存
+    pub expanded_groups: HashSet<String>, // 折叠显示时被展开的实验组ID
+    pub pinned_version: Option<u32>, // 固定显示在详情面板左侧的版本号，None表示未固定
+    pub pinned_content_cache: Vec<Line<'static>>, // 固定版本的详情内容缓存
+    pub similar_groups: HashMap<String, Vec<String>>, // 相似组关系，键为组ID，值为与之相似的组ID列表
+    pub similar_cycle: Option<(String, usize)>, // 相似组循环跳转的起始组ID和当前位置，None表示未在循环中
+}
+
+/// 详情面板中最多列出的版本目录文件数量
+const MAX_LISTED_FILES: usize = 50;
+
+/// 版本面板占比的范围（%），保证两个面板都可见
+const MIN_PANEL_PROPORTION: u16 = 10;
+const MAX_PANEL_PROPORTION: u16 = 90;
+
+/// 导出当前视图时写入的文件名（不含扩展名），位于日志目录下，同名文件已存在时附加序号
+const VIEW_EXPORT_STEM: &str = "view_export";
+const GROUP_DIFF_MARKDOWN: &str = "group_diff.md";
+const GROUP_DIFF_JSON: &str = "group_diff.json";
+// 信息面板中最多列出的解析失败文件数量
+const MAX_INFO_FAILED_FILES: usize = 5;
+
+fn join_version_nums(version_nums: &[u32]) -> String {
+    version_nums
+        .iter()
+        .map(|num| format!("version_{}", num))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 将版本面板占比限制在允许的范围内
+fn clamp_panel_proportion(proportion: i32) -> u16 {
+    proportion.clamp(i32::from(MIN_PANEL_PROPORTION), i32::from(MAX_PANEL_PROPORTION)) as u16
+}
+
+/// 弹出窗口内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct Popup {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+/// 版本列表中的一行，折叠显示时同一实验组的多个版本合并为一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionListRow {
+    pub version_index: usize,       // 该行代表的版本在all_versions中的索引，选中该行即选中此版本
+    pub member_indices: Vec<usize>, // 该行包含的所有版本索引，未折叠时只有version_index
+    pub label: Option<String>,      // 折叠行的显示文本，None表示显示版本名称
 }
 
 /// 视图模式 - 已简化，只支持版本列表模式
@@ -24,7 +107,13 @@ pub enum ViewMode {
 }
 
 impl App {
-    pub fn new(state: AppState) -> Self {
+    pub fn new(mut state: AppState) -> Self {
+        // 版本列表按配置的方式排列
+        sort_versions(&mut state.all_versions, &state.config);
+
+        let version_panel_proportion = clamp_panel_proportion(
+            i32::from(state.config.tui.version_panel_proportion),
+        );
         let mut app = Self {
             state,
             columns: 1,
@@ -36,6 +125,22 @@ impl App {
             detail_content_cache: Vec::new(),
             detail_content_version: None,
             detail_scroll_offset: 0, // 详情面板滚动偏移初始化为0
+            detail_h_scroll_offset: 0,
+            popup: None,
+            tags: TagStore::default(),
+            tag_input: None,
+            ignore_input: None,
+            needs_redraw: true,
+            show_hidden_params: false,
+            selected_versions: BTreeSet::new(),
+            version_files_cache: None,
+            version_panel_proportion,
+            config_path: None,
+            expanded_groups: HashSet::new(),
+            pinned_version: None,
+            pinned_content_cache: Vec::new(),
+            similar_groups: HashMap::new(),
+            similar_cycle: None,
         };
         // 初始化详情面板内容
         app.update_detail_content_cache();
@@ -50,6 +155,15 @@ impl App {
     /// 重置详情面板滚动偏移
     pub fn reset_detail_scroll(&mut self) {
         self.detail_scroll_offset = 0; // 重置渲染器使用的滚动偏移
+        self.detail_h_scroll_offset = 0;
+    }
+
+    /// 把选中索引限制在版本列表范围内，版本集合变化后调用
+    ///
+    /// 避免索引越界时在仍有版本的情况下显示"No version selected"
+    pub fn clamp_selection(&mut self) {
+        let last_index = self.state.all_versions.len().saturating_sub(1);
+        self.selected_version_index = self.selected_version_index.min(last_index);
     }
 
     /// 获取当前选中的版本
@@ -83,39 +197,834 @@ impl App {
         &self,
     ) -> Option<&std::collections::HashMap<String, crate::models::parameter_value::ParameterValue>>
     {
-        if let Some(version) = self.get_selected_version() {
-            // 构建分组键，格式为 "main_key1=value1, main_key2=value2"
-            if let Some(main_keys) = &self.state.config.grouping.main_key {
-                let mut group_key_parts = Vec::new();
-                for main_key in main_keys {
-                    if let Some(main_key_value) = version.hparams.get(main_key) {
-                        group_key_parts.push(format!("{}={}", main_key, main_key_value));
-                    }
-                }
+        self.get_selected_version()
+            .and_then(|version| self.get_main_key_params(version))
+    }
 
-                // 如果所有main_key都存在，则创建分组键并查找
-                if group_key_parts.len() == main_keys.len() {
-                    let group_key = group_key_parts.join(", ");
-                    return self.state.group_common_hparams.get(&group_key);
+    /// 获取指定版本所属main_key分组的共同参数
+    fn get_main_key_params(&self, version: &VersionData) -> Option<&HashMap<String, ParameterValue>> {
+        // 构建分组键，格式为 "main_key1=value1, main_key2=value2"
+        if let Some(main_keys) = &self.state.config.grouping.main_key {
+            let mut group_key_parts = Vec::new();
+            for main_key in main_keys {
+                if let Some(main_key_value) = version.hparams.get(main_key) {
+                    group_key_parts.push(format!("{}={}", main_key, main_key_value));
                 }
             }
+
+            // 如果所有main_key都存在，则创建分组键并查找
+            if group_key_parts.len() == main_keys.len() {
+                let group_key = group_key_parts.join(", ");
+                return self.state.group_common_hparams.get(&group_key);
+            }
         }
         None
     }
 
+    /// 获取版本的完整参数（补回加载时被移除的main_key分组共同参数）
+    fn get_full_hparams(&self, version: &VersionData) -> HashMap<String, ParameterValue> {
+        let mut hparams = version.hparams.clone();
+        if let Some(common) = self.get_main_key_params(version) {
+            for (key, value) in common {
+                hparams.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        hparams
+    }
+
+    /// 查找版本号小于指定版本号的最近版本，不考虑分组
+    pub fn find_previous_version(&self, version_num: u32) -> Option<&VersionData> {
+        self.state
+            .all_versions
+            .iter()
+            .filter(|v| v.version_num < version_num)
+            .max_by_key(|v| v.version_num)
+    }
+
+    /// 将当前选中版本与上一个版本比较
+    ///
+    /// # 返回值
+    /// * `Option<(u32, Vec<String>)>` - 上一个版本的版本号和差异参数键名，没有上一个版本时返回None
+    pub fn diff_against_previous_version(&self) -> Option<(u32, Vec<String>)> {
+        let version = self.get_selected_version()?;
+        let previous = self.find_previous_version(version.version_num)?;
+        let diff_keys = find_different_parameters(
+            &self.get_full_hparams(previous),
+            &self.get_full_hparams(version),
+            &self.state.config,
+        );
+        Some((previous.version_num, diff_keys))
+    }
+
+    /// 显示当前选中版本与上一个版本的差异弹窗
+    pub fn show_previous_version_diff(&mut self) {
+        let Some(version) = self.get_selected_version() else {
+            return;
+        };
+
+        let popup = match self.diff_against_previous_version() {
+            Some((previous_num, diff_keys)) => {
+                let lines = if diff_keys.is_empty() {
+                    vec!["No differences".to_string()]
+                } else {
+                    let previous_params = self
+                        .find_previous_version(version.version_num)
+                        .map(|previous| self.get_full_hparams(previous))
+                        .unwrap_or_default();
+                    let current_params = self.get_full_hparams(version);
+                    let tui = &self.state.config.tui;
+                    diff_keys
+                        .iter()
+                        .map(|key| {
+                            format_diff_line(
+                                key,
+                                previous_params.get(key),
+                                current_params.get(key),
+                                tui.float_display_precision,
+                                tui.trim_trailing_zeros,
+                                self.state.config.diff.show_numeric_delta,
+                            )
+                        })
+                        .collect()
+                };
+                Popup {
+                    title: format!(
+                        "Diff: version_{} vs version_{}",
+                        previous_num, version.version_num
+                    ),
+                    lines,
+                }
+            }
+            None => Popup {
+                title: "Diff".to_string(),
+                lines: vec![format!("No version before version_{}", version.version_num)],
+            },
+        };
+        self.popup = Some(popup);
+    }
+
+    /// 弹窗显示已加载数据集的概要信息
+    pub fn show_info(&mut self) {
+        self.popup = Some(Popup {
+            title: "Info".to_string(),
+            lines: self.info_lines(),
+        });
+    }
+
+    /// 生成信息面板内容：配置文件、日志目录、加载统计以及当前的容差设置
+    pub fn info_lines(&self) -> Vec<String> {
+        let report: &LoadReport = &self.state.load_report;
+        let config = &self.state.config;
+        let tolerance = &config.tolerance;
+
+        let mut lines = vec![
+            format!(
+                "Config file: {}",
+                report.config_path.as_deref().unwrap_or("(defaults)")
+            ),
+            format!("Log directory: {}", config.general.log_dir),
+            format!("Hparams files found: {}", report.files_found),
+            format!("Parse failures: {}", report.failed_files.len()),
+        ];
+        for path in report.failed_files.iter().take(MAX_INFO_FAILED_FILES) {
+            lines.push(format!("  {}", path.display()));
+        }
+        if report.failed_files.len() > MAX_INFO_FAILED_FILES {
+            lines.push(format!(
+                "  ... and {} more",
+                report.failed_files.len() - MAX_INFO_FAILED_FILES
+            ));
+        }
+        if !report.oversized_files.is_empty() {
+            lines.push(format!(
+                "Over max_hparams_file_bytes: {}",
+                report.oversized_files.len()
+            ));
+        }
+        if !report.skipped_versions.is_empty() {
+            lines.push(format!(
+                "Skipped for missing main_key: {}",
+                join_version_nums(&report.skipped_versions)
+            ));
+        }
+        if !report.duplicate_versions.is_empty() {
+            lines.push(format!(
+                "Duplicate version numbers: {}",
+                join_version_nums(&report.duplicate_versions)
+            ));
+        }
+        lines.push(format!("Versions: {}", self.state.all_versions.len()));
+        lines.push(format!("Groups: {}", self.state.experiment_groups.len()));
+        lines.push(format!("Float tolerance: {}", tolerance.float_tolerance));
+        lines.push(format!("Int tolerance: {}", tolerance.int_tolerance));
+        lines.push(format!(
+            "String case sensitive: {}",
+            tolerance.string_case_sensitive
+        ));
+        let mut per_parameter: Vec<_> = tolerance.per_parameter_tolerance.iter().collect();
+        per_parameter.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, value) in per_parameter {
+            lines.push(format!("  {} tolerance: {}", key, value));
+        }
+        lines
+    }
+
+    /// 设置版本备注存储，并刷新详情面板以显示备注
+    pub fn set_tags(&mut self, tags: TagStore) {
+        self.tags = tags;
+        self.update_detail_content_cache();
+    }
+
+    /// 开始为当前选中版本编辑备注，输入框中预填已有备注
+    pub fn start_tag_input(&mut self) {
+        if let Some(version) = self.get_selected_version() {
+            let note = self.tags.get(version.version_num).unwrap_or_default();
+            self.tag_input = Some(note.to_string());
+        }
+    }
+
+    /// 处理备注输入状态下的按键：Enter保存，Esc取消，Backspace删除
+    pub fn handle_tag_input(&mut self, code: KeyCode) {
+        let Some(input) = &mut self.tag_input else {
+            return;
+        };
+        match code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => self.commit_tag_input(),
+            KeyCode::Esc => self.tag_input = None,
+            _ => {}
+        }
+    }
+
+    /// 保存输入的备注并写入文件，写入失败时弹窗提示
+    fn commit_tag_input(&mut self) {
+        let Some(note) = self.tag_input.take() else {
+            return;
+        };
+        let Some(version_num) = self.get_selected_version().map(|v| v.version_num) else {
+            return;
+        };
+
+        self.tags.set(version_num, &note);
+        if let Err(e) = self.tags.save() {
+            self.popup = Some(Popup {
+                title: "Error".to_string(),
+                lines: vec![format!("{:#}", e)],
+            });
+        }
+        self.update_detail_content_cache();
+    }
+
+    /// 设置相似组关系，通常来自`find_similar_groups`
+    pub fn set_similar_groups(&mut self, similar_groups: HashMap<String, Vec<String>>) {
+        self.similar_groups = similar_groups;
+        self.similar_cycle = None;
+    }
+
+    /// 将选中版本跳转到与当前实验组相似的下一个实验组的代表版本
+    ///
+    /// 连续按键时在起始实验组的相似组之间依次循环，最后回到起始实验组；没有相似组时弹窗提示
+    pub fn select_next_similar_group(&mut self) {
+        let Some(group_idx) = self.get_selected_version_group() else {
+            self.popup = Some(Popup {
+                title: "Error".to_string(),
+                lines: vec!["No experiment group selected".to_string()],
+            });
+            return;
+        };
+        let current_id = self.state.experiment_groups[group_idx].group_id.clone();
+
+        // 当前组是上次跳转的目标时继续原来的循环，否则以当前组为起点开始新的循环
+        let (origin, position) = match &self.similar_cycle {
+            Some((origin, position))
+                if self.similar_cycle_targets(origin).get(*position) == Some(&current_id) =>
+            {
+                (origin.clone(), position + 1)
+            }
+            _ => (current_id, 0),
+        };
+        let targets = self.similar_cycle_targets(&origin);
+        if targets.len() <= 1 {
+            self.similar_cycle = None;
+            self.popup = Some(Popup {
+                title: "Similar Groups".to_string(),
+                lines: vec![format!("No groups are similar to {}", origin)],
+            });
+            return;
+        }
+        let position = position % targets.len();
+
+        let target_version = self
+            .state
+            .experiment_groups
+            .iter()
+            .find(|group| group.group_id == targets[position])
+            .map(|group| group.representative().version_num);
+        if let Some(index) = target_version.and_then(|version_num| {
+            self.state
+                .all_versions
+                .iter()
+                .position(|v| v.version_num == version_num)
+        }) {
+            self.selected_version_index = index;
+            self.update_detail_content_cache();
+        }
+        self.similar_cycle = Some((origin, position));
+    }
+
+    /// 循环跳转的目标实验组：起始组的所有相似组，最后是起始组本身
+    fn similar_cycle_targets(&self, origin: &str) -> Vec<String> {
+        self.similar_groups
+            .get(origin)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .chain(std::iter::once(origin.to_string()))
+            .collect()
+    }
+
+    /// 设置保存配置时写入的配置文件路径
+    pub fn set_config_path(&mut self, config_path: impl Into<String>) {
+        self.config_path = Some(config_path.into());
+    }
+
+    /// 将运行时的调整写回配置后保存到配置文件，结果以弹窗提示
+    pub fn save_current_config(&mut self) {
+        self.state.config.tui.version_panel_proportion = self.version_panel_proportion;
+
+        let result = match &self.config_path {
+            Some(config_path) => save_config(&self.state.config, config_path)
+                .map(|_| format!("Saved config to {}", config_path)),
+            None => Err(anyhow::anyhow!("No config file to save to")),
+        };
+        self.popup = Some(match result {
+            Ok(message) => Popup {
+                title: "Config".to_string(),
+                lines: vec![message],
+            },
+            Err(e) => Popup {
+                title: "Error".to_string(),
+                lines: vec![format!("{:#}", e)],
+            },
+        });
+    }
+
+    /// 开始输入要切换忽略状态的参数名
+    pub fn start_ignore_input(&mut self) {
+        self.ignore_input = Some(String::new());
+    }
+
+    /// 处理忽略参数名输入时的按键：Enter切换忽略状态，Esc取消
+    pub fn handle_ignore_input(&mut self, code: KeyCode) {
+        let Some(input) = &mut self.ignore_input else {
+            return;
+        };
+        match code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => self.commit_ignore_input(),
+            KeyCode::Esc => self.ignore_input = None,
+            _ => {}
+        }
+    }
+
+    /// 切换输入的参数的忽略状态，结果以弹窗提示
+    fn commit_ignore_input(&mut self) {
+        let Some(input) = self.ignore_input.take() else {
+            return;
+        };
+        let name = input.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        let popup = match self.toggle_ignored_parameter(name) {
+            Ok(true) => Popup {
+                title: "Ignored Parameters".to_string(),
+                lines: vec![format!("Now ignoring '{}' (this session only)", name)],
+            },
+            Ok(false) => Popup {
+                title: "Ignored Parameters".to_string(),
+                lines: vec![format!("No longer ignoring '{}' (this session only)", name)],
+            },
+            Err(e) => Popup {
+                title: "Error".to_string(),
+                lines: vec![format!("{:#}", e)],
+            },
+        };
+        self.popup = Some(popup);
+    }
+
+    /// 在本次运行中将参数加入或移出忽略参数，并重新过滤和分组所有版本
+    ///
+    /// 只修改内存中的配置，不写回配置文件。返回切换后该参数是否被忽略
+    pub fn toggle_ignored_parameter(&mut self, name: &str) -> Result<bool> {
+        let ignored = &mut self.state.config.ignored_parameters.parameters;
+        let now_ignored = match ignored.iter().position(|entry| entry == name) {
+            Some(index) => {
+                ignored.remove(index);
+                false
+            }
+            None => {
+                ignored.push(name.to_string());
+                true
+            }
+        };
+
+        self.regroup_versions()?;
+        Ok(now_ignored)
+    }
+
+    /// 在本次运行中切换字符串比较是否区分大小写，并立即重新分组所有版本
+    ///
+    /// 只修改内存中的配置，不写回配置文件
+    pub fn toggle_case_sensitivity(&mut self) {
+        let tolerance = &mut self.state.config.tolerance;
+        tolerance.string_case_sensitive = !tolerance.string_case_sensitive;
+
+        let popup = match self.regroup_versions() {
+            Ok(()) => Popup {
+                title: "Case Sensitivity".to_string(),
+                lines: vec![format!(
+                    "String comparison is now {} (this session only), {} groups",
+                    self.case_mode_label(),
+                    self.state.experiment_groups.len()
+                )],
+            },
+            Err(e) => Popup {
+                title: "Error".to_string(),
+                lines: vec![format!("{:#}", e)],
+            },
+        };
+        self.popup = Some(popup);
+    }
+
+    /// 当前字符串比较方式的描述，显示在版本列表边框上
+    pub fn case_mode_label(&self) -> &'static str {
+        if self.state.config.tolerance.string_case_sensitive {
+            "case-sensitive"
+        } else {
+            "case-insensitive"
+        }
+    }
+
+    /// 按当前配置重新过滤和分组所有版本，保持选中同一个版本并刷新详情面板
+    fn regroup_versions(&mut self) -> Result<()> {
+        let selected_version_num = self.get_selected_version().map(|v| v.version_num);
+        let state = &mut self.state;
+        state.group_common_hparams = refilter_versions(&state.config, &mut state.all_versions);
+        state.experiment_groups = group_versions(&state.config, state.all_versions.clone())?;
+        self.similar_groups = find_similar_groups(&state.experiment_groups, &state.config);
+        self.similar_cycle = None;
+
+        if let Some(index) = selected_version_num.and_then(|version_num| {
+            state
+                .all_versions
+                .iter()
+                .position(|v| v.version_num == version_num)
+        }) {
+            self.selected_version_index = index;
+        }
+        self.clamp_selection();
+        self.update_detail_content_cache();
+        Ok(())
+    }
+
+    /// 将后台重新扫描发现的新版本合并到已有数据中，返回实际加入的版本数
+    ///
+    /// 已存在的版本目录会被忽略；合并后重新过滤和分组所有版本，
+    /// 保持选中同一个版本以及详情面板的滚动位置
+    pub fn merge_new_versions(&mut self, versions: Vec<VersionData>) -> Result<usize> {
+        let known_dirs: HashSet<PathBuf> = self
+            .state
+            .all_versions
+            .iter()
+            .map(|v| v.path.clone())
+            .collect();
+        let new_versions: Vec<VersionData> = versions
+            .into_iter()
+            .filter(|v| !known_dirs.contains(&v.path))
+            .collect();
+        if new_versions.is_empty() {
+            return Ok(0);
+        }
+        let added = new_versions.len();
+
+        let selected_path = self.get_selected_version().map(|v| v.path.clone());
+        let state = &mut self.state;
+        state.all_versions.extend(new_versions);
+        state.group_common_hparams = refilter_versions(&state.config, &mut state.all_versions);
+        sort_versions(&mut state.all_versions, &state.config);
+        state.experiment_groups = group_versions(&state.config, state.all_versions.clone())?;
+        state.load_report.versions_loaded = state.all_versions.len();
+        self.similar_groups = find_similar_groups(&state.experiment_groups, &state.config);
+        self.similar_cycle = None;
+
+        if let Some(index) =
+            selected_path.and_then(|path| state.all_versions.iter().position(|v| v.path == path))
+        {
+            self.selected_version_index = index;
+        }
+        self.clamp_selection();
+        // 刷新详情内容（组成员可能变化），但不重置滚动位置
+        let scroll = (self.detail_scroll_offset, self.detail_h_scroll_offset);
+        self.update_detail_content_cache();
+        (self.detail_scroll_offset, self.detail_h_scroll_offset) = scroll;
+        self.needs_redraw = true;
+        Ok(added)
+    }
+
+    /// 用后台重新读取的指标替换对应版本（按目录匹配）的指标，实验组中的成员副本一并更新
+    pub fn update_metrics(&mut self, updates: Vec<MetricsUpdate>) {
+        let updates: HashMap<PathBuf, VersionMetrics> = updates.into_iter().collect();
+        let state = &mut self.state;
+        let members = state
+            .experiment_groups
+            .iter_mut()
+            .flat_map(|group| group.member_versions.iter_mut());
+        for version in state.all_versions.iter_mut().chain(members) {
+            if let Some(update) = updates.get(&version.path) {
+                version.metrics = update.metrics.clone();
+                version.best_epoch = update.best_epoch.clone();
+            }
+        }
+        let scroll = (self.detail_scroll_offset, self.detail_h_scroll_offset);
+        self.update_detail_content_cache();
+        (self.detail_scroll_offset, self.detail_h_scroll_offset) = scroll;
+        self.needs_redraw = true;
+    }
+
+    /// 所属实验组重复次数不足（成员数少于`min_replication`）的版本号
+    pub fn under_replicated_versions(&self) -> HashSet<u32> {
+        let min_replication = self.state.config.grouping.min_replication;
+        self.state
+            .experiment_groups
+            .iter()
+            .filter(|group| is_under_replicated(group, min_replication))
+            .flat_map(|group| group.member_versions.iter().map(|v| v.version_num))
+            .collect()
+    }
+
+    /// 关闭弹出窗口
+    pub fn close_popup(&mut self) {
+        self.popup = None;
+    }
+
+    /// 切换是否在详情面板中显示被忽略参数和共同参数
+    pub fn toggle_hidden_params(&mut self) {
+        self.show_hidden_params = !self.show_hidden_params;
+        self.update_detail_content_cache();
+    }
+
+    /// 固定当前选中的版本，使其详情在浏览其他版本时保持可见；已有固定版本时取消固定
+    pub fn toggle_pin(&mut self) {
+        self.pinned_version = match self.pinned_version {
+            Some(_) => None,
+            None => self.get_selected_version().map(|v| v.version_num),
+        };
+        self.update_detail_content_cache();
+    }
+
+    /// 获取固定的版本，固定的版本已不存在（如重新加载后）时返回None
+    pub fn get_pinned_version(&self) -> Option<&VersionData> {
+        let version_num = self.pinned_version?;
+        self.state
+            .all_versions
+            .iter()
+            .find(|v| v.version_num == version_num)
+    }
+
+    /// 是否需要并排显示固定版本的详情，即固定了与当前选中版本不同的版本
+    pub fn shows_pinned_details(&self) -> bool {
+        self.get_pinned_version().is_some_and(|pinned| {
+            self.get_selected_version()
+                .is_none_or(|selected| selected.version_num != pinned.version_num)
+        })
+    }
+
+    /// 按步长调整版本面板占比，结果限制在10%到90%之间
+    pub fn resize_version_panel(&mut self, delta: i16) {
+        self.version_panel_proportion = clamp_panel_proportion(
+            i32::from(self.version_panel_proportion) + i32::from(delta),
+        );
+    }
+
+    /// 版本列表中当前可见的版本索引，与版本列表渲染的行一一对应
+    ///
+    /// 只包含当前页内的版本；折叠的实验组只算作一行，返回该行代表的版本
+    pub fn visible_version_indices(&self) -> Vec<usize> {
+        self.version_list_rows(self.displayed_version_range())
+            .into_iter()
+            .map(|row| row.version_index)
+            .collect()
+    }
+
+    /// 版本列表当前页显示的版本索引范围，始终包含当前选中的版本
+    pub fn displayed_version_range(&self) -> Range<usize> {
+        displayed_version_range(
+            self.state.all_versions.len(),
+            self.state.config.tui.max_versions_displayed,
+            self.selected_version_index,
+        )
+    }
+
+    /// 因分页而未显示在版本列表中的版本数量
+    pub fn hidden_version_count(&self) -> usize {
+        self.state.all_versions.len() - self.displayed_version_range().len()
+    }
+
+    /// 翻到版本列表的下一页或上一页，选中该页的第一个版本，已在首页或末页时不移动
+    pub fn change_version_page(&mut self, forward: bool) {
+        let range = self.displayed_version_range();
+        let page_size = self.state.config.tui.max_versions_displayed;
+        let new_index = if forward && range.end < self.state.all_versions.len() {
+            range.end
+        } else if !forward && range.start > 0 {
+            range.start.saturating_sub(page_size)
+        } else {
+            return;
+        };
+        self.selected_version_index = new_index;
+        self.version_list_scroll_offset = 0;
+        self.reset_detail_scroll();
+    }
+
+    /// 生成版本列表中`range`范围内的版本对应的行
+    ///
+    /// 开启`collapse_group_versions`时，同一实验组在范围内的多个版本合并为一行，
+    /// 显示在组内第一个版本的位置；被展开的实验组和只有一个版本的实验组不折叠
+    pub fn version_list_rows(&self, range: Range<usize>) -> Vec<VersionListRow> {
+        let single = |index: usize| VersionListRow {
+            version_index: index,
+            member_indices: vec![index],
+            label: None,
+        };
+        if !self.state.config.tui.collapse_group_versions {
+            return range.map(single).collect();
+        }
+
+        let group_of: HashMap<u32, usize> = self
+            .state
+            .experiment_groups
+            .iter()
+            .enumerate()
+            .flat_map(|(group_idx, group)| {
+                group.member_versions.iter().map(move |v| (v.version_num, group_idx))
+            })
+            .collect();
+        let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+        for index in range.clone() {
+            if let Some(&group_idx) = group_of.get(&self.state.all_versions[index].version_num) {
+                members.entry(group_idx).or_default().push(index);
+            }
+        }
+
+        let mut rows = Vec::new();
+        for index in range {
+            let version_num = self.state.all_versions[index].version_num;
+            let Some(&group_idx) = group_of.get(&version_num) else {
+                rows.push(single(index));
+                continue;
+            };
+            let group = &self.state.experiment_groups[group_idx];
+            let group_members = &members[&group_idx];
+            if group_members.len() < 2 || self.expanded_groups.contains(&group.group_id) {
+                rows.push(single(index));
+            } else if group_members[0] == index {
+                let version_nums: Vec<u32> = group_members
+                    .iter()
+                    .map(|&member| self.state.all_versions[member].version_num)
+                    .collect();
+                rows.push(VersionListRow {
+                    version_index: index,
+                    member_indices: group_members.clone(),
+                    label: Some(collapsed_group_label(
+                        &group.label(&self.state.config),
+                        &version_nums,
+                    )),
+                });
+            }
+        }
+        rows
+    }
+
+    /// 展开或重新折叠当前版本所在的实验组，只在折叠显示时有效
+    pub fn toggle_group_expansion(&mut self) {
+        if !self.state.config.tui.collapse_group_versions {
+            return;
+        }
+        let Some(group_idx) = self.get_selected_version_group() else {
+            return;
+        };
+        let group_id = self.state.experiment_groups[group_idx].group_id.clone();
+        if !self.expanded_groups.remove(&group_id) {
+            self.expanded_groups.insert(group_id);
+        }
+    }
+
+    /// 切换当前版本的多选状态
+    pub fn toggle_current_selection(&mut self) {
+        let Some(version_num) = self.get_selected_version().map(|v| v.version_num) else {
+            return;
+        };
+        if !self.selected_versions.remove(&version_num) {
+            self.selected_versions.insert(version_num);
+        }
+    }
+
+    /// 选中所有可见版本
+    pub fn select_all_visible(&mut self) {
+        for index in self.visible_version_indices() {
+            self.selected_versions
+                .insert(self.state.all_versions[index].version_num);
+        }
+    }
+
+    /// 反转所有可见版本的选中状态，不可见版本的选中状态保持不变
+    pub fn invert_visible_selection(&mut self) {
+        for index in self.visible_version_indices() {
+            let version_num = self.state.all_versions[index].version_num;
+            if !self.selected_versions.remove(&version_num) {
+                self.selected_versions.insert(version_num);
+            }
+        }
+    }
+
+    /// 当前视图中的版本，按版本列表的显示顺序排列
+    pub fn visible_versions(&self) -> Vec<&VersionData> {
+        self.visible_version_indices()
+            .into_iter()
+            .map(|index| &self.state.all_versions[index])
+            .collect()
+    }
+
+    /// 将当前视图按显示顺序导出为CSV和JSON，写入日志目录，结果以弹窗提示
+    pub fn export_current_view(&mut self) {
+        let export_dir = PathBuf::from(&self.state.config.general.log_dir);
+        let versions = self.visible_versions();
+        let columns = export_columns(&versions, self.state.config.tui.detail_sort_mode);
+
+        let metadata = ExportMetadata::from(&self.state.config);
+
+        let popup = match export_view_files(&export_dir, &versions, &columns, &metadata) {
+            Ok(paths) => Popup {
+                title: "Export".to_string(),
+                lines: std::iter::once(format!("Exported {} versions to:", versions.len()))
+                    .chain(paths.iter().map(|path| path.display().to_string()))
+                    .collect(),
+            },
+            Err(e) => Popup {
+                title: "Error".to_string(),
+                lines: vec![format!("{:#}", e)],
+            },
+        };
+        self.popup = Some(popup);
+    }
+
+    /// 多选版本所属的实验组索引，按实验组顺序排列且不重复
+    pub fn selected_group_indices(&self) -> Vec<usize> {
+        self.state
+            .experiment_groups
+            .iter()
+            .enumerate()
+            .filter(|(_, group)| {
+                group
+                    .member_versions
+                    .iter()
+                    .any(|v| self.selected_versions.contains(&v.version_num))
+            })
+            .map(|(group_idx, _)| group_idx)
+            .collect()
+    }
+
+    /// 多选的版本恰好属于两个实验组时，将两组基础参数的差异导出为Markdown和JSON，写入日志目录
+    pub fn export_group_diff(&mut self) {
+        let group_indices = self.selected_group_indices();
+        let popup = match group_indices[..] {
+            [a, b] => {
+                let groups = &self.state.experiment_groups;
+                let diff = diff_groups(&groups[a], &groups[b], &self.state.config);
+                let export_dir = PathBuf::from(&self.state.config.general.log_dir);
+                match export_group_diff_files(&export_dir, &diff) {
+                    Ok(paths) => Popup {
+                        title: "Group Diff".to_string(),
+                        lines: std::iter::once(format!(
+                            "Exported {} differences between {} and {} to:",
+                            diff.differences.len(),
+                            diff.left_group_id,
+                            diff.right_group_id
+                        ))
+                        .chain(paths.iter().map(|path| path.display().to_string()))
+                        .collect(),
+                    },
+                    Err(e) => Popup {
+                        title: "Error".to_string(),
+                        lines: vec![format!("{:#}", e)],
+                    },
+                }
+            }
+            _ => Popup {
+                title: "Error".to_string(),
+                lines: vec![format!(
+                    "Select versions from exactly two groups to diff them ({} selected)",
+                    group_indices.len()
+                )],
+            },
+        };
+        self.popup = Some(popup);
+    }
+
+    /// 将当前版本所属实验组的基础参数格式化为命令行参数，并在弹出窗口中显示
+    ///
+    /// 返回需要复制到剪贴板的文本，没有选中实验组时返回None
+    pub fn copy_group_flags(&mut self) -> Option<String> {
+        let Some(group_idx) = self.get_selected_version_group() else {
+            self.popup = Some(Popup {
+                title: "Error".to_string(),
+                lines: vec!["No experiment group selected".to_string()],
+            });
+            return None;
+        };
+        let group = &self.state.experiment_groups[group_idx];
+        let flags = group_as_flags(group, "--", &self.state.config.tui.flag_key_separator);
+        self.popup = Some(Popup {
+            title: "Copy Flags".to_string(),
+            lines: vec![
+                format!("Copied flags of group {} to clipboard:", group.group_id),
+                flags.clone(),
+            ],
+        });
+        Some(flags)
+    }
+
+    /// 按配置的浮点数显示精度格式化参数值
+    pub fn format_value(&self, value: &ParameterValue) -> String {
+        let tui = &self.state.config.tui;
+        value.to_display_string(tui.float_display_precision, tui.trim_trailing_zeros)
+    }
+
     /// 更新详情面板内容缓存
     pub fn update_detail_content_cache(&mut self) {
         let mut all_content_lines = Vec::new();
 
+        // 先生成固定版本的内容，使版本目录文件列表缓存最终保留当前版本的结果
+        self.pinned_content_cache = match self.get_pinned_version().cloned() {
+            Some(pinned) => self.build_detail_lines(&pinned),
+            None => Vec::new(),
+        };
+
         // 先获取版本信息，避免借用冲突
         let version_info = self
             .get_selected_version()
             .map(|v| (v.version_num, v.clone()));
 
         if let Some((version_num, version)) = version_info {
-            self.build_version_content(&mut all_content_lines, &version);
-            self.build_experiment_group_content(&mut all_content_lines, &version);
-            self.build_main_key_content(&mut all_content_lines, &version);
+            all_content_lines = self.build_detail_lines(&version);
             self.detail_content_version = Some(version_num);
         } else {
             all_content_lines.push(Line::from("No version selected"));
@@ -126,10 +1035,24 @@ impl App {
         self.reset_detail_scroll();
     }
 
+    /// 生成单个版本的详情面板内容
+    fn build_detail_lines(&mut self, version: &VersionData) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        self.build_version_content(&mut lines, version);
+        self.build_experiment_group_content(&mut lines, version);
+        self.build_main_key_content(&mut lines, version);
+        if self.show_hidden_params {
+            self.build_hidden_params_content(&mut lines, version);
+        }
+        self.build_notes_content(&mut lines, version);
+        self.build_files_content(&mut lines, version);
+        lines
+    }
+
     fn build_version_content(
         &mut self,
         lines: &mut Vec<Line<'static>>,
-        _version: &crate::models::models::VersionData,
+        version: &crate::models::models::VersionData,
     ) {
         lines.push(Line::from(vec![
             Span::styled(
@@ -139,10 +1062,50 @@ impl App {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                _version.version_num.to_string(),
+                version.version_num.to_string(),
                 Style::default().fg(Color::Green),
             ),
         ]));
+        if let Some(note) = self.tags.get(version.version_num) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "Tag: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(note.to_string(), Style::default().fg(Color::Yellow)),
+            ]));
+        }
+        if let Some(hparams_file) = &version.hparams_file {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "Hparams file: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(hparams_file.clone(), Style::default().fg(Color::Green)),
+            ]));
+        }
+        if let Some(best) = &version.best_epoch {
+            let value = self.format_value(&ParameterValue::Basic(BasicParameterValue::Float(
+                best.value,
+            )));
+            let text = match best.epoch {
+                Some(epoch) => format!("epoch {}, {}={}", epoch, best.metric, value),
+                None => format!("{}={}", best.metric, value),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "Best: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(text, Style::default().fg(Color::Green)),
+            ]));
+        }
         lines.push(Line::from(""));
         lines.push(Line::from(vec![Span::styled(
             "Hyperparameters:",
@@ -151,17 +1114,121 @@ impl App {
                 .add_modifier(Modifier::BOLD),
         )]));
 
-        for (key, value) in &_version.hparams {
+        // 固定参数按配置顺序显示在最前面，缺失的跳过
+        let pinned = &self.state.config.tui.pinned_parameters;
+        let pinned_entries: Vec<_> = pinned
+            .iter()
+            .filter_map(|key| version.hparams.get_key_value(key))
+            .collect();
+        let mut other_entries: Vec<_> = version
+            .hparams
+            .iter()
+            .filter(|(key, _)| !pinned.contains(key))
+            .collect();
+        sort_parameter_entries(
+            &mut other_entries,
+            self.state.config.tui.detail_sort_mode,
+            &version.param_order,
+        );
+
+        for (key, value) in &pinned_entries {
+            lines.push(self.build_param_line(key, value));
+        }
+        if !pinned_entries.is_empty() && !other_entries.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  ────────",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for (key, value) in &other_entries {
+            lines.push(self.build_param_line(key, value));
+        }
+
+        if let Some(content_hash) = &version.content_hash {
+            lines.push(Line::from(""));
             lines.push(Line::from(vec![
                 Span::styled(
-                    format!("  {}: ", key),
+                    "Content SHA-256: ",
                     Style::default()
                         .fg(Color::Cyan)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(value.to_simple_string(), Style::default().fg(Color::Green)),
+                Span::styled(content_hash.clone(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+    }
+
+    /// 构建版本目录下备注文件的内容，未配置或文件不存在时不显示
+    fn build_notes_content(&self, lines: &mut Vec<Line<'static>>, version: &VersionData) {
+        let Some(notes_file) = &self.state.config.general.notes_file else {
+            return;
+        };
+        let Ok(notes) = std::fs::read_to_string(version.path.join(notes_file)) else {
+            return;
+        };
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "Notes:",
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        for line in notes.trim_end().lines() {
+            lines.push(Line::from(format!("  {}", line)));
+        }
+    }
+
+    /// 构建版本目录的文件列表，只在选中的版本目录变化时重新扫描
+    fn build_files_content(&mut self, lines: &mut Vec<Line<'static>>, version: &VersionData) {
+        if self
+            .version_files_cache
+            .as_ref()
+            .is_none_or(|(path, _)| *path != version.path)
+        {
+            let listing = list_directory_files(&version.path).ok();
+            self.version_files_cache = Some((version.path.clone(), listing));
+        }
+        let Some((_, Some(listing))) = &self.version_files_cache else {
+            return;
+        };
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Files ({} files, {}):",
+                listing.files.len(),
+                format_file_size(listing.total_bytes)
+            ),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        for (name, size) in listing.files.iter().take(MAX_LISTED_FILES) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}  ", name), Style::default().fg(Color::Cyan)),
+                Span::styled(format_file_size(*size), Style::default().fg(Color::DarkGray)),
             ]));
         }
+        if listing.files.len() > MAX_LISTED_FILES {
+            lines.push(Line::from(Span::styled(
+                format!("  ... and {} more", listing.files.len() - MAX_LISTED_FILES),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+
+    /// 构建单个参数行
+    fn build_param_line(&self, key: &str, value: &ParameterValue) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(
+                format!("  {}: ", key),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(self.format_value(value), Style::default().fg(Color::Green)),
+        ])
     }
 
     /// 构建实验组内容
@@ -171,15 +1238,21 @@ impl App {
         _version: &crate::models::models::VersionData,
     ) {
         if let Some(group_idx) = self.get_selected_version_group() {
+            let group = &self.state.experiment_groups[group_idx];
             lines.push(Line::from(""));
             lines.push(Line::from(vec![Span::styled(
-                format!("Experiment Group {}:", group_idx + 1),
+                format!(
+                    "Experiment Group {} [{}] (representative: version_{}, latest: version_{}):",
+                    group_idx + 1,
+                    group.label(&self.state.config),
+                    group.representative().version_num,
+                    group.latest().version_num
+                ),
                 Style::default()
                     .fg(Color::Magenta)
                     .add_modifier(Modifier::BOLD),
             )]));
 
-            let group = &self.state.experiment_groups[group_idx];
             for (key, value) in &group.base_parameters {
                 lines.push(Line::from(vec![
                     Span::styled(
@@ -188,17 +1261,58 @@ impl App {
                             .fg(Color::Cyan)
                             .add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(value.to_simple_string(), Style::default().fg(Color::Green)),
+                    Span::styled(self.format_value(value), Style::default().fg(Color::Green)),
                 ]));
             }
+
+            // 成员列表，按版本号排列，附加各成员在变化参数上的取值
+            let varying = if self.state.config.tui.show_member_varying_params {
+                group.varying_parameters(&self.state.config)
+            } else {
+                Vec::new()
+            };
+            lines.push(Line::from(Span::styled(
+                "  Members:",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            let mut members: Vec<_> = group.member_versions.iter().collect();
+            members.sort_by_key(|member| member.version_num);
+            for member in members {
+                let mut spans = vec![Span::raw(format!("    {}", version_display_name(member)))];
+                let suffix = ExperimentGroup::member_suffix(member, &varying, &self.state.config);
+                if !suffix.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" {}", suffix),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+            let min_replication = self.state.config.grouping.min_replication;
+            if is_under_replicated(group, min_replication) {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "  Under-replicated: {} of {} runs",
+                        group.member_versions.len(),
+                        min_replication
+                    ),
+                    Style::default().fg(UNDER_REPLICATED_COLOR),
+                )));
+            }
         }
     }
 
     /// 构建main_key内容
+    /// 构建main_key分组共有参数的内容
+    ///
+    /// 版本保留了完整解析结果时，与版本自身的值比较：不一致的共有参数以黄色显示，
+    /// 并在后面附上版本的实际值（缺失时显示missing）
     fn build_main_key_content(
         &self,
         lines: &mut Vec<Line<'static>>,
-        _version: &crate::models::models::VersionData,
+        version: &crate::models::models::VersionData,
     ) {
         if let Some(main_key_params) = self.get_selected_version_main_key_params() {
             lines.push(Line::from(""));
@@ -209,16 +1323,90 @@ impl App {
                     .add_modifier(Modifier::BOLD),
             )]));
 
-            for (key, value) in main_key_params {
-                lines.push(Line::from(vec![
+            let tolerance = &self.state.config.tolerance;
+            let mut entries: Vec<_> = main_key_params.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, value) in entries {
+                // 没有完整解析结果时无法比较，按一致处理
+                let override_value = if version.full_hparams.is_empty() {
+                    None
+                } else {
+                    match version.full_hparams.get(key) {
+                        Some(actual)
+                            if actual.equals_with_tolerance_for_key(value, key, tolerance) =>
+                        {
+                            None
+                        }
+                        Some(actual) => Some(self.format_value(actual)),
+                        None => Some("missing".to_string()),
+                    }
+                };
+
+                let value_color = if override_value.is_some() {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                };
+                let mut spans = vec![
                     Span::styled(
                         format!("  {}: ", key),
                         Style::default()
                             .fg(Color::Cyan)
                             .add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(value.to_simple_string(), Style::default().fg(Color::Green)),
-                ]));
+                    Span::styled(self.format_value(value), Style::default().fg(value_color)),
+                ];
+                if let Some(actual) = override_value {
+                    spans.push(Span::styled(
+                        format!(" (version: {})", actual),
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+    }
+
+    /// 构建被隐藏参数的内容
+    ///
+    /// 完整解析结果中不在版本参数里的参数分为两类：被配置忽略（或不在分组参数中）的标记为ignored，
+    /// 其余是加载时作为共同参数删除的，标记为common
+    fn build_hidden_params_content(&self, lines: &mut Vec<Line<'static>>, version: &VersionData) {
+        let config = &self.state.config;
+        let ignore_rules = IgnoreRule::parse_all(&config.ignored_parameters.parameters);
+        let is_ignored = |key: &str, value: &ParameterValue| {
+            ignore_rules.iter().any(|rule| rule.matches(key, value))
+                || config
+                    .grouping
+                    .effective_grouping_parameters()
+                    .is_some_and(|params| !params.iter().any(|p| p == key))
+        };
+
+        let mut hidden: Vec<_> = version
+            .full_hparams
+            .iter()
+            .filter(|(key, _)| !version.hparams.contains_key(*key))
+            .collect();
+        hidden.sort_by(|a, b| a.0.cmp(b.0));
+        let (ignored, common): (Vec<_>, Vec<_>) =
+            hidden.into_iter().partition(|(key, value)| is_ignored(key, value));
+
+        for (title, entries) in [
+            ("Ignored Parameters (ignored):", ignored),
+            ("Common Parameters (common):", common),
+        ] {
+            if entries.is_empty() {
+                continue;
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![Span::styled(
+                title,
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+            for (key, value) in entries {
+                lines.push(self.build_param_line(key, value));
             }
         }
     }
@@ -251,14 +1439,87 @@ impl App {
     }
 }
 
+/// 返回导出目录下不覆盖已有文件的一组导出路径
+///
+/// 依次尝试`stem`、`stem_2`、`stem_3`……，直到所有扩展名对应的文件都不存在
+fn unique_export_paths(export_dir: &Path, stem: &str, extensions: &[&str]) -> Vec<PathBuf> {
+    (1..)
+        .map(|n| {
+            let name = if n == 1 {
+                stem.to_string()
+            } else {
+                format!("{}_{}", stem, n)
+            };
+            extensions
+                .iter()
+                .map(|ext| export_dir.join(format!("{}.{}", name, ext)))
+                .collect::<Vec<_>>()
+        })
+        .find(|paths| paths.iter().all(|path| !path.exists()))
+        .expect("export path candidates are unbounded")
+}
+
+/// 将版本写入导出目录下的CSV和JSON文件，返回写入的文件路径，不覆盖之前的导出
+fn export_view_files(
+    export_dir: &Path,
+    versions: &[&VersionData],
+    columns: &[String],
+    metadata: &ExportMetadata,
+) -> Result<Vec<PathBuf>> {
+    let paths = unique_export_paths(export_dir, VIEW_EXPORT_STEM, &["csv", "json"]);
+    let (csv_path, json_path) = (paths[0].clone(), paths[1].clone());
+
+    let mut writer = BufWriter::new(
+        File::create(&csv_path)
+            .with_context(|| format!("Failed to create {}", csv_path.display()))?,
+    );
+    write_versions_csv(versions, columns, metadata, &mut writer)
+        .and_then(|_| writer.flush())
+        .with_context(|| format!("Failed to write {}", csv_path.display()))?;
+
+    let mut writer = BufWriter::new(
+        File::create(&json_path)
+            .with_context(|| format!("Failed to create {}", json_path.display()))?,
+    );
+    write_versions_json(versions, columns, metadata, &mut writer)
+        .and_then(|_| writer.flush())
+        .with_context(|| format!("Failed to write {}", json_path.display()))?;
+
+    Ok(vec![csv_path, json_path])
+}
+
+/// 将实验组差异报告写为Markdown和JSON文件，返回写入的文件路径
+fn export_group_diff_files(export_dir: &Path, diff: &GroupDiff) -> Result<Vec<PathBuf>> {
+    let markdown_path = export_dir.join(GROUP_DIFF_MARKDOWN);
+    let json_path = export_dir.join(GROUP_DIFF_JSON);
+
+    let mut writer = BufWriter::new(
+        File::create(&markdown_path)
+            .with_context(|| format!("Failed to create {}", markdown_path.display()))?,
+    );
+    write_group_diff_markdown(diff, &mut writer)
+        .and_then(|_| writer.flush())
+        .with_context(|| format!("Failed to write {}", markdown_path.display()))?;
+
+    let mut writer = BufWriter::new(
+        File::create(&json_path)
+            .with_context(|| format!("Failed to create {}", json_path.display()))?,
+    );
+    write_group_diff_json(diff, &mut writer)
+        .and_then(|_| writer.flush())
+        .with_context(|| format!("Failed to write {}", json_path.display()))?;
+
+    Ok(vec![markdown_path, json_path])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::metrics::BestEpoch;
     use crate::models::config::Config;
     use crate::models::models::{ExperimentGroup, VersionData};
     use crate::models::parameter_value::{BasicParameterValue, ParameterValue};
-    use std::collections::HashMap;
-    use std::path::PathBuf;
+    use std::collections::{BTreeMap, HashMap};
 
     fn create_test_app_state() -> AppState {
         let config = Config::default();
@@ -285,15 +1546,15 @@ mod tests {
         );
 
         let version1 = VersionData {
-            version_num: 1,
             path: PathBuf::from("logs/version_1"),
             hparams: hparams1,
+            ..VersionData::for_test(1)
         };
 
         let version2 = VersionData {
-            version_num: 2,
             path: PathBuf::from("logs/version_2"),
             hparams: hparams2,
+            ..VersionData::for_test(2)
         };
 
         let all_versions = vec![version1.clone(), version2.clone()];
@@ -325,6 +1586,7 @@ mod tests {
             experiment_groups,
             config,
             group_common_hparams,
+            load_report: LoadReport::default(),
         }
     }
 
@@ -373,4 +1635,962 @@ mod tests {
         let group_idx = app.get_selected_version_group();
         assert_eq!(group_idx, Some(1));
     }
+
+    #[test]
+    fn test_diff_against_previous_version() {
+        let mut state = create_test_app_state();
+        let mut hparams5 = HashMap::new();
+        hparams5.insert(
+            "learning_rate".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.001)),
+        );
+        hparams5.insert(
+            "batch_size".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Int(128)),
+        );
+        hparams5.insert(
+            "dropout".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.1)),
+        );
+        // 版本号不连续，上一个版本应为version_2
+        state.all_versions.push(
+            VersionData {
+                path: PathBuf::from("logs/version_5"),
+                hparams: hparams5,
+                ..VersionData::for_test(5)
+            },
+        );
+        let mut app = App::new(state);
+
+        app.selected_version_index = 2;
+        assert_eq!(app.find_previous_version(5).unwrap().version_num, 2);
+        let (previous, diff_keys) = app.diff_against_previous_version().unwrap();
+        assert_eq!(previous, 2);
+        assert_eq!(diff_keys, vec!["batch_size", "dropout"]);
+
+        app.show_previous_version_diff();
+        let popup = app.popup.clone().unwrap();
+        assert_eq!(popup.lines.len(), 2);
+        assert!(popup.lines[1].starts_with("dropout: <missing> vs"));
+
+        // version_1没有更早的版本
+        app.selected_version_index = 0;
+        assert!(app.diff_against_previous_version().is_none());
+        app.show_previous_version_diff();
+        assert_eq!(
+            app.popup.unwrap().lines,
+            vec!["No version before version_1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_pinned_parameters_first() {
+        let mut state = create_test_app_state();
+        for (key, value) in [("model", "cnn"), ("dataset", "mnist"), ("activation", "relu")] {
+            state.all_versions[0].hparams.insert(
+                key.to_string(),
+                ParameterValue::Basic(BasicParameterValue::String(value.to_string())),
+            );
+        }
+        state.config.tui.pinned_parameters = vec![
+            "model".to_string(),
+            "missing".to_string(),
+            "learning_rate".to_string(),
+        ];
+        let app = App::new(state);
+
+        let lines: Vec<String> = app
+            .detail_content_cache
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let start = lines
+            .iter()
+            .position(|line| line == "Hyperparameters:")
+            .unwrap()
+            + 1;
+        let params: Vec<&str> = lines[start..start + 6].iter().map(String::as_str).collect();
+
+        assert_eq!(
+            params,
+            vec![
+                "  model: cnn",
+                "  learning_rate: 0.01",
+                "  ────────",
+                "  activation: relu",
+                "  batch_size: 32",
+                "  dataset: mnist",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_header_shows_latest_version() {
+        let mut state = create_test_app_state();
+        let extra = VersionData {
+            path: PathBuf::from("logs/version_7"),
+            ..VersionData::for_test(7)
+        };
+        state.experiment_groups[0].member_versions.push(extra.clone());
+        state.all_versions.push(extra);
+        let app = App::new(state);
+
+        assert!(app.detail_content_cache.iter().any(|line| {
+            line.to_string()
+                .ends_with("(representative: version_1, latest: version_7):")
+        }));
+    }
+
+    #[test]
+    fn test_tag_input() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let tags_path = temp_dir.path().join("tags.json");
+        let mut app = App::new(create_test_app_state());
+        app.set_tags(TagStore::load(&tags_path).unwrap());
+
+        app.start_tag_input();
+        for c in "bestt".chars() {
+            app.handle_tag_input(KeyCode::Char(c));
+        }
+        app.handle_tag_input(KeyCode::Backspace);
+        app.handle_tag_input(KeyCode::Enter);
+
+        assert!(app.tag_input.is_none());
+        assert_eq!(app.tags.get(1), Some("best"));
+        assert!(
+            app.detail_content_cache
+                .iter()
+                .any(|line| line.to_string() == "Tag: best")
+        );
+        // 已写入文件
+        assert_eq!(TagStore::load(&tags_path).unwrap().get(1), Some("best"));
+
+        // Esc取消编辑，不修改备注
+        app.start_tag_input();
+        assert_eq!(app.tag_input.as_deref(), Some("best"));
+        app.handle_tag_input(KeyCode::Char('!'));
+        app.handle_tag_input(KeyCode::Esc);
+        assert_eq!(app.tags.get(1), Some("best"));
+    }
+
+    #[test]
+    fn test_pinned_version() {
+        let mut app = App::new(create_test_app_state());
+        assert!(!app.shows_pinned_details());
+
+        // 固定版本1后浏览版本2，固定内容仍然对应版本1
+        app.toggle_pin();
+        assert_eq!(app.pinned_version, Some(1));
+        assert!(!app.shows_pinned_details());
+        app.selected_version_index = 1;
+        app.smart_update_detail_content_cache();
+        assert!(app.shows_pinned_details());
+        assert_eq!(app.get_pinned_version().unwrap().version_num, 1);
+        assert_eq!(app.pinned_content_cache[0].to_string(), "Version: 1");
+        assert_eq!(app.detail_content_cache[0].to_string(), "Version: 2");
+        assert!(
+            app.pinned_content_cache
+                .iter()
+                .any(|line| line.to_string().contains("learning_rate: 0.01"))
+        );
+
+        // 固定的版本不存在时不再并排显示
+        app.pinned_version = Some(42);
+        assert!(app.get_pinned_version().is_none());
+        assert!(!app.shows_pinned_details());
+
+        app.toggle_pin();
+        assert_eq!(app.pinned_version, None);
+        assert!(app.pinned_content_cache.is_empty());
+    }
+
+    #[test]
+    fn test_merge_new_versions() {
+        let mut state = create_test_app_state();
+        for version in &mut state.all_versions {
+            version.full_hparams = version.hparams.clone();
+        }
+        let mut app = App::new(state);
+        app.selected_version_index = 1;
+        app.update_detail_content_cache();
+        app.detail_scroll_offset = 3;
+        app.needs_redraw = false;
+
+        // 已存在的版本目录被忽略
+        let existing = app.state.all_versions[0].clone();
+        assert_eq!(app.merge_new_versions(vec![existing.clone()]).unwrap(), 0);
+        assert!(!app.needs_redraw);
+
+        let mut version0 = existing.clone();
+        version0.version_num = 0;
+        version0.path = PathBuf::from("logs/version_0");
+        let mut version3 = existing;
+        version3.version_num = 3;
+        version3.path = PathBuf::from("logs/version_3");
+        assert_eq!(app.merge_new_versions(vec![version3, version0]).unwrap(), 2);
+
+        // 新版本加入列表和实验组，排在前面的新版本不影响当前选中的版本和滚动位置
+        let version_nums: Vec<u32> = app
+            .state
+            .all_versions
+            .iter()
+            .map(|v| v.version_num)
+            .collect();
+        assert_eq!(version_nums, vec![0, 1, 2, 3]);
+        let grouped: usize = app
+            .state
+            .experiment_groups
+            .iter()
+            .map(|g| g.member_versions.len())
+            .sum();
+        assert_eq!(grouped, 4);
+        assert_eq!(app.state.load_report.versions_loaded, 4);
+        assert_eq!(app.get_selected_version().unwrap().version_num, 2);
+        assert_eq!(app.detail_scroll_offset, 3);
+        assert!(app.needs_redraw);
+    }
+
+    #[test]
+    fn test_update_metrics() {
+        let mut app = App::new(create_test_app_state());
+        app.needs_redraw = false;
+        let path = app.state.all_versions[0].path.clone();
+        let metrics = BTreeMap::from([("val_loss".to_string(), 0.3)]);
+        let best_epoch = BestEpoch {
+            metric: "val_loss".to_string(),
+            epoch: Some(4),
+            value: 0.3,
+        };
+        app.update_metrics(vec![(
+            path.clone(),
+            VersionMetrics {
+                metrics: metrics.clone(),
+                best_epoch: Some(best_epoch.clone()),
+            },
+        )]);
+
+        // 版本列表和实验组中的副本都被更新
+        assert_eq!(app.state.all_versions[0].metrics, metrics);
+        assert_eq!(app.state.all_versions[0].best_epoch, Some(best_epoch));
+        assert!(app.state.all_versions[1].metrics.is_empty());
+        let member = app
+            .state
+            .experiment_groups
+            .iter()
+            .flat_map(|group| &group.member_versions)
+            .find(|version| version.path == path)
+            .unwrap();
+        assert_eq!(member.metrics, metrics);
+        assert!(app.needs_redraw);
+    }
+
+    #[test]
+    fn test_under_replicated_versions() {
+        let mut state = create_test_app_state();
+        state.experiment_groups[0]
+            .member_versions
+            .push(state.all_versions[1].clone());
+        state.experiment_groups[0].member_versions[1].version_num = 3;
+        let mut app = App::new(state);
+        assert!(app.under_replicated_versions().is_empty());
+
+        // 组1有2个成员，达到阈值；组2只有1个成员
+        app.state.config.grouping.min_replication = 2;
+        assert_eq!(app.under_replicated_versions(), HashSet::from([2]));
+        app.selected_version_index = 1;
+        app.update_detail_content_cache();
+        assert!(
+            app.detail_content_cache
+                .iter()
+                .any(|line| line.to_string() == "  Under-replicated: 1 of 2 runs")
+        );
+    }
+
+    #[test]
+    fn test_select_next_similar_group() {
+        let mut state = create_test_app_state();
+        let mut version3 = state.all_versions[1].clone();
+        version3.version_num = 3;
+        version3.path = PathBuf::from("logs/version_3");
+        state.all_versions.push(version3.clone());
+        state.experiment_groups.push(ExperimentGroup {
+            group_id: "group_3".to_string(),
+            base_parameters: HashMap::new(),
+            member_versions: vec![version3],
+        });
+        let mut app = App::new(state);
+        app.set_similar_groups(HashMap::from([
+            (
+                "group_1".to_string(),
+                vec!["group_2".to_string(), "group_3".to_string()],
+            ),
+            ("group_2".to_string(), vec!["group_1".to_string()]),
+            ("group_3".to_string(), vec!["group_1".to_string()]),
+        ]));
+
+        // 从组1出发依次经过组2、组3，再回到组1
+        let mut visited = Vec::new();
+        for _ in 0..4 {
+            app.select_next_similar_group();
+            visited.push(app.get_selected_version().unwrap().version_num);
+        }
+        assert_eq!(visited, vec![2, 3, 1, 2]);
+        assert!(app.popup.is_none());
+
+        // 手动选中其他版本后以新的组为起点重新循环
+        app.selected_version_index = 2;
+        app.select_next_similar_group();
+        assert_eq!(app.get_selected_version().unwrap().version_num, 1);
+        app.select_next_similar_group();
+        assert_eq!(app.get_selected_version().unwrap().version_num, 3);
+
+        // 没有相似组时保持选中并弹窗提示
+        app.set_similar_groups(HashMap::new());
+        app.select_next_similar_group();
+        assert_eq!(app.get_selected_version().unwrap().version_num, 3);
+        assert_eq!(app.popup.as_ref().unwrap().title, "Similar Groups");
+    }
+
+    #[test]
+    fn test_toggle_hidden_params() {
+        let mut state = create_test_app_state();
+        state.config.ignored_parameters.parameters = vec!["seed".to_string()];
+        for version in &mut state.all_versions {
+            version.full_hparams = version.hparams.clone();
+            version.full_hparams.insert(
+                "seed".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(42)),
+            );
+            version.full_hparams.insert(
+                "optimizer".to_string(),
+                ParameterValue::Basic(BasicParameterValue::String("adam".to_string())),
+            );
+        }
+        let mut app = App::new(state);
+        let content = |app: &App| -> Vec<String> {
+            app.detail_content_cache
+                .iter()
+                .map(|line| line.to_string())
+                .collect()
+        };
+
+        // 默认不显示
+        let lines = content(&app);
+        assert!(!lines.iter().any(|line| line.contains("(ignored)")));
+        assert!(!lines.iter().any(|line| line.contains("optimizer")));
+
+        app.toggle_hidden_params();
+        let lines = content(&app);
+        let ignored = lines
+            .iter()
+            .position(|line| line == "Ignored Parameters (ignored):")
+            .expect("Ignored section should be shown");
+        assert_eq!(lines[ignored + 1], "  seed: 42");
+        let common = lines
+            .iter()
+            .position(|line| line == "Common Parameters (common):")
+            .expect("Common section should be shown");
+        assert_eq!(lines[common + 1], "  optimizer: adam");
+
+        app.toggle_hidden_params();
+        assert!(!content(&app).iter().any(|line| line.contains("(common)")));
+    }
+
+    #[test]
+    fn test_select_all_and_invert_selection() {
+        let mut app = App::new(create_test_app_state());
+        assert!(app.selected_versions.is_empty());
+
+        app.selected_version_index = 0;
+        app.toggle_current_selection();
+        assert_eq!(app.selected_versions, BTreeSet::from([1]));
+
+        app.invert_visible_selection();
+        assert_eq!(app.selected_versions, BTreeSet::from([2]));
+
+        app.select_all_visible();
+        assert_eq!(app.selected_versions, BTreeSet::from([1, 2]));
+
+        app.invert_visible_selection();
+        assert!(app.selected_versions.is_empty());
+
+        // 再次切换取消选中
+        app.toggle_current_selection();
+        app.toggle_current_selection();
+        assert!(app.selected_versions.is_empty());
+    }
+
+    #[test]
+    fn test_notes_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = create_test_app_state();
+        state.config.general.notes_file = Some("notes.md".to_string());
+        state.all_versions[0].path = temp_dir.path().join("version_1");
+        state.all_versions[1].path = temp_dir.path().join("version_2");
+        std::fs::create_dir_all(&state.all_versions[0].path).unwrap();
+        std::fs::create_dir_all(&state.all_versions[1].path).unwrap();
+        std::fs::write(
+            state.all_versions[0].path.join("notes.md"),
+            "Baseline run\nlr warmup disabled\n",
+        )
+        .unwrap();
+        let mut app = App::new(state);
+
+        let content_text = |app: &App| -> Vec<String> {
+            app.detail_content_cache
+                .iter()
+                .map(|line| line.to_string())
+                .collect()
+        };
+
+        app.selected_version_index = 0;
+        app.update_detail_content_cache();
+        let text = content_text(&app);
+        let notes_index = text.iter().position(|line| line == "Notes:").unwrap();
+        assert_eq!(text[notes_index + 1], "  Baseline run");
+        assert_eq!(text[notes_index + 2], "  lr warmup disabled");
+
+        // 没有备注文件的版本不显示Notes
+        app.selected_version_index = 1;
+        app.update_detail_content_cache();
+        assert!(!content_text(&app).contains(&"Notes:".to_string()));
+    }
+
+    #[test]
+    fn test_best_epoch_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = create_test_app_state();
+        state.config.tui.trim_trailing_zeros = true;
+        state.all_versions[0].path = temp_dir.path().to_path_buf();
+        state.all_versions[0].full_hparams.insert(
+            "call_back_monitor".to_string(),
+            ParameterValue::Basic(BasicParameterValue::String("VAL--acc".to_string())),
+        );
+        let metrics_file = temp_dir.path().join("metrics.csv");
+        std::fs::write(
+            &metrics_file,
+            "epoch,val_acc,val_loss\n0,0.71,0.9\n1,0.93,0.5\n2,0.88,0.4\n",
+        )
+        .unwrap();
+        let version = &state.all_versions[0];
+        let monitor =
+            crate::metrics::monitored_metric(&version.full_hparams, &state.config.metrics);
+        let loaded =
+            crate::metrics::load_version_metrics(&version.path, &state.config.metrics, monitor)
+                .unwrap();
+        state.all_versions[0].best_epoch = loaded.best_epoch;
+        // 最佳记录在加载时读取，渲染详情时不再读取指标文件
+        std::fs::remove_file(&metrics_file).unwrap();
+        let mut app = App::new(state);
+
+        app.selected_version_index = 0;
+        app.update_detail_content_cache();
+        let text: Vec<String> = app
+            .detail_content_cache
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert!(text.contains(&"Best: epoch 1, val_acc=0.93".to_string()));
+
+        // 没有监控参数的版本不显示
+        app.selected_version_index = 1;
+        app.update_detail_content_cache();
+        assert!(
+            !app.detail_content_cache
+                .iter()
+                .any(|line| line.to_string().starts_with("Best: "))
+        );
+    }
+
+    #[test]
+    fn test_collapsed_version_rows() {
+        let mut state = create_test_app_state();
+        let template = state.all_versions[0].clone();
+        let version = |version_num: u32| VersionData {
+            version_num,
+            path: PathBuf::from(format!("logs/version_{}", version_num)),
+            ..template.clone()
+        };
+        state.all_versions = (1..=4).map(version).collect();
+        state.experiment_groups = vec![
+            ExperimentGroup {
+                group_id: "group_a".to_string(),
+                base_parameters: HashMap::new(),
+                member_versions: vec![version(1), version(3), version(4)],
+            },
+            ExperimentGroup {
+                group_id: "group_b".to_string(),
+                base_parameters: HashMap::new(),
+                member_versions: vec![version(2)],
+            },
+        ];
+        let mut app = App::new(state);
+
+        // 默认不折叠
+        assert_eq!(app.version_list_rows(0..4).len(), 4);
+
+        app.state.config.tui.collapse_group_versions = true;
+        let rows = app.version_list_rows(0..4);
+        assert_eq!(
+            rows,
+            vec![
+                VersionListRow {
+                    version_index: 0,
+                    member_indices: vec![0, 2, 3],
+                    label: Some("group_a (×3: v1,v3,v4)".to_string()),
+                },
+                VersionListRow {
+                    version_index: 1,
+                    member_indices: vec![1],
+                    label: None,
+                },
+            ]
+        );
+        // 分页后只折叠当前页内的成员
+        let rows = app.version_list_rows(2..4);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].label.as_deref(), Some("group_a (×2: v3,v4)"));
+
+        // 选中折叠组内的版本后展开，再次切换重新折叠
+        app.selected_version_index = 2;
+        app.toggle_group_expansion();
+        assert_eq!(app.version_list_rows(0..4).len(), 4);
+        app.toggle_group_expansion();
+        assert_eq!(app.version_list_rows(0..4).len(), 2);
+    }
+
+    #[test]
+    fn test_version_list_paging() {
+        let mut state = create_test_app_state();
+        let template = state.all_versions[0].clone();
+        state.all_versions = (1..=5)
+            .map(|version_num| VersionData {
+                version_num,
+                path: PathBuf::from(format!("logs/version_{}", version_num)),
+                ..template.clone()
+            })
+            .collect();
+        state.config.tui.max_versions_displayed = 2;
+        let mut app = App::new(state);
+
+        assert_eq!(app.displayed_version_range(), 0..2);
+        assert_eq!(app.hidden_version_count(), 3);
+
+        // 翻页后选中新一页的第一个版本，末页不满
+        app.change_version_page(true);
+        assert_eq!(app.selected_version_index, 2);
+        app.change_version_page(true);
+        assert_eq!(app.displayed_version_range(), 4..5);
+        assert_eq!(app.hidden_version_count(), 4);
+        // 已在末页时不移动
+        app.change_version_page(true);
+        assert_eq!(app.selected_version_index, 4);
+
+        app.change_version_page(false);
+        assert_eq!(app.displayed_version_range(), 2..4);
+        app.change_version_page(false);
+        app.change_version_page(false);
+        assert_eq!(app.selected_version_index, 0);
+
+        // 不限制时不隐藏任何版本，分组和导出使用的版本列表不受影响
+        app.state.config.tui.max_versions_displayed = 0;
+        assert_eq!(app.displayed_version_range(), 0..5);
+        assert_eq!(app.hidden_version_count(), 0);
+        assert_eq!(app.state.all_versions.len(), 5);
+    }
+
+    #[test]
+    fn test_visible_version_indices() {
+        let mut state = create_test_app_state();
+        let template = state.all_versions[0].clone();
+        let version = |version_num: u32| VersionData {
+            version_num,
+            path: PathBuf::from(format!("logs/version_{}", version_num)),
+            ..template.clone()
+        };
+        state.all_versions = (1..=5).map(version).collect();
+        state.experiment_groups = vec![ExperimentGroup {
+            group_id: "group_a".to_string(),
+            base_parameters: HashMap::new(),
+            member_versions: vec![version(1), version(2), version(5)],
+        }];
+        state.config.tui.max_versions_displayed = 3;
+        state.config.tui.collapse_group_versions = true;
+        let mut app = App::new(state);
+
+        // 第一页的1、2折叠为一行，第2页的5单独一行
+        assert_eq!(app.visible_version_indices(), vec![0, 2]);
+        app.select_all_visible();
+        assert_eq!(app.selected_versions, BTreeSet::from([1, 3]));
+
+        app.change_version_page(true);
+        assert_eq!(app.visible_version_indices(), vec![3, 4]);
+        app.invert_visible_selection();
+        assert_eq!(app.selected_versions, BTreeSet::from([1, 3, 4, 5]));
+
+        // 展开后组内每个版本各占一行
+        app.change_version_page(false);
+        app.toggle_group_expansion();
+        assert_eq!(app.visible_version_indices(), vec![0, 1, 2]);
+        let visible: Vec<u32> = app
+            .visible_versions()
+            .iter()
+            .map(|v| v.version_num)
+            .collect();
+        assert_eq!(visible, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_group_member_varying_suffix() {
+        let mut state = create_test_app_state();
+        state.config.tui.trim_trailing_zeros = true;
+        let mut version3 = state.experiment_groups[0].member_versions[0].clone();
+        version3.version_num = 3;
+        version3.path = PathBuf::from("logs/version_3");
+        version3.hparams.insert(
+            "learning_rate".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.1)),
+        );
+        state.experiment_groups[0].member_versions.push(version3);
+        let mut app = App::new(state);
+
+        app.selected_version_index = 0;
+        app.update_detail_content_cache();
+        let text: Vec<String> = app
+            .detail_content_cache
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert!(text.contains(&"    version_1 (learning_rate=0.01)".to_string()));
+        assert!(text.contains(&"    version_3 (learning_rate=0.1)".to_string()));
+
+        // 关闭后只显示成员名称
+        app.state.config.tui.show_member_varying_params = false;
+        app.update_detail_content_cache();
+        assert!(
+            app.detail_content_cache
+                .iter()
+                .any(|line| line.to_string() == "    version_3")
+        );
+    }
+
+    #[test]
+    fn test_main_key_common_params_compared_with_version() {
+        let mut state = create_test_app_state();
+        state.config.grouping.main_key = Some(vec!["model".to_string()]);
+        let string = |s: &str| ParameterValue::Basic(BasicParameterValue::String(s.to_string()));
+        let mut common = HashMap::new();
+        common.insert("optimizer".to_string(), string("adam"));
+        common.insert("epochs".to_string(), ParameterValue::Basic(BasicParameterValue::Int(10)));
+        common.insert("scheduler".to_string(), string("cosine"));
+        state
+            .group_common_hparams
+            .insert("model=cnn".to_string(), common);
+
+        let version = &mut state.all_versions[0];
+        version.hparams.insert("model".to_string(), string("cnn"));
+        version.full_hparams = version.hparams.clone();
+        version
+            .full_hparams
+            .insert("optimizer".to_string(), string("adam"));
+        version.full_hparams.insert(
+            "epochs".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Int(20)),
+        );
+
+        let mut app = App::new(state);
+        app.selected_version_index = 0;
+        app.update_detail_content_cache();
+        let text: Vec<String> = app
+            .detail_content_cache
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        assert!(text.contains(&"  epochs: 10 (version: 20)".to_string()));
+        assert!(text.contains(&"  optimizer: adam".to_string()));
+        assert!(text.contains(&"  scheduler: cosine (version: missing)".to_string()));
+    }
+
+    #[test]
+    fn test_toggle_ignored_parameter() {
+        let config = Config::builder().build();
+        let versions: Vec<VersionData> = [(1, "2024-01-01"), (2, "2024-01-02")]
+            .into_iter()
+            .map(|(version_num, timestamp)| {
+                let mut full_hparams = HashMap::new();
+                full_hparams.insert(
+                    "lr".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::Float(0.01)),
+                );
+                full_hparams.insert(
+                    "timestamp".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::String(timestamp.to_string())),
+                );
+                VersionData {
+                    path: PathBuf::from(format!("logs/version_{}", version_num)),
+                    hparams: full_hparams.clone(),
+                    full_hparams,
+                    ..VersionData::for_test(version_num)
+                }
+            })
+            .collect();
+        let experiment_groups = group_versions(&config, versions.clone()).unwrap();
+        let mut app = App::new(AppState {
+            all_versions: versions,
+            experiment_groups,
+            config,
+            group_common_hparams: HashMap::new(),
+            load_report: LoadReport::default(),
+        });
+        assert_eq!(app.state.experiment_groups.len(), 2);
+
+        // 忽略timestamp后两个版本参数相同，合并为一组
+        app.selected_version_index = 1;
+        assert!(app.toggle_ignored_parameter("timestamp").unwrap());
+        assert_eq!(app.state.experiment_groups.len(), 1);
+        assert!(
+            app.state
+                .all_versions
+                .iter()
+                .all(|v| !v.hparams.contains_key("timestamp"))
+        );
+        assert_eq!(app.get_selected_version().unwrap().version_num, 2);
+
+        // 再次切换恢复原来的分组和忽略参数列表
+        assert!(!app.toggle_ignored_parameter("timestamp").unwrap());
+        assert_eq!(app.state.experiment_groups.len(), 2);
+        assert!(app.state.all_versions[0].hparams.contains_key("timestamp"));
+        assert!(app.state.config.ignored_parameters.parameters.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_case_sensitivity() {
+        let config = Config::builder().string_case_sensitive(false).build();
+        let versions: Vec<VersionData> = [(1, "Adam"), (2, "adam")]
+            .into_iter()
+            .map(|(version_num, optimizer)| {
+                let mut full_hparams = HashMap::new();
+                full_hparams.insert(
+                    "lr".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::Float(0.01)),
+                );
+                full_hparams.insert(
+                    "optimizer".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::String(optimizer.to_string())),
+                );
+                VersionData {
+                    path: PathBuf::from(format!("logs/version_{}", version_num)),
+                    hparams: full_hparams.clone(),
+                    full_hparams,
+                    ..VersionData::for_test(version_num)
+                }
+            })
+            .collect();
+        let experiment_groups = group_versions(&config, versions.clone()).unwrap();
+        let mut app = App::new(AppState {
+            all_versions: versions,
+            experiment_groups,
+            config,
+            group_common_hparams: HashMap::new(),
+            load_report: LoadReport::default(),
+        });
+        assert_eq!(app.state.experiment_groups.len(), 1);
+        assert_eq!(app.case_mode_label(), "case-insensitive");
+
+        // 区分大小写后两个版本只有大小写不同的optimizer被分到不同组
+        app.selected_version_index = 1;
+        app.toggle_case_sensitivity();
+        assert!(app.state.config.tolerance.string_case_sensitive);
+        assert_eq!(app.state.experiment_groups.len(), 2);
+        assert_eq!(app.case_mode_label(), "case-sensitive");
+        assert_eq!(app.get_selected_version().unwrap().version_num, 2);
+        assert_eq!(app.popup.as_ref().unwrap().title, "Case Sensitivity");
+
+        // 再次切换恢复为一组
+        app.toggle_case_sensitivity();
+        assert_eq!(app.state.experiment_groups.len(), 1);
+        assert_eq!(app.case_mode_label(), "case-insensitive");
+    }
+
+    #[test]
+    fn test_clamp_selection() {
+        let mut app = App::new(create_test_app_state());
+        app.selected_version_index = 1;
+        app.clamp_selection();
+        assert_eq!(app.selected_version_index, 1);
+
+        // 版本减少后选中索引落到最后一个版本上
+        app.state.all_versions.truncate(1);
+        app.selected_version_index = 5;
+        app.regroup_versions().unwrap();
+        assert_eq!(app.selected_version_index, 0);
+        assert!(app.get_selected_version().is_some());
+
+        app.state.all_versions.clear();
+        app.clamp_selection();
+        assert_eq!(app.selected_version_index, 0);
+    }
+
+    #[test]
+    fn test_save_current_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("saved.toml");
+        let mut app = App::new(create_test_app_state());
+
+        // 未设置配置文件路径时提示错误
+        app.save_current_config();
+        assert_eq!(app.popup.take().unwrap().title, "Error");
+
+        app.set_config_path(config_path.to_str().unwrap());
+        app.resize_version_panel(-20);
+        app.save_current_config();
+        assert_eq!(app.popup.take().unwrap().title, "Config");
+
+        let saved: Config = toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(saved.tui.version_panel_proportion, app.version_panel_proportion);
+        assert_eq!(saved, app.state.config);
+    }
+
+    #[test]
+    fn test_export_current_view() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = create_test_app_state();
+        state.config.general.log_dir = temp_dir.path().display().to_string();
+        let mut app = App::new(state);
+
+        app.export_current_view();
+        assert_eq!(app.popup.as_ref().unwrap().title, "Export");
+
+        let csv = std::fs::read_to_string(temp_dir.path().join("view_export.csv")).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), app.state.all_versions.len() + 2);
+        assert!(rows[0].starts_with("# {\"ignored_parameters\""));
+        assert!(rows[1].starts_with("version,path,"));
+        assert!(rows[2].starts_with("1,"));
+        assert!(rows[3].starts_with("2,"));
+        let json = std::fs::read_to_string(temp_dir.path().join("view_export.json")).unwrap();
+        assert!(json.contains("\"metadata\""));
+
+        // 再次导出时不覆盖之前的文件，分页后只导出当前页显示的版本
+        app.state.config.tui.max_versions_displayed = 1;
+        app.export_current_view();
+        let csv = std::fs::read_to_string(temp_dir.path().join("view_export_2.csv")).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(temp_dir.path().join("view_export_2.json").exists());
+        let first = std::fs::read_to_string(temp_dir.path().join("view_export.csv")).unwrap();
+        assert_eq!(first.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_export_group_diff() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = create_test_app_state();
+        state.config.general.log_dir = temp_dir.path().display().to_string();
+        let mut app = App::new(state);
+
+        // 只选中一个实验组的版本时提示错误
+        app.selected_versions.insert(1);
+        app.export_group_diff();
+        assert_eq!(app.popup.as_ref().unwrap().title, "Error");
+        assert!(!temp_dir.path().join(GROUP_DIFF_MARKDOWN).exists());
+
+        app.selected_versions.insert(2);
+        assert_eq!(app.selected_group_indices(), vec![0, 1]);
+        app.export_group_diff();
+        let popup = app.popup.as_ref().unwrap();
+        assert_eq!(popup.title, "Group Diff");
+        assert_eq!(
+            popup.lines[0],
+            "Exported 1 differences between group_1 and group_2 to:"
+        );
+        let markdown =
+            std::fs::read_to_string(temp_dir.path().join(GROUP_DIFF_MARKDOWN)).unwrap();
+        assert!(markdown.contains("| model_type | `CNN` | *missing* |"));
+        assert!(temp_dir.path().join(GROUP_DIFF_JSON).exists());
+    }
+
+    #[test]
+    fn test_copy_group_flags() {
+        let state = create_test_app_state();
+        let mut app = App::new(state);
+
+        // 默认选中version_1，属于group_1
+        assert_eq!(app.copy_group_flags().as_deref(), Some("--model_type CNN"));
+        let popup = app.popup.as_ref().unwrap();
+        assert_eq!(popup.title, "Copy Flags");
+        assert_eq!(popup.lines[1], "--model_type CNN");
+
+        // 选中的版本不属于任何实验组时提示错误
+        app.state.experiment_groups.clear();
+        assert_eq!(app.copy_group_flags(), None);
+        assert_eq!(app.popup.as_ref().unwrap().title, "Error");
+    }
+
+    #[test]
+    fn test_resize_version_panel() {
+        let mut state = create_test_app_state();
+        state.config.tui.version_panel_proportion = 95;
+        let mut app = App::new(state);
+        // 配置值超出范围时被限制
+        assert_eq!(app.version_panel_proportion, 90);
+
+        app.resize_version_panel(5);
+        assert_eq!(app.version_panel_proportion, 90);
+        app.resize_version_panel(-5);
+        assert_eq!(app.version_panel_proportion, 85);
+
+        for _ in 0..20 {
+            app.resize_version_panel(-5);
+        }
+        assert_eq!(app.version_panel_proportion, 10);
+        app.resize_version_panel(5);
+        assert_eq!(app.version_panel_proportion, 15);
+    }
+
+    #[test]
+    fn test_info_lines() {
+        let mut state = create_test_app_state();
+        state.config.general.log_dir = "lightning_logs".to_string();
+        state
+            .config
+            .tolerance
+            .per_parameter_tolerance
+            .insert("lr".to_string(), 0.5);
+        state.load_report = LoadReport {
+            config_path: Some("lightning_explorer.toml".to_string()),
+            files_found: 10,
+            failed_files: (0..7)
+                .map(|i| PathBuf::from(format!("logs/version_{}/hparams.yaml", i)))
+                .collect(),
+            ..LoadReport::default()
+        };
+        let mut app = App::new(state);
+
+        let lines = app.info_lines();
+        assert_eq!(lines[0], "Config file: lightning_explorer.toml");
+        assert_eq!(lines[1], "Log directory: lightning_logs");
+        assert_eq!(lines[2], "Hparams files found: 10");
+        assert_eq!(lines[3], "Parse failures: 7");
+        // 只列出前几个失败文件
+        assert_eq!(lines[4], "  logs/version_0/hparams.yaml");
+        assert_eq!(lines[9], "  ... and 2 more");
+        assert_eq!(lines[10], "Versions: 2");
+        assert_eq!(lines[11], "Groups: 2");
+        assert!(lines.contains(&"  lr tolerance: 0.5".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("Skipped")));
+
+        app.state.load_report.skipped_versions = vec![2, 4];
+        assert!(
+            app.info_lines()
+                .contains(&"Skipped for missing main_key: version_2, version_4".to_string())
+        );
+
+        app.show_info();
+        assert_eq!(app.popup.as_ref().unwrap().title, "Info");
+
+        // 未从文件加载配置时显示默认配置
+        app.state.load_report = LoadReport::default();
+        assert_eq!(app.info_lines()[0], "Config file: (defaults)");
+    }
 }
@@ -1,6 +1,7 @@
 use crate::tui::{
-    App, Event, EventHandler, InputHandler, Renderer, UserAction
+    App, Event, EventHandler, InputHandler, Popup, Renderer, Rescanner, UserAction
 };
+use crate::tui::utils::osc52_copy_sequence;
 use anyhow::Result;
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::{
@@ -9,15 +10,19 @@ use crossterm::{
 };
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
-use std::io;
+use std::io::{self, Write};
 use std::time::Duration;
 
+/// 每次调整版本面板占比的步长（%）
+const PANEL_RESIZE_STEP: i16 = 5;
+
 /// TUI应用控制器，负责协调各个组件
 pub struct TuiApp {
     app: App,
     input_handler: InputHandler,
     renderer: Renderer,
     terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    rescanner: Option<Rescanner>, // 后台重新扫描器，运行时启动，None表示不重新扫描
 }
 
 impl TuiApp {
@@ -37,35 +42,109 @@ impl TuiApp {
             input_handler,
             renderer,
             terminal,
+            rescanner: None,
         })
     }
 
+    /// 设置后台重新扫描器，在运行主循环时启动
+    pub fn set_rescanner(&mut self, rescanner: Rescanner) {
+        self.rescanner = Some(rescanner);
+    }
+
     /// 运行TUI应用主循环
     pub fn run(&mut self) -> Result<()> {
         // 创建事件处理器，使用配置中的刷新率
         let tick_rate = Duration::from_millis(self.app.state.config.tui.refresh_rate_ms);
         let events = EventHandler::new(tick_rate);
+        if let Some(rescanner) = self.rescanner.take() {
+            rescanner.spawn(events.sender());
+        }
 
         // 初始化时更新详情内容缓存
         self.app.smart_update_detail_content_cache();
 
         loop {
-            // 渲染界面
-            self.terminal.draw(|f| {
-                self.renderer.draw(f, &mut self.app);
-            })?;
+            // 仅在界面状态变化时渲染，空闲时跳过绘制
+            if self.app.needs_redraw {
+                self.terminal.draw(|f| {
+                    self.renderer.draw(f, &mut self.app);
+                })?;
+                self.app.needs_redraw = false;
+            }
 
             // 处理事件
-            match events.next()? {
+            let event = events.next()?;
+            if event.requires_redraw() {
+                self.app.needs_redraw = true;
+            }
+            match event {
                 Event::Input(event) => {
+                    if self.app.tag_input.is_some() {
+                        // 输入备注时按键直接作为文本输入
+                        self.app.handle_tag_input(event.code);
+                        continue;
+                    }
+                    if self.app.ignore_input.is_some() {
+                        // 输入忽略参数名时按键直接作为文本输入
+                        self.app.handle_ignore_input(event.code);
+                        continue;
+                    }
                     let action = self.input_handler.handle_key_event(event);
+                    if self.app.popup.is_some() {
+                        // 弹出窗口显示时，任意按键关闭弹出窗口
+                        self.app.close_popup();
+                        continue;
+                    }
                     match action {
                         UserAction::Quit => self.app.quit(),
+                        UserAction::DiffPrevious => self.app.show_previous_version_diff(),
+                        UserAction::Tag => self.app.start_tag_input(),
+                        UserAction::ToggleHiddenParams => self.app.toggle_hidden_params(),
+                        UserAction::Select => self.app.toggle_current_selection(),
+                        UserAction::SelectAll => self.app.select_all_visible(),
+                        UserAction::InvertSelection => self.app.invert_visible_selection(),
+                        UserAction::GrowPanel => self.app.resize_version_panel(PANEL_RESIZE_STEP),
+                        UserAction::ShrinkPanel => self.app.resize_version_panel(-PANEL_RESIZE_STEP),
+                        UserAction::ExportView => self.app.export_current_view(),
+                        UserAction::ToggleIgnore => self.app.start_ignore_input(),
+                        UserAction::SaveConfig => self.app.save_current_config(),
+                        UserAction::Confirm => self.app.toggle_group_expansion(),
+                        UserAction::NextPage => self.app.change_version_page(true),
+                        UserAction::PreviousPage => self.app.change_version_page(false),
+                        UserAction::ShowInfo => self.app.show_info(),
+                        UserAction::ExportGroupDiff => self.app.export_group_diff(),
+                        UserAction::PinVersion => self.app.toggle_pin(),
+                        UserAction::NextSimilarGroup => self.app.select_next_similar_group(),
+                        UserAction::ToggleCaseSensitivity => self.app.toggle_case_sensitivity(),
+                        UserAction::CopyGroupFlags => {
+                            if let Some(flags) = self.app.copy_group_flags() {
+                                self.copy_to_clipboard(&flags)?;
+                            }
+                        }
                         _ => self.app.last_user_action = action
                     }
                      
                     // self.handle_user_action(action)?;
                 }
+                Event::Resize => {}
+                Event::NewVersions(versions) => {
+                    if let Err(e) = self.app.merge_new_versions(versions) {
+                        self.app.popup = Some(Popup {
+                            title: "Error".to_string(),
+                            lines: vec![format!("Failed to load new versions: {:#}", e)],
+                        });
+                        self.app.needs_redraw = true;
+                    }
+                }
+                Event::MetricsUpdated(updates) => self.app.update_metrics(updates),
+                Event::Warnings(warnings) => {
+                    self.app.popup = Some(Popup {
+                        title: "Warning".to_string(),
+                        lines: warnings.clone(),
+                    });
+                    self.app.state.load_report.warnings.extend(warnings);
+                    self.app.needs_redraw = true;
+                }
                 Event::Tick => {
                     // 可以在这里添加定时任务
                 }
@@ -105,6 +184,14 @@ impl TuiApp {
     // }
 
 
+    // 通过OSC 52转义序列将文本写入终端剪贴板
+    fn copy_to_clipboard(&mut self, text: &str) -> Result<()> {
+        let backend = self.terminal.backend_mut();
+        backend.write_all(osc52_copy_sequence(text).as_bytes())?;
+        backend.flush()?;
+        Ok(())
+    }
+
     // 清理终端设置
     pub fn cleanup(&mut self) -> Result<()> {
         disable_raw_mode()?;
@@ -1,3 +1,5 @@
+use crate::models::VersionData;
+use crate::tui::rescan::MetricsUpdate;
 use crossterm::event::{self, Event as CEvent, KeyEvent};
 use std::sync::mpsc;
 use std::thread;
@@ -5,27 +7,41 @@ use std::time::{Duration, Instant};
 
 pub enum Event {
     Input(KeyEvent),
+    Resize,
     Tick,
+    NewVersions(Vec<VersionData>),      // 后台重新扫描发现的新版本
+    MetricsUpdated(Vec<MetricsUpdate>), // 后台重新读取的已知版本的指标
+    Warnings(Vec<String>),              // 后台扫描产生的警告，在TUI中显示而不是打印到终端
+}
+
+impl Event {
+    /// 判断该事件是否需要重新绘制界面：按键与终端尺寸变化需要重绘，空闲Tick不需要；
+    /// 新版本是否改变界面由合并结果决定
+    pub fn requires_redraw(&self) -> bool {
+        matches!(self, Event::Input(_) | Event::Resize)
+    }
 }
 
 pub struct EventHandler {
     rx: mpsc::Receiver<Event>,
-    _tx: mpsc::Sender<Event>,
+    tx: mpsc::Sender<Event>, // 保留一个发送端，供后台线程克隆使用
 }
 
 impl EventHandler {
     pub fn new(tick_rate: Duration) -> Self {
         let (tx, rx) = mpsc::channel();
-        let _tx = tx.clone();
+        let sender = tx.clone();
 
         thread::spawn(move || {
             Self::event_loop(tx, tick_rate);
         });
 
-        EventHandler { rx, _tx }
+        EventHandler { rx, tx: sender }
     }
 
     /// 事件循环处理函数
+    ///
+    /// 无输入时在 `event::poll` 上阻塞至下一个Tick，不会空转占用CPU
     fn event_loop(tx: mpsc::Sender<Event>, tick_rate: Duration) {
         let mut last_tick = Instant::now();
         let mut last_key_time = Instant::now();
@@ -81,12 +97,16 @@ impl EventHandler {
 
     /// 处理按键事件
     fn handle_key_event(tx: &mpsc::Sender<Event>, last_key_time: &mut Instant) {
-        if let CEvent::Key(key) = event::read().expect("can read events") {
+        match event::read().expect("can read events") {
             // 添加按键防抖，防止一次按键多次触发
-            if last_key_time.elapsed() > Duration::from_millis(150) {
+            CEvent::Key(key) if last_key_time.elapsed() > Duration::from_millis(150) => {
                 tx.send(Event::Input(key)).expect("can send events");
                 *last_key_time = Instant::now();
             }
+            CEvent::Resize(_, _) => {
+                tx.send(Event::Resize).expect("can send events");
+            }
+            _ => {}
         }
     }
 
@@ -95,7 +115,26 @@ impl EventHandler {
         last_tick.elapsed() >= tick_rate
     }
 
+    /// 获取事件发送端，供其他后台线程向事件循环发送事件
+    pub fn sender(&self) -> mpsc::Sender<Event> {
+        self.tx.clone()
+    }
+
     pub fn next(&self) -> Result<Event, mpsc::RecvError> {
         self.rx.recv()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn test_requires_redraw() {
+        assert!(Event::Input(KeyEvent::from(KeyCode::Char('j'))).requires_redraw());
+        assert!(Event::Resize.requires_redraw());
+        assert!(!Event::Tick.requires_redraw());
+        assert!(!Event::NewVersions(Vec::new()).requires_redraw());
+    }
+}
@@ -1,9 +1,10 @@
-use crate::models::KeybindingsConfig;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::models::{KeySpec, KeybindingsConfig};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// 输入处理器，负责将按键事件映射到应用操作
 pub struct InputHandler {
     keybindings: KeybindingsConfig,
+    action_map: Vec<(KeySpec, UserAction)>, // 按键（含修饰键要求）到操作的映射，构造时解析
 }
 
 /// 用户操作类型
@@ -16,6 +17,26 @@ pub enum UserAction {
     MoveRight,
     ScrollDetailUp,
     ScrollDetailDown,
+    ScrollDetailLeft,
+    ScrollDetailRight,
+    DiffPrevious,
+    Tag,
+    ToggleHiddenParams,
+    SelectAll,
+    InvertSelection,
+    GrowPanel,
+    ShrinkPanel,
+    ExportView,
+    ToggleIgnore,
+    SaveConfig,
+    CopyGroupFlags,
+    NextPage,
+    PreviousPage,
+    ShowInfo,
+    ExportGroupDiff,
+    PinVersion,
+    NextSimilarGroup,
+    ToggleCaseSensitivity,
     Help,
     Filter,
     Select,
@@ -23,15 +44,76 @@ pub enum UserAction {
     None,
 }
 
+impl UserAction {
+    /// 按键绑定配置项名称对应的操作，没有对应操作的配置项返回None
+    pub fn from_binding_name(name: &str) -> Option<Self> {
+        let action = match name {
+            "up" => UserAction::MoveUp,
+            "down" => UserAction::MoveDown,
+            "left" => UserAction::MoveLeft,
+            "right" => UserAction::MoveRight,
+            "select" => UserAction::Select,
+            "confirm" => UserAction::Confirm,
+            "quit" => UserAction::Quit,
+            "help" => UserAction::Help,
+            "filter" => UserAction::Filter,
+            "scroll_detail_up" => UserAction::ScrollDetailUp,
+            "scroll_detail_down" => UserAction::ScrollDetailDown,
+            "diff_previous" => UserAction::DiffPrevious,
+            "scroll_detail_left" => UserAction::ScrollDetailLeft,
+            "scroll_detail_right" => UserAction::ScrollDetailRight,
+            "tag" => UserAction::Tag,
+            "toggle_hidden_params" => UserAction::ToggleHiddenParams,
+            "select_all" => UserAction::SelectAll,
+            "invert_selection" => UserAction::InvertSelection,
+            "grow_panel" => UserAction::GrowPanel,
+            "shrink_panel" => UserAction::ShrinkPanel,
+            "export_view" => UserAction::ExportView,
+            "toggle_ignore" => UserAction::ToggleIgnore,
+            "save_config" => UserAction::SaveConfig,
+            "copy_group_flags" => UserAction::CopyGroupFlags,
+            "next_page" => UserAction::NextPage,
+            "previous_page" => UserAction::PreviousPage,
+            "info" => UserAction::ShowInfo,
+            "export_group_diff" => UserAction::ExportGroupDiff,
+            "pin_version" => UserAction::PinVersion,
+            "next_similar_group" => UserAction::NextSimilarGroup,
+            "toggle_case_sensitivity" => UserAction::ToggleCaseSensitivity,
+            _ => return None,
+        };
+        Some(action)
+    }
+}
+
 impl InputHandler {
     pub fn new(keybindings: KeybindingsConfig) -> Self {
-        Self { keybindings }
+        let action_map = Self::build_action_map(&keybindings);
+        Self {
+            keybindings,
+            action_map,
+        }
     }
 
     /// 处理按键事件，返回对应的用户操作
+    ///
+    /// 按住Ctrl或Alt时只匹配带有相同修饰键的绑定，如`ctrl+r`；Shift不参与匹配，
+    /// 大写字母本身即可区分
     pub fn handle_key_event(&self, key_event: KeyEvent) -> UserAction {
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key_event.modifiers.contains(KeyModifiers::ALT);
+        let named = |name: &str| KeySpec {
+            key: name.to_string(),
+            ctrl,
+            alt,
+        };
         match key_event.code {
-            KeyCode::Char(c) => self.handle_char_key(c),
+            KeyCode::Char(c) => self.find_matching_action(&named(&Self::char_key_name(c))),
+            // 带修饰键的方向键和回车键按配置的组合键匹配
+            KeyCode::Up if ctrl || alt => self.find_matching_action(&named("up")),
+            KeyCode::Down if ctrl || alt => self.find_matching_action(&named("down")),
+            KeyCode::Left if ctrl || alt => self.find_matching_action(&named("left")),
+            KeyCode::Right if ctrl || alt => self.find_matching_action(&named("right")),
+            KeyCode::Enter if ctrl || alt => self.find_matching_action(&named("enter")),
             KeyCode::Up => self.get_move_action(&self.keybindings.up),
             KeyCode::Down => self.get_move_action(&self.keybindings.down),
             KeyCode::Left => self.get_move_action(&self.keybindings.left),
@@ -42,48 +124,34 @@ impl InputHandler {
         }
     }
 
-    /// 处理字符按键
-    fn handle_char_key(&self, c: char) -> UserAction {
-        let key_str = c.to_string();
-
-        // 构建操作映射表
-        let action_map = self.build_action_map();
-
-        // 查找匹配的操作
-        self.find_matching_action(&key_str, &action_map)
+    /// 字符按键在配置中的名称，空格键在配置中写作"space"
+    fn char_key_name(c: char) -> String {
+        if c == ' ' {
+            "space".to_string()
+        } else {
+            c.to_string()
+        }
     }
 
-    /// 构建操作映射表
-    fn build_action_map(&self) -> [(&str, UserAction); 11] {
-        [
-            (&self.keybindings.quit, UserAction::Quit),
-            (&self.keybindings.help, UserAction::Help),
-            (&self.keybindings.filter, UserAction::Filter),
-            (&self.keybindings.select, UserAction::Select),
-            (
-                &self.keybindings.scroll_detail_up,
-                UserAction::ScrollDetailUp,
-            ),
-            (
-                &self.keybindings.scroll_detail_down,
-                UserAction::ScrollDetailDown,
-            ),
-            (&self.keybindings.up, UserAction::MoveUp),
-            (&self.keybindings.down, UserAction::MoveDown),
-            (&self.keybindings.left, UserAction::MoveLeft),
-            (&self.keybindings.right, UserAction::MoveRight),
-            (&self.keybindings.confirm, UserAction::Confirm),
-        ]
+    /// 构建操作映射表，按`KeybindingsConfig::bindings`遍历所有绑定，
+    /// 没有对应操作的配置项（如`switch_view`）和无法解析的绑定被跳过（配置加载时已校验）
+    fn build_action_map(keybindings: &KeybindingsConfig) -> Vec<(KeySpec, UserAction)> {
+        keybindings
+            .bindings()
+            .into_iter()
+            .filter_map(|(name, binding)| {
+                let action = UserAction::from_binding_name(name)?;
+                KeySpec::parse(binding).ok().map(|spec| (spec, action))
+            })
+            .collect()
     }
 
     /// 查找匹配的操作
-    fn find_matching_action(&self, key_str: &str, action_map: &[(&str, UserAction)]) -> UserAction {
-        for (key, action) in action_map {
-            if key_str == *key {
-                return *action;
-            }
-        }
-        UserAction::None
+    fn find_matching_action(&self, key: &KeySpec) -> UserAction {
+        self.action_map
+            .iter()
+            .find(|(spec, _)| spec == key)
+            .map_or(UserAction::None, |(_, action)| *action)
     }
 
     /// 获取移动操作
@@ -131,6 +199,26 @@ mod tests {
             switch_view: "v".to_string(),
             scroll_detail_up: "u".to_string(),
             scroll_detail_down: "d".to_string(),
+            diff_previous: "p".to_string(),
+            scroll_detail_left: "[".to_string(),
+            scroll_detail_right: "]".to_string(),
+            tag: "t".to_string(),
+            toggle_hidden_params: "i".to_string(),
+            select_all: "a".to_string(),
+            invert_selection: "x".to_string(),
+            grow_panel: "+".to_string(),
+            shrink_panel: "-".to_string(),
+            export_view: "e".to_string(),
+            toggle_ignore: "I".to_string(),
+            save_config: "S".to_string(),
+            copy_group_flags: "c".to_string(),
+            next_page: "n".to_string(),
+            previous_page: "N".to_string(),
+            info: "o".to_string(),
+            export_group_diff: "g".to_string(),
+            pin_version: "f".to_string(),
+            next_similar_group: "s".to_string(),
+            toggle_case_sensitivity: "C".to_string(),
         };
 
         let input_handler = InputHandler::new(keybindings);
@@ -152,6 +240,67 @@ mod tests {
             UserAction::ScrollDetailDown
         );
 
+        // 测试详情水平滚动键
+        let scroll_left_event = KeyEvent::from(KeyCode::Char('['));
+        assert_eq!(
+            input_handler.handle_key_event(scroll_left_event),
+            UserAction::ScrollDetailLeft
+        );
+
+        let scroll_right_event = KeyEvent::from(KeyCode::Char(']'));
+        assert_eq!(
+            input_handler.handle_key_event(scroll_right_event),
+            UserAction::ScrollDetailRight
+        );
+
+        // 测试与上一版本比较键
+        let diff_event = KeyEvent::from(KeyCode::Char('p'));
+        assert_eq!(
+            input_handler.handle_key_event(diff_event),
+            UserAction::DiffPrevious
+        );
+
+        // 测试显示隐藏参数键
+        let toggle_event = KeyEvent::from(KeyCode::Char('i'));
+        assert_eq!(
+            input_handler.handle_key_event(toggle_event),
+            UserAction::ToggleHiddenParams
+        );
+
+        // 测试多选相关按键
+        let select_event = KeyEvent::from(KeyCode::Char(' '));
+        assert_eq!(
+            input_handler.handle_key_event(select_event),
+            UserAction::Select
+        );
+        let select_all_event = KeyEvent::from(KeyCode::Char('a'));
+        assert_eq!(
+            input_handler.handle_key_event(select_all_event),
+            UserAction::SelectAll
+        );
+        let invert_event = KeyEvent::from(KeyCode::Char('x'));
+        assert_eq!(
+            input_handler.handle_key_event(invert_event),
+            UserAction::InvertSelection
+        );
+
+        // 测试导出视图键
+        let export_event = KeyEvent::from(KeyCode::Char('e'));
+        assert_eq!(
+            input_handler.handle_key_event(export_event),
+            UserAction::ExportView
+        );
+        let toggle_ignore_event = KeyEvent::from(KeyCode::Char('I'));
+        assert_eq!(
+            input_handler.handle_key_event(toggle_ignore_event),
+            UserAction::ToggleIgnore
+        );
+        let copy_flags_event = KeyEvent::from(KeyCode::Char('c'));
+        assert_eq!(
+            input_handler.handle_key_event(copy_flags_event),
+            UserAction::CopyGroupFlags
+        );
+
         // 测试方向键
         let up_event = KeyEvent::from(KeyCode::Up);
         assert_eq!(input_handler.handle_key_event(up_event), UserAction::MoveUp);
@@ -161,6 +310,65 @@ mod tests {
         assert_eq!(input_handler.handle_key_event(esc_event), UserAction::Quit);
     }
 
+    #[test]
+    fn test_modifier_keybindings() {
+        let keybindings = KeybindingsConfig {
+            tag: "ctrl+r".to_string(),
+            export_view: "alt+f".to_string(),
+            save_config: "ctrl+alt+s".to_string(),
+            next_page: "ctrl+down".to_string(),
+            ..KeybindingsConfig::default()
+        };
+        let input_handler = InputHandler::new(keybindings);
+        let key = |code: KeyCode, modifiers: KeyModifiers| {
+            input_handler.handle_key_event(KeyEvent::new(code, modifiers))
+        };
+
+        assert_eq!(
+            key(KeyCode::Char('r'), KeyModifiers::CONTROL),
+            UserAction::Tag
+        );
+        assert_eq!(
+            key(KeyCode::Char('f'), KeyModifiers::ALT),
+            UserAction::ExportView
+        );
+        assert_eq!(
+            key(
+                KeyCode::Char('s'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ),
+            UserAction::SaveConfig
+        );
+        assert_eq!(
+            key(KeyCode::Down, KeyModifiers::CONTROL),
+            UserAction::NextPage
+        );
+
+        // 修饰键必须完全一致
+        assert_eq!(
+            key(KeyCode::Char('r'), KeyModifiers::NONE),
+            UserAction::None
+        );
+        assert_eq!(
+            key(KeyCode::Char('f'), KeyModifiers::CONTROL),
+            UserAction::None
+        );
+        assert_eq!(
+            key(KeyCode::Char('q'), KeyModifiers::CONTROL),
+            UserAction::None
+        );
+        // 不带修饰键的绑定和方向键不受影响，Shift不参与匹配
+        assert_eq!(
+            key(KeyCode::Char('q'), KeyModifiers::NONE),
+            UserAction::Quit
+        );
+        assert_eq!(
+            key(KeyCode::Char('I'), KeyModifiers::SHIFT),
+            UserAction::ToggleIgnore
+        );
+        assert_eq!(key(KeyCode::Down, KeyModifiers::NONE), UserAction::MoveDown);
+    }
+
     #[test]
     fn test_custom_keybindings() {
         let keybindings = KeybindingsConfig {
@@ -176,6 +384,26 @@ mod tests {
             switch_view: "t".to_string(),
             scroll_detail_up: "p".to_string(),
             scroll_detail_down: "n".to_string(),
+            diff_previous: "b".to_string(),
+            scroll_detail_left: "<".to_string(),
+            scroll_detail_right: ">".to_string(),
+            tag: "m".to_string(),
+            toggle_hidden_params: "g".to_string(),
+            select_all: "A".to_string(),
+            invert_selection: "I".to_string(),
+            grow_panel: "=".to_string(),
+            shrink_panel: "_".to_string(),
+            export_view: "E".to_string(),
+            toggle_ignore: "N".to_string(),
+            save_config: "W".to_string(),
+            copy_group_flags: "C".to_string(),
+            next_page: "J".to_string(),
+            previous_page: "K".to_string(),
+            info: "O".to_string(),
+            export_group_diff: "G".to_string(),
+            pin_version: "F".to_string(),
+            next_similar_group: "L".to_string(),
+            toggle_case_sensitivity: "Y".to_string(),
         };
 
         let input_handler = InputHandler::new(keybindings);
@@ -194,4 +422,20 @@ mod tests {
             UserAction::MoveDown
         );
     }
+
+    #[test]
+    fn test_action_map_covers_bindings() {
+        let keybindings = KeybindingsConfig::default();
+        // 除switch_view外，每个绑定都对应一个操作
+        let unmapped: Vec<&str> = keybindings
+            .bindings()
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| UserAction::from_binding_name(name).is_none())
+            .collect();
+        assert_eq!(unmapped, vec!["switch_view"]);
+
+        let action_map = InputHandler::build_action_map(&keybindings);
+        assert_eq!(action_map.len(), keybindings.bindings().len() - 1);
+    }
 }
@@ -1,16 +1,37 @@
-use crate::tui::utils::{calculate_list_layout, extract_version_names, parse_color};
+use crate::tui::utils::{
+    UNDER_REPLICATED_COLOR, calculate_list_layout, extract_version_names, max_horizontal_offset,
+    metric_ranks, navigate_index, parse_color, rank_to_color, scroll_horizontal, scroll_percentage,
+    version_display_name,
+};
+use crate::models::{ColorConfig, TuiConfig};
 use crate::tui::{App, UserAction};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 
 /// TUI渲染器，负责处理所有UI渲染逻辑
 pub struct Renderer;
 
+/// 版本列表中的一项
+struct VersionListItem<'a> {
+    name: &'a str,               // 显示名称
+    marked: bool,                // 是否被多选
+    metric_color: Option<Color>, // 重复次数不足的标记颜色或指标渐变颜色
+}
+
+/// 版本列表的排列和滚动参数
+struct VersionListLayout {
+    cols: usize,            // 列数
+    visible_rows: usize,    // 可见行数
+    scroll_offset: usize,   // 滚动偏移（行）
+    spacing: usize,         // 列间距
+    max_name_length: usize, // 名称填充宽度
+}
+
 impl Renderer {
     pub fn new() -> Self {
         Self
@@ -18,7 +39,7 @@ impl Renderer {
 
     /// 从app结构体中读取数据并渲染
     pub fn draw(&self, f: &mut Frame, app: &mut App) {
-        let version_panel_proportion = app.state.config.tui.version_panel_proportion.min(90).max(10);
+        let version_panel_proportion = app.version_panel_proportion;
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -30,12 +51,109 @@ impl Renderer {
         
         self.draw_version_list(f, app, chunks[0]);
         self.draw_version_details(f, app, chunks[1]);
+        self.draw_popup(f, app);
+        self.draw_tag_input(f, app);
+        self.draw_ignore_input(f, app);
+    }
+
+    /// 绘制备注输入框，显示在界面底部
+    fn draw_tag_input(&self, f: &mut Frame, app: &App) {
+        let Some(input) = &app.tag_input else {
+            return;
+        };
+        let version_name = app
+            .get_selected_version()
+            .map(|version| self.extract_version_name(version))
+            .unwrap_or_default();
+
+        self.draw_input_box(
+            f,
+            app,
+            input,
+            format!(
+                "Tag {} (Enter to save, Esc to cancel, empty to remove)",
+                version_name
+            ),
+        );
+    }
+
+    /// 绘制忽略参数名输入框，显示在界面底部
+    fn draw_ignore_input(&self, f: &mut Frame, app: &App) {
+        let Some(input) = &app.ignore_input else {
+            return;
+        };
+        self.draw_input_box(
+            f,
+            app,
+            input,
+            "Toggle ignored parameter (Enter to apply, Esc to cancel)".to_string(),
+        );
+    }
+
+    /// 在界面底部绘制单行文本输入框，边框使用高亮颜色
+    fn draw_input_box(&self, f: &mut Frame, app: &App, input: &str, title: String) {
+        let colors = app.state.config.tui.resolved_colors();
+        let area = f.area();
+        let height = 3.min(area.height);
+        let input_area = Rect {
+            x: area.x,
+            y: area.y + area.height - height,
+            width: area.width,
+            height,
+        };
+
+        let content = Paragraph::new(format!("{}_", input)).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(parse_color(&colors.highlight))),
+        );
+
+        f.render_widget(Clear, input_area);
+        f.render_widget(content, input_area);
+    }
+
+    /// 绘制弹出窗口，覆盖在主界面中央，边框使用高亮颜色
+    fn draw_popup(&self, f: &mut Frame, app: &App) {
+        let Some(popup) = &app.popup else {
+            return;
+        };
+        let colors = app.state.config.tui.resolved_colors();
+
+        let area = f.area();
+        let width = (area.width * 3 / 5).max(20).min(area.width);
+        let height = ((popup.lines.len() + 2) as u16)
+            .min(area.height * 4 / 5)
+            .max(3)
+            .min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let content = Paragraph::new(popup.lines.join("\n"))
+            .block(
+                Block::default()
+                    .title(popup.title.as_str())
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(parse_color(&colors.highlight))),
+            )
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(Clear, popup_area);
+        f.render_widget(content, popup_area);
     }
 
     /// 绘制版本列表
     fn draw_version_list(&self, f: &mut Frame, app: &mut App, area: Rect) {
-        let versions = &app.state.all_versions;
-        
+        // 版本过多时只显示包含当前选中版本的一页
+        let page = app.displayed_version_range();
+        let hidden_count = app.hidden_version_count();
+        let versions = &app.state.all_versions[page.clone()];
+        let colors = app.state.config.tui.resolved_colors();
+
         // 处理空版本列表情况
         if versions.is_empty() {
             let empty_list = Paragraph::new("No versions found")
@@ -43,62 +161,122 @@ impl Renderer {
                     Block::default()
                         .title("Version List")
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Cyan)),
+                        .border_style(Style::default().fg(parse_color(&colors.border))),
                 )
                 .alignment(Alignment::Center);
             f.render_widget(empty_list, area);
             return;
         }
 
-        let version_names: Vec<String> = extract_version_names(versions);
+        // 开启折叠显示时同一实验组的多个版本合并为一行
+        let rows = app.version_list_rows(page.clone());
+        let page_names = extract_version_names(versions, &app.state.config.tui);
+        // 有备注的版本在名称后加`*`标记
+        let version_names: Vec<String> = rows
+            .iter()
+            .map(|row| match &row.label {
+                Some(label) => label.clone(),
+                None => {
+                    let name = &page_names[row.version_index - page.start];
+                    let version = &app.state.all_versions[row.version_index];
+                    match app.tags.get(version.version_num) {
+                        Some(_) => format!("{}*", name),
+                        None => name.clone(),
+                    }
+                }
+            })
+            .collect();
+        let selected_flags: Vec<bool> = rows
+            .iter()
+            .map(|row| {
+                let version = &app.state.all_versions[row.version_index];
+                app.selected_versions.contains(&version.version_num)
+            })
+            .collect();
+        // 配置了指标时按名次从最佳到最差渐变着色
+        let metric_colors: Vec<Option<Color>> = match &app.state.config.tui.sort_metric {
+            Some(metric) => {
+                // 名次在全部版本中计算，翻页后颜色保持一致
+                let (ranks, count) = metric_ranks(
+                    &app.state.all_versions,
+                    metric,
+                    app.state.config.metrics.higher_is_better(metric),
+                );
+                rows.iter()
+                    .map(|row| ranks[row.version_index].map(|rank| rank_to_color(rank, count)))
+                    .collect()
+            }
+            None => vec![None; rows.len()],
+        };
+        // 所属实验组重复次数不足的版本以橙色标记，优先于指标颜色
+        let under_replicated = app.under_replicated_versions();
+        let metric_colors: Vec<Option<Color>> = metric_colors
+            .into_iter()
+            .zip(&rows)
+            .map(|(color, row)| {
+                let version = &app.state.all_versions[row.version_index];
+                if under_replicated.contains(&version.version_num) {
+                    Some(UNDER_REPLICATED_COLOR)
+                } else {
+                    color
+                }
+            })
+            .collect();
         let max_name_length = version_names
             .iter()
             .map(|name| name.len())
             .max()
             .unwrap_or(1);
         let num_names = version_names.len().max(1);
-        let (cols, spacing) =
-            calculate_list_layout(max_name_length, num_names, area.width.saturating_sub(2));
+        let tui_config = &app.state.config.tui;
+        let (cols, spacing) = calculate_list_layout(
+            max_name_length,
+            num_names,
+            area.width.saturating_sub(2),
+            tui_config.version_list_max_columns,
+            tui_config.version_list_min_column_width,
+        );
+        // 名称按列宽填充，不足最小列宽时补齐空格
+        let max_name_length = max_name_length.max(tui_config.version_list_min_column_width);
             
         if app.columns != cols {
             app.columns = cols;
         }
 
-        // 更新好列数后处理用户动作
+        // 更新好列数后处理用户动作，选中索引为当前选中版本所在的行
         let action = app.last_user_action;
-        let mut selected_version_index = app.selected_version_index;
-        match action {
-            UserAction::MoveUp => {
-                if selected_version_index != 0 {
-                    selected_version_index = selected_version_index.saturating_sub(cols);
-                    app.reset_detail_scroll();
-                }
-                app.last_user_action = UserAction::None;
-            },
-            UserAction::MoveDown => {
-                if selected_version_index != versions.len() - 1 {
-                    selected_version_index = selected_version_index.saturating_add(cols).min(versions.len() - 1);
-                    app.reset_detail_scroll();
-                }
-                app.last_user_action = UserAction::None;
-            },
-            UserAction::MoveLeft => {
-                if selected_version_index != 0 {
-                    selected_version_index = selected_version_index.saturating_sub(1);
-                    app.reset_detail_scroll();
-                }
-                app.last_user_action = UserAction::None;
-            },
-            UserAction::MoveRight => {
-                if selected_version_index != versions.len() - 1 {
-                    selected_version_index = selected_version_index.saturating_add(1).min(versions.len() - 1);
-                    app.reset_detail_scroll();
-                }
-                app.last_user_action = UserAction::None;
+        let mut selected_version_index = rows
+            .iter()
+            .position(|row| row.member_indices.contains(&app.selected_version_index))
+            .unwrap_or(0);
+        if matches!(
+            action,
+            UserAction::MoveUp
+                | UserAction::MoveDown
+                | UserAction::MoveLeft
+                | UserAction::MoveRight
+        ) {
+            let new_index = navigate_index(
+                selected_version_index,
+                action,
+                cols,
+                rows.len(),
+                app.state.config.tui.wrap_navigation,
+            );
+            if new_index != selected_version_index {
+                selected_version_index = new_index;
+                app.reset_detail_scroll();
             }
-            _ => {}
+            app.last_user_action = UserAction::None;
+        }
+        // 移动到新的行时选中该行代表的版本，折叠行内已选中的版本保持不变
+        let selected_row = &rows[selected_version_index];
+        if !selected_row
+            .member_indices
+            .contains(&app.selected_version_index)
+        {
+            app.selected_version_index = selected_row.version_index;
         }
-        app.selected_version_index = selected_version_index;
 
         let (visible_rows, total_rows, scroll_offset) = self.calculate_scroll_info(
             num_names,
@@ -108,24 +286,45 @@ impl Renderer {
             app.version_list_scroll_offset,
         );
         app.version_list_scroll_offset = scroll_offset;
-        let lines = self.build_version_list_lines(
-            &version_names,
+        let items: Vec<VersionListItem> = version_names
+            .iter()
+            .zip(&selected_flags)
+            .zip(&metric_colors)
+            .map(|((name, &marked), &metric_color)| VersionListItem {
+                name,
+                marked,
+                metric_color,
+            })
+            .collect();
+        let layout = VersionListLayout {
             cols,
             visible_rows,
             scroll_offset,
-            selected_version_index,
             spacing,
             max_name_length,
-        );
+        };
+        let lines = self.build_version_list_lines(&items, selected_version_index, &layout, &colors);
 
         let title = self.generate_list_title(total_rows, visible_rows, scroll_offset);
+        let mut block = Block::default()
+            .title(title)
+            .title_top(Line::from(format!(" {} ", app.case_mode_label())).right_aligned())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(parse_color(&colors.border)));
+        if hidden_count > 0 {
+            let keybindings = &app.state.config.keybindings;
+            block = block.title_bottom(format!(
+                " Showing {}-{} of {} ({} hidden, {}/{}: next/previous page) ",
+                page.start + 1,
+                page.end,
+                app.state.all_versions.len(),
+                hidden_count,
+                keybindings.next_page,
+                keybindings.previous_page
+            ));
+        }
         let version_list = Paragraph::new(lines)
-            .block(
-                Block::default()
-                    .title(title)
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
-            )
+            .block(block)
             .alignment(Alignment::Left);
 
         f.render_widget(version_list, area);
@@ -154,23 +353,21 @@ impl Renderer {
         (visible_rows, total_rows, scroll_offset)
     }
 
-    /// 构建版本列表行
+    /// 构建版本列表行，只生成`layout`中可见的行
     fn build_version_list_lines(
         &self,
-        version_names: &[String],
-        cols: usize,
-        visible_rows: usize,
-        scroll_offset: usize,
+        items: &[VersionListItem],
         selected_index: usize,
-        spacing: usize,
-        max_name_length: usize,
-    ) -> Vec<Line> {
+        layout: &VersionListLayout,
+        colors: &ColorConfig,
+    ) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
-        let total_versions = version_names.len();
+        let cols = layout.cols;
+        let total_versions = items.len();
         let total_rows = (total_versions + cols - 1) / cols;
 
-        for display_row in 0..visible_rows {
-            let actual_row = scroll_offset + display_row;
+        for display_row in 0..layout.visible_rows {
+            let actual_row = layout.scroll_offset + display_row;
             if actual_row >= total_rows {
                 break;
             }
@@ -182,13 +379,19 @@ impl Renderer {
                     break;
                 }
 
-                let version_name = &version_names[index];
-                let style = self.get_version_style(index == selected_index);
-                let formatted_name = format!("{:width$}", version_name, width = max_name_length);
+                let item = &items[index];
+                let style = self.get_version_style(
+                    index == selected_index,
+                    item.marked,
+                    item.metric_color,
+                    colors,
+                );
+                let formatted_name =
+                    format!("{:width$}", item.name, width = layout.max_name_length);
                 row_spans.push(Span::styled(formatted_name, style));
 
                 if col < cols - 1 && index < total_versions - 1 {
-                    row_spans.push(Span::raw(" ".repeat(spacing)));
+                    row_spans.push(Span::raw(" ".repeat(layout.spacing)));
                 }
             }
             lines.push(Line::from(row_spans));
@@ -197,14 +400,23 @@ impl Renderer {
         lines
     }
 
-    /// 获取版本样式
-    fn get_version_style(&self, is_selected: bool) -> Style {
+    /// 获取版本样式，当前版本使用`selected`颜色，多选中的版本使用`same_experiment`颜色，
+    /// 其余版本使用重复次数不足的标记颜色或指标渐变颜色（如果有），否则使用`text`颜色
+    fn get_version_style(
+        &self,
+        is_selected: bool,
+        is_marked: bool,
+        metric_color: Option<Color>,
+        colors: &ColorConfig,
+    ) -> Style {
         if is_selected {
             Style::default()
-                .fg(Color::Yellow)
+                .fg(parse_color(&colors.selected))
                 .add_modifier(Modifier::BOLD)
+        } else if is_marked {
+            Style::default().fg(parse_color(&colors.same_experiment))
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(metric_color.unwrap_or_else(|| parse_color(&colors.text)))
         }
     }
 
@@ -216,63 +428,128 @@ impl Renderer {
         scroll_offset: usize,
     ) -> String {
         if total_rows > visible_rows {
-            let percentage =
-                self.calculate_scroll_percentage(total_rows, visible_rows, scroll_offset);
+            let percentage = scroll_percentage(scroll_offset, total_rows, visible_rows);
             format!("Version List [{}%]", percentage)
         } else {
             "Version List".to_string()
         }
     }
 
-    /// 计算滚动百分比
-    fn calculate_scroll_percentage(
-        &self,
-        total_rows: usize,
-        visible_rows: usize,
-        scroll_offset: usize,
-    ) -> u32 {
-        if total_rows > 0 && total_rows > visible_rows {
-            ((scroll_offset as f64 / (total_rows - visible_rows.min(total_rows)) as f64) * 100.0)
-                as u32
-        } else {
-            0
-        }
-    }
-
     /// 绘制版本详情面板
     fn draw_version_details(&self, f: &mut Frame, app: &mut App, area: Rect) {
         app.smart_update_detail_content_cache();
         let content = self.get_detail_content(app);
 
+        // 固定了其他版本时，左侧显示固定版本的详情，右侧显示当前版本
+        let (pinned_area, area) = if app.shows_pinned_details() {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(area);
+            (Some(columns[0]), columns[1])
+        } else {
+            (None, area)
+        };
+
         let action = app.last_user_action;
         let mut detail_scroll_offset = app.detail_scroll_offset;
+        let max_scroll_offset = content.len().saturating_sub(area.height as usize);
         match action {
             UserAction::ScrollDetailUp => {
                 detail_scroll_offset = detail_scroll_offset.saturating_sub(1);
                 app.last_user_action = UserAction::None;
             },
             UserAction::ScrollDetailDown => {
-                detail_scroll_offset = detail_scroll_offset.saturating_add(1).min(content.len() - area.height as usize);
+                detail_scroll_offset = detail_scroll_offset.saturating_add(1).min(max_scroll_offset);
                 app.last_user_action = UserAction::None;
             },
             _ => {}
         }
         app.detail_scroll_offset = detail_scroll_offset;
-        let scroll_percentage = (detail_scroll_offset as f32 / (content.len() - area.height as usize) as f32 * 100.0) as usize;
 
-        let title = self.generate_detail_title(app, scroll_percentage);
+        // 水平滚动只在关闭自动换行时有效
+        let max_h_offset = if app.state.config.tui.detail_wrap {
+            0
+        } else {
+            max_horizontal_offset(&content, area.width.saturating_sub(2) as usize)
+        };
+        app.detail_h_scroll_offset =
+            scroll_horizontal(app.detail_h_scroll_offset, action, max_h_offset);
+        if matches!(
+            action,
+            UserAction::ScrollDetailLeft | UserAction::ScrollDetailRight
+        ) {
+            app.last_user_action = UserAction::None;
+        }
+        let percentage =
+            scroll_percentage(detail_scroll_offset, content.len(), area.height as usize);
+
+        let title = self.generate_detail_title(app, percentage);
 
-        let details = Paragraph::new(content.join("\n"))
+        let details = self.build_detail_paragraph(
+            content.join("\n"),
+            title,
+            (
+                app.detail_scroll_offset as u16,
+                app.detail_h_scroll_offset as u16,
+            ),
+            &app.state.config.tui,
+        );
+
+        f.render_widget(details, area);
+        if let Some(pinned_area) = pinned_area {
+            self.draw_pinned_details(f, app, pinned_area);
+        }
+    }
+
+    /// 绘制固定版本的详情，与当前版本使用相同的滚动位置，便于逐行对照
+    fn draw_pinned_details(&self, f: &mut Frame, app: &App, area: Rect) {
+        let Some(version) = app.get_pinned_version() else {
+            return;
+        };
+        let content: Vec<String> = app
+            .pinned_content_cache
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let title = format!("Pinned - {}", self.extract_version_name(version));
+        let details = self.build_detail_paragraph(
+            content.join("\n"),
+            title,
+            (
+                app.detail_scroll_offset as u16,
+                app.detail_h_scroll_offset as u16,
+            ),
+            &app.state.config.tui,
+        );
+        f.render_widget(details, area);
+    }
+
+    /// 构建详情面板组件，按配置决定边框颜色、是否自动换行及换行时是否去除行首空白
+    fn build_detail_paragraph(
+        &self,
+        content: String,
+        title: String,
+        scroll: (u16, u16),
+        tui_config: &TuiConfig,
+    ) -> Paragraph<'static> {
+        let border = parse_color(&tui_config.resolved_colors().border);
+        let paragraph = Paragraph::new(content)
             .block(
                 Block::default()
                     .title(title)
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(Style::default().fg(border)),
             )
-            .scroll((app.detail_scroll_offset as u16, 0))
-            .wrap(Wrap { trim: true });
+            .scroll(scroll);
 
-        f.render_widget(details, area);
+        if tui_config.detail_wrap {
+            paragraph.wrap(Wrap {
+                trim: tui_config.detail_trim,
+            })
+        } else {
+            paragraph
+        }
     }
 
     /// 获取详情内容
@@ -287,22 +564,151 @@ impl Renderer {
     }
 
     /// 生成详情面板标题
-    fn generate_detail_title(&self, app: &App, scroll_percentage: usize) -> String {
+    fn generate_detail_title(&self, app: &App, scroll_percentage: u32) -> String {
         if let Some(version) = app.get_current_version() {
-            self.extract_version_name(version)
-                .map(|name| format!("Details - {} [{}%]", name, scroll_percentage))
-                .unwrap_or_else(|| "Details - Unknown".to_string())
+            format!(
+                "Details - {} [{}%]",
+                self.extract_version_name(version),
+                scroll_percentage
+            )
         } else {
             "Details".to_string()
         }
     }
 
-    /// 提取版本名称
-    fn extract_version_name(&self, version: &crate::models::models::VersionData) -> Option<String> {
-        version
-            .path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .map(|s| s.to_string())
+    /// 提取版本名称，路径中没有目录名时使用`version_N`
+    fn extract_version_name(&self, version: &crate::models::models::VersionData) -> String {
+        version_display_name(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(tui_config: &TuiConfig) -> Paragraph<'static> {
+        Renderer::new().build_detail_paragraph(
+            "  indented\nline".to_string(),
+            "Details".to_string(),
+            (1, 0),
+            tui_config,
+        )
+    }
+
+    fn unwrapped() -> Paragraph<'static> {
+        Paragraph::new("  indented\nline")
+            .block(
+                Block::default()
+                    .title("Details")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((1, 0))
+    }
+
+    #[test]
+    fn test_detail_paragraph_wrap_config() {
+        let mut tui_config = TuiConfig::default();
+
+        // 默认：换行并去除行首空白
+        assert_eq!(build(&tui_config), unwrapped().wrap(Wrap { trim: true }));
+
+        // 换行但保留缩进
+        tui_config.detail_trim = false;
+        assert_eq!(build(&tui_config), unwrapped().wrap(Wrap { trim: false }));
+
+        // 不换行时trim不生效
+        tui_config.detail_wrap = false;
+        assert_eq!(build(&tui_config), unwrapped());
+        tui_config.detail_trim = true;
+        assert_eq!(build(&tui_config), unwrapped());
+    }
+
+    #[test]
+    fn test_detail_paragraph_theme_border() {
+        let mut tui_config = TuiConfig {
+            color_theme: "light".to_string(),
+            ..TuiConfig::default()
+        };
+        let expected = Paragraph::new("  indented\nline")
+            .block(
+                Block::default()
+                    .title("Details")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .scroll((1, 0))
+            .wrap(Wrap { trim: true });
+        assert_eq!(build(&tui_config), expected);
+
+        // 显式配置的颜色优先于主题
+        tui_config.colors.border = Some("red".to_string());
+        assert_ne!(build(&tui_config), expected);
+    }
+
+    #[test]
+    fn test_build_version_list_lines_scrolled() {
+        let names = ["v1", "v2", "v3", "v4", "v5"];
+        let items: Vec<VersionListItem> = names
+            .iter()
+            .map(|name| VersionListItem {
+                name,
+                marked: false,
+                metric_color: None,
+            })
+            .collect();
+        let layout = VersionListLayout {
+            cols: 2,
+            visible_rows: 2,
+            scroll_offset: 1,
+            spacing: 1,
+            max_name_length: 3,
+        };
+        let lines = Renderer::new().build_version_list_lines(
+            &items,
+            2,
+            &layout,
+            &TuiConfig::default().resolved_colors(),
+        );
+        // 跳过第一行，最后一行只有一项且不补列间距
+        let text: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        assert_eq!(text, vec!["v3  v4 ", "v5 "]);
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_version_style_uses_theme_colors() {
+        let renderer = Renderer::new();
+        let colors = crate::models::config::theme_colors("light");
+        let style = renderer.get_version_style(true, false, None, &colors);
+        assert_eq!(style.fg, Some(Color::Blue));
+        assert_eq!(
+            renderer.get_version_style(false, true, None, &colors).fg,
+            Some(Color::Green)
+        );
+        assert_eq!(
+            renderer.get_version_style(false, false, None, &colors).fg,
+            Some(Color::Black)
+        );
+        assert_eq!(
+            renderer
+                .get_version_style(false, false, Some(Color::Red), &colors)
+                .fg,
+            Some(Color::Red)
+        );
+    }
+
+    #[test]
+    fn test_extract_version_name_fallback() {
+        let mut version = crate::models::VersionData {
+            path: std::path::PathBuf::from("logs/run_a"),
+            ..crate::models::VersionData::for_test(3)
+        };
+        let renderer = Renderer::new();
+        assert_eq!(renderer.extract_version_name(&version), "run_a");
+
+        // 路径中没有目录名时使用版本号
+        version.path = std::path::PathBuf::from("logs/..");
+        assert_eq!(renderer.extract_version_name(&version), "version_3");
     }
 }
@@ -0,0 +1,259 @@
+use crate::experiment_grouping::create_version_data_list;
+use crate::file_utils::find_hparams_files;
+use crate::metrics::{VersionMetrics, load_version_metrics, monitored_metric};
+use crate::models::{Config, VersionData};
+use crate::tui::Event;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// 版本目录及其重新读取的指标
+pub type MetricsUpdate = (PathBuf, VersionMetrics);
+
+/// 后台定期重新扫描日志目录，把新出现的版本和更新的指标发送到事件循环
+pub struct Rescanner {
+    scan_dir: String,                                     // 扫描的目录，启动后保持不变
+    config: Config,                                       // 解析新版本使用的配置
+    known_dirs: HashSet<PathBuf>,                         // 已经成功解析的版本目录，不再重复解析
+    metrics_mtimes: HashMap<PathBuf, Option<SystemTime>>, // 已知版本的指标文件修改时间，变化时重新读取
+    monitors: HashMap<PathBuf, String>, // 已知版本监控的指标名，重新读取指标时一并更新最佳记录
+    reported_warnings: HashSet<String>, // 已经发送过的警告，重试失败的文件时不重复提示
+    interval: Duration,                 // 两次扫描之间的间隔
+}
+
+impl Rescanner {
+    /// 创建重新扫描器，`known_files`为启动时已经找到的hparams文件，
+    /// `known_versions`为其中加载成功的版本，用于确定各版本监控的指标
+    ///
+    /// 配置的`rescan_interval_ms`为0时返回None
+    pub fn new(
+        scan_dir: &str,
+        config: &Config,
+        known_files: &[PathBuf],
+        known_versions: &[VersionData],
+    ) -> Option<Self> {
+        if config.tui.rescan_interval_ms == 0 {
+            return None;
+        }
+        let mut rescanner = Self {
+            scan_dir: scan_dir.to_string(),
+            config: config.clone(),
+            known_dirs: HashSet::new(),
+            metrics_mtimes: HashMap::new(),
+            monitors: HashMap::new(),
+            reported_warnings: HashSet::new(),
+            interval: Duration::from_millis(config.tui.rescan_interval_ms),
+        };
+        for dir in known_files.iter().filter_map(|file| file.parent()) {
+            rescanner.mark_known(dir);
+        }
+        rescanner.record_monitors(known_versions);
+        Some(rescanner)
+    }
+
+    /// 在后台线程中循环扫描，发现新版本时发送`Event::NewVersions`，
+    /// 已知版本的指标文件变化时发送`Event::MetricsUpdated`，新的警告通过`Event::Warnings`发送
+    ///
+    /// 后台线程不直接打印任何内容，避免破坏TUI界面；事件循环退出、发送失败时线程随之结束
+    pub fn spawn(mut self, tx: mpsc::Sender<Event>) {
+        thread::spawn(move || {
+            loop {
+                thread::sleep(self.interval);
+                let mut warnings = Vec::new();
+                // 扫描失败（如目录暂时不可读）时等待下一次扫描
+                match self.scan_new_versions() {
+                    Ok((versions, scan_warnings)) => {
+                        warnings.extend(scan_warnings);
+                        if !versions.is_empty() && tx.send(Event::NewVersions(versions)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => warnings.push(format!("Failed to rescan {}: {:#}", self.scan_dir, e)),
+                }
+                let (updates, metrics_warnings) = self.refresh_metrics();
+                warnings.extend(metrics_warnings);
+                if !updates.is_empty() && tx.send(Event::MetricsUpdated(updates)).is_err() {
+                    return;
+                }
+                let warnings = self.unreported(warnings);
+                if !warnings.is_empty() && tx.send(Event::Warnings(warnings)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// 查找并解析尚未成功解析过的版本目录，返回新版本和加载时的警告
+    ///
+    /// 只有解析成功的目录才被记为已处理，仍在写入中、暂时无法解析的文件在下一次扫描时重试
+    fn scan_new_versions(&mut self) -> Result<(Vec<VersionData>, Vec<String>)> {
+        let new_files: Vec<PathBuf> = find_hparams_files(
+            &self.scan_dir,
+            &self.config.general.hparams_file_candidates(),
+            &self.config.general.ignore_dir_patterns,
+        )?
+        .into_iter()
+        .filter(|file| {
+            file.parent()
+                .is_some_and(|dir| !self.known_dirs.contains(dir))
+        })
+        .collect();
+        if new_files.is_empty() {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let (versions, _, load_report) = create_version_data_list(&self.config, &new_files)?;
+        let failed: HashSet<&PathBuf> = load_report.failed_files.iter().collect();
+        for dir in new_files
+            .iter()
+            .filter(|file| !failed.contains(file))
+            .filter_map(|file| file.parent())
+        {
+            self.mark_known(dir);
+        }
+        self.record_monitors(&versions);
+        Ok((versions, load_report.warnings))
+    }
+
+    /// 重新读取修改时间发生变化的指标文件，返回更新后的指标和读取失败的警告
+    fn refresh_metrics(&mut self) -> (Vec<MetricsUpdate>, Vec<String>) {
+        let mut updates = Vec::new();
+        let mut warnings = Vec::new();
+        for (dir, last_mtime) in &mut self.metrics_mtimes {
+            let mtime = metrics_mtime(dir, &self.config);
+            if mtime == *last_mtime {
+                continue;
+            }
+            *last_mtime = mtime;
+            let monitor = self.monitors.get(dir).map(String::as_str);
+            match load_version_metrics(dir, &self.config.metrics, monitor) {
+                Ok(metrics) => updates.push((dir.clone(), metrics)),
+                Err(e) => warnings.push(format!("{:#}", e)),
+            }
+        }
+        (updates, warnings)
+    }
+
+    /// 记录解析成功的版本目录及其指标文件的当前修改时间
+    fn mark_known(&mut self, dir: &Path) {
+        self.metrics_mtimes
+            .insert(dir.to_path_buf(), metrics_mtime(dir, &self.config));
+        self.known_dirs.insert(dir.to_path_buf());
+    }
+
+    /// 记录版本监控的指标名，没有监控参数的版本不记录
+    fn record_monitors(&mut self, versions: &[VersionData]) {
+        for version in versions {
+            if let Some(monitor) = monitored_metric(&version.full_hparams, &self.config.metrics) {
+                self.monitors
+                    .insert(version.path.clone(), monitor.to_string());
+            }
+        }
+    }
+
+    /// 过滤掉已经发送过的警告
+    fn unreported(&mut self, warnings: Vec<String>) -> Vec<String> {
+        warnings
+            .into_iter()
+            .filter(|warning| self.reported_warnings.insert(warning.clone()))
+            .collect()
+    }
+}
+
+/// 版本目录下指标文件的修改时间，文件不存在时为None
+fn metrics_mtime(dir: &Path, config: &Config) -> Option<SystemTime> {
+    std::fs::metadata(dir.join(&config.metrics.metrics_file))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::write_version_fixtures;
+
+    #[test]
+    fn test_scan_new_versions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let log_dir = temp_dir.path().to_string_lossy().into_owned();
+        let write_version = |version: u32| {
+            let content = format!("lr: 0.0{}\n", version);
+            write_version_fixtures(temp_dir.path(), &[(version, content.as_str())]).remove(0)
+        };
+
+        let mut config = Config::builder().log_dir(&log_dir).build();
+        assert!(Rescanner::new(&log_dir, &config, &[], &[]).is_none());
+
+        config.tui.rescan_interval_ms = 1000;
+        let known = write_version(1);
+        let mut rescanner = Rescanner::new(&log_dir, &config, &[known], &[]).unwrap();
+        assert!(rescanner.scan_new_versions().unwrap().0.is_empty());
+
+        // 只返回新出现的版本，同一个版本不会被再次返回
+        write_version(2);
+        let (versions, warnings) = rescanner.scan_new_versions().unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].version_num, 2);
+        assert!(warnings.is_empty());
+        assert!(rescanner.scan_new_versions().unwrap().0.is_empty());
+
+        // 写了一半的文件解析失败时不记为已处理，写完后的下一次扫描重新解析
+        let partial = temp_dir.path().join("version_3/hparams.yaml");
+        std::fs::create_dir_all(partial.parent().unwrap()).unwrap();
+        std::fs::write(&partial, "lr: [0.01").unwrap();
+        let (versions, warnings) = rescanner.scan_new_versions().unwrap();
+        assert!(versions.is_empty());
+        assert_eq!(warnings.len(), 1);
+        // 同一个警告只发送一次
+        assert_eq!(rescanner.unreported(warnings.clone()), warnings);
+        assert!(rescanner.unreported(warnings).is_empty());
+        write_version(3);
+        let (versions, _) = rescanner.scan_new_versions().unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].version_num, 3);
+    }
+
+    #[test]
+    fn test_refresh_metrics() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let log_dir = temp_dir.path().to_string_lossy().into_owned();
+        let files = write_version_fixtures(
+            temp_dir.path(),
+            &[(1, "lr: 0.01\ncall_back_monitor: val_loss\n")],
+        );
+        let version_dir = files[0].parent().unwrap().to_path_buf();
+        let mut config = Config::builder().log_dir(&log_dir).build();
+        config.tui.rescan_interval_ms = 1000;
+        let (versions, _, _) = create_version_data_list(&config, &files).unwrap();
+        let mut rescanner = Rescanner::new(&log_dir, &config, &files, &versions).unwrap();
+        assert!(rescanner.refresh_metrics().0.is_empty());
+
+        // 训练过程中写出的指标文件在下一次扫描时被重新读取
+        let metrics_file = version_dir.join(&config.metrics.metrics_file);
+        std::fs::write(&metrics_file, "epoch,val_loss\n0,0.5\n").unwrap();
+        let (updates, warnings) = rescanner.refresh_metrics();
+        assert!(warnings.is_empty());
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].0, version_dir);
+        assert!(updates[0].1.metrics.contains_key("val_loss"));
+        assert_eq!(updates[0].1.best_epoch.as_ref().unwrap().epoch, Some(0));
+        // 文件未变化时不重复读取
+        assert!(rescanner.refresh_metrics().0.is_empty());
+
+        std::fs::write(&metrics_file, "epoch,val_loss\n0,0.5\n1,0.3\n").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&metrics_file)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        let (updates, _) = rescanner.refresh_metrics();
+        assert_eq!(updates.len(), 1);
+        // 监控指标的最佳记录随指标文件一起更新
+        let best = updates[0].1.best_epoch.as_ref().unwrap();
+        assert_eq!((best.epoch, best.value), (Some(1), 0.3));
+    }
+}
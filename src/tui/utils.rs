@@ -1,22 +1,227 @@
+use crate::models::{
+    BasicParameterValue, Config, DetailSortMode, ParameterValue, TuiConfig, VersionData,
+    VersionSortMode,
+};
+use crate::tui::UserAction;
 use ratatui::style::Color;
+use ratatui::text::Line;
+use std::cmp::Ordering;
+use std::ops::Range;
 
-/// 从版本数据中提取版本名称
-pub fn extract_version_names(versions: &[crate::models::VersionData]) -> Vec<String> {
+/// 重复次数不足的实验组的标记颜色（橙色）
+pub const UNDER_REPLICATED_COLOR: Color = Color::Rgb(255, 165, 0);
+
+/// 从版本数据中提取版本列表中显示的名称，配置了`version_name_template`时按模板生成
+pub fn extract_version_names(versions: &[VersionData], tui: &TuiConfig) -> Vec<String> {
     versions
         .iter()
-        .map(|version| {
-            version
-                .path
-                .file_name()
-                .and_then(|name| name.to_str())
-                .unwrap_or(&format!("version_{}", version.version_num))
-                .to_string()
+        .map(|version| match &tui.version_name_template {
+            Some(template) => format_version_name(template, version, tui),
+            None => version_display_name(version),
         })
         .collect()
 }
 
-/// 将颜色字符串转换为ratatui的Color
+/// 按模板生成版本名称
+///
+/// `{num}`替换为版本号，`{name}`替换为版本目录名，其他`{参数名}`替换为该版本的超参数值
+/// （浮点数按配置的显示精度格式化），版本没有该参数时替换为`missing`；
+/// 没有闭合的`{`原样保留
+pub fn format_version_name(template: &str, version: &VersionData, tui: &TuiConfig) -> String {
+    let mut name = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        name.push_str(&rest[..start]);
+        let placeholder = &rest[start + 1..start + len];
+        match placeholder {
+            "num" => name.push_str(&version.version_num.to_string()),
+            "name" => name.push_str(&version_display_name(version)),
+            key => match version
+                .full_hparams
+                .get(key)
+                .or_else(|| version.hparams.get(key))
+            {
+                Some(value) => name.push_str(
+                    &value.to_display_string(tui.float_display_precision, tui.trim_trailing_zeros),
+                ),
+                None => name.push_str("missing"),
+            },
+        }
+        rest = &rest[start + len + 1..];
+    }
+    name.push_str(rest);
+    name
+}
+
+/// 获取单个版本的显示名称，即版本目录名；路径中没有目录名时使用`version_N`
+pub fn version_display_name(version: &crate::models::VersionData) -> String {
+    version
+        .path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("version_{}", version.version_num))
+}
+
+/// 按配置的方式排列版本列表（稳定排序）
+///
+/// - `name`：按显示名称的自然顺序，同名版本保持原有顺序
+/// - `recently_modified`：按版本目录的修改时间从新到旧，没有修改时间的版本排在最后，
+///   修改时间相同时按名称排列
+/// - `metric`：按`sort_metric`的值从最佳到最差排列，缺少该指标的版本排在最后，
+///   优化方向由`[metrics]`中的`metric_directions`决定，未配置`sort_metric`时按名称排列
+pub fn sort_versions(versions: &mut [VersionData], config: &Config) {
+    let tui = &config.tui;
+    match (tui.version_sort_mode, tui.sort_metric.as_deref()) {
+        (VersionSortMode::Metric, Some(metric)) => {
+            let descending = config.metrics.higher_is_better(metric);
+            versions.sort_by(|a, b| metric_cmp(a, b, metric, descending))
+        }
+        (VersionSortMode::Name | VersionSortMode::Metric, _) => {
+            versions.sort_by(|a, b| natural_cmp(&version_display_name(a), &version_display_name(b)))
+        }
+        (VersionSortMode::RecentlyModified, _) => versions.sort_by(|a, b| {
+            b.mtime
+                .cmp(&a.mtime)
+                .then_with(|| natural_cmp(&version_display_name(a), &version_display_name(b)))
+        }),
+    }
+}
+
+/// 获取版本的指标值，指标中没有时使用同名的数值型超参数
+pub fn version_metric_value(version: &VersionData, metric: &str) -> Option<f64> {
+    version
+        .metrics
+        .get(metric)
+        .copied()
+        .or_else(|| version.get_f64(metric))
+        .filter(|value| !value.is_nan())
+}
+
+/// 按指标值比较两个版本，最佳的排在前面
+///
+/// `descending`为true时值越大越好；缺少指标的版本排在最后，值相同时按名称的自然顺序排列
+pub fn metric_cmp(a: &VersionData, b: &VersionData, metric: &str, descending: bool) -> Ordering {
+    let by_value = match (
+        version_metric_value(a, metric),
+        version_metric_value(b, metric),
+    ) {
+        (Some(x), Some(y)) if descending => y.total_cmp(&x),
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    by_value.then_with(|| natural_cmp(&version_display_name(a), &version_display_name(b)))
+}
+
+/// 计算每个版本在指标上的名次（0为最佳），值相同的版本名次相同，缺少指标的版本为None
+///
+/// 返回值与`versions`一一对应，同时返回有指标的版本数
+pub fn metric_ranks(
+    versions: &[VersionData],
+    metric: &str,
+    descending: bool,
+) -> (Vec<Option<usize>>, usize) {
+    let values: Vec<Option<f64>> = versions
+        .iter()
+        .map(|version| version_metric_value(version, metric))
+        .collect();
+    let mut sorted: Vec<f64> = values.iter().flatten().copied().collect();
+    sorted.sort_by(|x, y| {
+        if descending {
+            y.total_cmp(x)
+        } else {
+            x.total_cmp(y)
+        }
+    });
+    let ranks = values
+        .iter()
+        .map(|value| {
+            value.map(|value| {
+                sorted.partition_point(|other| {
+                    if descending {
+                        *other > value
+                    } else {
+                        *other < value
+                    }
+                })
+            })
+        })
+        .collect();
+    (ranks, sorted.len())
+}
+
+/// 将名次映射为从最佳（绿色）经黄色到最差（红色）的渐变颜色
+pub fn rank_to_color(rank: usize, count: usize) -> Color {
+    if count <= 1 {
+        return Color::Rgb(0, 255, 0);
+    }
+    let t = rank.min(count - 1) as f64 / (count - 1) as f64;
+    let red = (t * 2.0).min(1.0) * 255.0;
+    let green = ((1.0 - t) * 2.0).min(1.0) * 255.0;
+    Color::Rgb(red.round() as u8, green.round() as u8, 0)
+}
+
+/// 按自然顺序比较两个名称，名称中的连续数字按数值大小比较
+///
+/// 例如`version_2`排在`version_10`之前
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ca), Some(cb)) if ca.is_ascii_digit() && cb.is_ascii_digit() => {
+                let num_a = take_digits(&mut a_chars);
+                let num_b = take_digits(&mut b_chars);
+                // 去掉前导0后先比较位数再比较字典序，避免大数溢出
+                let trimmed_a = num_a.trim_start_matches('0');
+                let trimmed_b = num_b.trim_start_matches('0');
+                let ordering = trimmed_a
+                    .len()
+                    .cmp(&trimmed_b.len())
+                    .then_with(|| trimmed_a.cmp(trimmed_b))
+                    .then_with(|| num_a.len().cmp(&num_b.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(ca), Some(cb)) => {
+                if ca != cb {
+                    return ca.cmp(&cb);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// 从字符迭代器中取出连续的数字字符
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.peek().copied() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        digits.push(c);
+        chars.next();
+    }
+    digits
+}
+
+/// 将颜色字符串（颜色名称或`#rrggbb`）转换为ratatui的Color
 pub fn parse_color(color_str: &str) -> Color {
+    if let Some(color) = parse_hex_color(color_str) {
+        return color;
+    }
     let color_map = get_color_map();
     color_map
         .get(&color_str.to_lowercase())
@@ -24,6 +229,16 @@ pub fn parse_color(color_str: &str) -> Color {
         .unwrap_or(Color::White)
 }
 
+/// 解析`#rrggbb`格式的十六进制颜色
+fn parse_hex_color(color_str: &str) -> Option<Color> {
+    let hex = color_str.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
 /// 获取颜色映射表
 fn get_color_map() -> std::collections::HashMap<String, Color> {
     use std::collections::HashMap;
@@ -73,14 +288,30 @@ fn insert_light_colors(map: &mut std::collections::HashMap<String, Color>) {
 }
 
 /// 计算列表布局参数
+///
+/// # 参数
+/// * `max_name_length` - 最长版本名称的长度
+/// * `num_names` - 版本名称数量
+/// * `area_width` - 可用宽度
+/// * `max_columns` - 最大列数，0表示不限制
+/// * `min_column_width` - 最小列宽，名称不足此宽度时用空格填充
+///
+/// # 返回值
+/// * `(usize, usize)` - 列数和列间距
 pub fn calculate_list_layout(
     max_name_length: usize,
     num_names: usize,
     area_width: u16,
+    max_columns: usize,
+    min_column_width: usize,
 ) -> (usize, usize) {
-    let cols = calculate_optimal_columns(area_width, max_name_length, num_names);
+    let column_width = max_name_length.max(min_column_width);
+    let mut cols = calculate_optimal_columns(area_width, column_width, num_names);
+    if max_columns > 0 {
+        cols = cols.min(max_columns);
+    }
 
-    let spacing = ((area_width as f64 - (cols * max_name_length) as f64) / (cols - 1) as f64)
+    let spacing = ((area_width as f64 - (cols * column_width) as f64) / (cols - 1) as f64)
         .floor()
         .max(1f64) as usize;
 
@@ -108,33 +339,455 @@ fn calculate_optimal_columns(
     best_cols
 }
 
+/// 计算滚动百分比（0-100）
+///
+/// 可滚动范围为`total - visible`，内容未超出可见区域（范围为0）时返回0，
+/// 偏移超出范围时按100处理
+///
+/// # 参数
+/// * `offset` - 当前滚动偏移
+/// * `total` - 内容总行数
+/// * `visible` - 可见行数
+pub fn scroll_percentage(offset: usize, total: usize, visible: usize) -> u32 {
+    let max_offset = total.saturating_sub(visible);
+    if max_offset == 0 {
+        return 0;
+    }
+    (offset.min(max_offset) as f64 / max_offset as f64 * 100.0) as u32
+}
+
+/// 计算详情面板的最大水平滚动偏移，保证最长行的末尾恰好可见
+///
+/// 行宽按终端显示宽度计算，中文等宽字符占两列，与Paragraph水平滚动的单位一致
+///
+/// # 参数
+/// * `lines` - 详情内容行
+/// * `visible_width` - 可见宽度（不含边框）
+pub fn max_horizontal_offset(lines: &[String], visible_width: usize) -> usize {
+    let longest_line = lines
+        .iter()
+        .map(|line| Line::from(line.as_str()).width())
+        .max()
+        .unwrap_or(0);
+    longest_line.saturating_sub(visible_width)
+}
+
+/// 根据水平滚动操作计算新的水平偏移，结果限制在`[0, max_offset]`内
+pub fn scroll_horizontal(offset: usize, action: UserAction, max_offset: usize) -> usize {
+    match action {
+        UserAction::ScrollDetailLeft => offset.saturating_sub(1).min(max_offset),
+        UserAction::ScrollDetailRight => offset.saturating_add(1).min(max_offset),
+        _ => offset.min(max_offset),
+    }
+}
+
+/// 生成差异弹窗中的一行，格式为`key: 旧值 vs 新值`，缺失的值显示为`<missing>`
+///
+/// `show_numeric_delta`为true且两侧都是同类型数值时，在行尾附加差值或倍数；
+/// 两侧都是列表时，在行尾附加元素级别的差异（新增、删除、修改的元素）
+pub fn format_diff_line(
+    key: &str,
+    previous: Option<&ParameterValue>,
+    current: Option<&ParameterValue>,
+    precision: usize,
+    trim_trailing_zeros: bool,
+    show_numeric_delta: bool,
+) -> String {
+    let format_side = |value: Option<&ParameterValue>| {
+        value
+            .map(|v| v.to_display_string(precision, trim_trailing_zeros))
+            .unwrap_or_else(|| "<missing>".to_string())
+    };
+    let line = format!("{}: {} vs {}", key, format_side(previous), format_side(current));
+
+    let delta = match (previous, current) {
+        (Some(ParameterValue::List(previous)), Some(ParameterValue::List(current))) => {
+            let changes: Vec<String> = diff_lists(previous, current)
+                .iter()
+                .map(|change| change.to_display_string(precision, trim_trailing_zeros))
+                .collect();
+            (!changes.is_empty()).then(|| changes.join("; "))
+        }
+        (Some(previous), Some(current)) if show_numeric_delta => {
+            numeric_delta(previous, current, precision, trim_trailing_zeros)
+        }
+        _ => None,
+    };
+    match delta {
+        Some(delta) => format!("{} ({})", line, delta),
+        None => line,
+    }
+}
+
+/// 列表参数中单个元素的变化
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListChange {
+    Added {
+        index: usize, // 新元素在新列表中的位置
+        value: ParameterValue,
+    },
+    Removed {
+        index: usize, // 被删除元素在旧列表中的位置
+        value: ParameterValue,
+    },
+    Changed {
+        index: usize, // 元素在新列表中的位置
+        from: ParameterValue,
+        to: ParameterValue,
+    },
+}
+
+impl ListChange {
+    /// 生成变化的显示文本，如`added [2]: 256`、`changed [1]: 128 -> 256`
+    pub fn to_display_string(&self, precision: usize, trim_trailing_zeros: bool) -> String {
+        let fmt = |value: &ParameterValue| value.to_display_string(precision, trim_trailing_zeros);
+        match self {
+            ListChange::Added { index, value } => format!("added [{}]: {}", index, fmt(value)),
+            ListChange::Removed { index, value } => {
+                format!("removed [{}]: {}", index, fmt(value))
+            }
+            ListChange::Changed { index, from, to } => {
+                format!("changed [{}]: {} -> {}", index, fmt(from), fmt(to))
+            }
+        }
+    }
+}
+
+/// 计算两个列表之间元素级别的差异
+///
+/// 先按最长公共子序列对齐两个列表，未对齐的元素记为删除或新增；
+/// 同一位置上紧邻的删除和新增合并为修改
+pub fn diff_lists(previous: &[ParameterValue], current: &[ParameterValue]) -> Vec<ListChange> {
+    let (n, m) = (previous.len(), current.len());
+
+    // lcs[i][j]为previous[i..]与current[j..]的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if previous[i] == current[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut removed, mut added): (Vec<usize>, Vec<usize>) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && previous[i] == current[j] {
+            flush_list_changes(&mut changes, &mut removed, &mut added, previous, current);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            added.push(j);
+            j += 1;
+        } else {
+            removed.push(i);
+            i += 1;
+        }
+    }
+    flush_list_changes(&mut changes, &mut removed, &mut added, previous, current);
+    changes
+}
+
+/// 将一段连续的删除和新增转换为变化记录，两者一一配对的部分记为修改
+fn flush_list_changes(
+    changes: &mut Vec<ListChange>,
+    removed: &mut Vec<usize>,
+    added: &mut Vec<usize>,
+    previous: &[ParameterValue],
+    current: &[ParameterValue],
+) {
+    let paired = removed.len().min(added.len());
+    for (&from, &to) in removed.iter().zip(added.iter()) {
+        changes.push(ListChange::Changed {
+            index: to,
+            from: previous[from].clone(),
+            to: current[to].clone(),
+        });
+    }
+    for &index in &removed[paired..] {
+        changes.push(ListChange::Removed {
+            index,
+            value: previous[index].clone(),
+        });
+    }
+    for &index in &added[paired..] {
+        changes.push(ListChange::Added {
+            index,
+            value: current[index].clone(),
+        });
+    }
+    removed.clear();
+    added.clear();
+}
+
+/// 计算两个同类型数值之间的变化
+///
+/// 两值同号且相差两倍以上时返回倍数（如`×10`、`×0.5`），否则返回带符号的差值（如`+0.009`）。
+/// 非数值或类型不一致（如Int与Float）时返回None
+pub fn numeric_delta(
+    previous: &ParameterValue,
+    current: &ParameterValue,
+    precision: usize,
+    trim_trailing_zeros: bool,
+) -> Option<String> {
+    let (previous, current, delta) = match (previous, current) {
+        (
+            ParameterValue::Basic(BasicParameterValue::Int(a)),
+            ParameterValue::Basic(BasicParameterValue::Int(b)),
+        ) => {
+            let delta = i128::from(*b) - i128::from(*a);
+            (*a as f64, *b as f64, format!("{:+}", delta))
+        }
+        (
+            ParameterValue::Basic(BasicParameterValue::Float(a)),
+            ParameterValue::Basic(BasicParameterValue::Float(b)),
+        ) => {
+            let delta = b - a;
+            let magnitude = BasicParameterValue::Float(delta.abs())
+                .to_display_string(precision, trim_trailing_zeros);
+            let sign = if delta < 0.0 { '-' } else { '+' };
+            (*a, *b, format!("{}{}", sign, magnitude))
+        }
+        _ => return None,
+    };
+
+    let ratio = current / previous;
+    if previous != 0.0 && ratio.is_finite() && (ratio >= 2.0 || (ratio > 0.0 && ratio <= 0.5)) {
+        let ratio = BasicParameterValue::Float(ratio).to_display_string(3, true);
+        return Some(format!("×{}", ratio));
+    }
+    Some(delta)
+}
+
+/// 计算版本列表当前页显示的版本索引范围
+///
+/// `max_displayed`为0或版本总数不超过上限时显示全部版本；
+/// 否则每页显示`max_displayed`个版本，返回包含`selected_index`的那一页
+pub fn displayed_version_range(
+    total: usize,
+    max_displayed: usize,
+    selected_index: usize,
+) -> Range<usize> {
+    if max_displayed == 0 || total <= max_displayed {
+        return 0..total;
+    }
+    let start = selected_index.min(total - 1) / max_displayed * max_displayed;
+    start..(start + max_displayed).min(total)
+}
+
+/// 生成折叠显示的实验组行文本，如`model=cnn, lr=0.001 (×5: v3,v7,v8,v9,v12)`
+pub fn collapsed_group_label(group_label: &str, version_nums: &[u32]) -> String {
+    let versions: Vec<String> = version_nums.iter().map(|num| format!("v{}", num)).collect();
+    format!(
+        "{} (×{}: {})",
+        group_label,
+        version_nums.len(),
+        versions.join(",")
+    )
+}
+
+/// 根据移动操作计算网格布局中新的选中索引
+///
+/// 版本列表按行优先排列成`cols`列的网格。`wrap`为false时在边界处停止，
+/// 为true时循环移动：左右移动在首尾之间循环，上下移动在同一列的首尾行之间循环
+///
+/// # 参数
+/// * `index` - 当前选中的索引
+/// * `action` - 用户操作，非移动操作时返回原索引
+/// * `cols` - 网格列数
+/// * `len` - 列表总长度
+/// * `wrap` - 是否循环导航
+pub fn navigate_index(
+    index: usize,
+    action: UserAction,
+    cols: usize,
+    len: usize,
+    wrap: bool,
+) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    let cols = cols.max(1);
+    let last = len - 1;
+    let index = index.min(last);
+
+    match action {
+        UserAction::MoveLeft => {
+            if index > 0 {
+                index - 1
+            } else if wrap {
+                last
+            } else {
+                index
+            }
+        }
+        UserAction::MoveRight => {
+            if index < last {
+                index + 1
+            } else if wrap {
+                0
+            } else {
+                index
+            }
+        }
+        UserAction::MoveUp => {
+            if index >= cols {
+                index - cols
+            } else if wrap {
+                // 跳到同一列的最后一个元素
+                let col = index % cols;
+                let bottom = (last / cols) * cols + col;
+                if bottom > last { bottom - cols } else { bottom }
+            } else {
+                0
+            }
+        }
+        UserAction::MoveDown => {
+            if index + cols <= last {
+                index + cols
+            } else if wrap {
+                // 跳到同一列的第一个元素
+                index % cols
+            } else {
+                last
+            }
+        }
+        _ => index,
+    }
+}
+
+/// 将字节数格式化为便于阅读的大小，如 512 B、1.5 KiB、2.0 MiB
+pub fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// 计算导出视图的参数列顺序：取所有版本参数的并集，按详情面板的排序方式排列
+///
+/// 插入顺序模式下，参数按各版本`param_order`中首次出现的位置排列
+pub fn export_columns(
+    versions: &[&crate::models::VersionData],
+    mode: DetailSortMode,
+) -> Vec<String> {
+    let mut param_order: Vec<String> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for version in versions {
+        for key in &version.param_order {
+            if seen.insert(key.clone()) {
+                param_order.push(key.clone());
+            }
+        }
+    }
+
+    let mut first_values: std::collections::HashMap<&String, &ParameterValue> =
+        std::collections::HashMap::new();
+    for version in versions {
+        for (key, value) in &version.hparams {
+            first_values.entry(key).or_insert(value);
+        }
+    }
+    let mut entries: Vec<(&String, &ParameterValue)> = first_values.into_iter().collect();
+    sort_parameter_entries(&mut entries, mode, &param_order);
+    entries.into_iter().map(|(key, _)| key.clone()).collect()
+}
+
+/// 按详情面板的排序方式排列参数
+///
+/// `param_order`为参数在文件中出现的顺序，`insertion`模式下不在其中的参数排在最后并按名称排序
+pub fn sort_parameter_entries(
+    entries: &mut [(&String, &ParameterValue)],
+    mode: DetailSortMode,
+    param_order: &[String],
+) {
+    match mode {
+        DetailSortMode::Alpha => entries.sort_by(|a, b| a.0.cmp(b.0)),
+        DetailSortMode::ByType => entries.sort_by(|a, b| {
+            type_rank(a.1)
+                .cmp(&type_rank(b.1))
+                .then_with(|| a.0.cmp(b.0))
+        }),
+        DetailSortMode::Insertion => {
+            let positions: std::collections::HashMap<&str, usize> = param_order
+                .iter()
+                .enumerate()
+                .map(|(index, key)| (key.as_str(), index))
+                .collect();
+            entries.sort_by(|a, b| {
+                let position =
+                    |key: &String| positions.get(key.as_str()).copied().unwrap_or(usize::MAX);
+                position(a.0).cmp(&position(b.0)).then_with(|| a.0.cmp(b.0))
+            });
+        }
+    }
+}
+
+/// 参数值类型的排列顺序：字符串、数字、布尔值、列表、映射
+fn type_rank(value: &ParameterValue) -> u8 {
+    match value {
+        ParameterValue::Basic(BasicParameterValue::String(_)) => 0,
+        ParameterValue::Basic(BasicParameterValue::Int(_) | BasicParameterValue::Float(_)) => 1,
+        ParameterValue::Basic(BasicParameterValue::Bool(_)) => 2,
+        ParameterValue::List(_) => 3,
+        ParameterValue::Map(_) => 4,
+    }
+}
+
+/// 生成将文本写入系统剪贴板的OSC 52转义序列
+///
+/// 终端收到该序列后设置剪贴板内容，无需依赖平台剪贴板库，SSH会话中同样可用
+pub fn osc52_copy_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// 标准Base64编码（带`=`填充）
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::AppState;
-    use crate::models::config::Config;
+    use crate::models::config::{Config, MetricDirection};
     use crate::tui::app::App;
+    use std::collections::BTreeMap;
 
     fn create_test_app_with_versions() -> App {
         use crate::models::models::VersionData;
-        use std::path::PathBuf;
 
         let versions = vec![
-            VersionData {
-                version_num: 0,
-                path: PathBuf::from("version_0"),
-                hparams: std::collections::HashMap::new(),
-            },
-            VersionData {
-                version_num: 1,
-                path: PathBuf::from("version_1"),
-                hparams: std::collections::HashMap::new(),
-            },
-            VersionData {
-                version_num: 2,
-                path: PathBuf::from("version_2"),
-                hparams: std::collections::HashMap::new(),
-            },
+            VersionData::for_test(0),
+            VersionData::for_test(1),
+            VersionData::for_test(2),
         ];
 
         let app_state = AppState {
@@ -142,10 +795,32 @@ mod tests {
             experiment_groups: vec![],
             config: Config::default(),
             group_common_hparams: std::collections::HashMap::new(),
+            load_report: crate::models::LoadReport::default(),
         };
         App::new(app_state)
     }
 
+    #[test]
+    fn test_natural_cmp() {
+        let mut names = vec!["version_1", "version_10", "version_2"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(names, vec!["version_1", "version_2", "version_10"]);
+
+        // 混合命名
+        let mut names = vec!["run_b2", "run_a10", "run_a9", "baseline"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(names, vec!["baseline", "run_a9", "run_a10", "run_b2"]);
+
+        // 前导0与超出u64范围的数字
+        assert_eq!(natural_cmp("v007", "v7"), Ordering::Greater);
+        assert_eq!(natural_cmp("v7", "v08"), Ordering::Less);
+        assert_eq!(
+            natural_cmp("v99999999999999999999999", "v100000000000000000000000"),
+            Ordering::Less
+        );
+        assert_eq!(natural_cmp("version_3", "version_3"), Ordering::Equal);
+    }
+
     #[test]
     fn test_parse_color() {
         assert_eq!(parse_color("red"), Color::Red);
@@ -157,6 +832,8 @@ mod tests {
         assert_eq!(parse_color("yellow"), Color::Yellow);
         assert_eq!(parse_color("invalid"), Color::White); // 默认颜色
         assert_eq!(parse_color("RED"), Color::Red); // 测试大小写不敏感
+        assert_eq!(parse_color("#268bd2"), Color::Rgb(0x26, 0x8b, 0xd2)); // 十六进制颜色
+        assert_eq!(parse_color("#26"), Color::White);
     }
 
     #[test]
@@ -177,10 +854,324 @@ mod tests {
     #[test]
     fn test_calculate_list_layout() {
         // 测试基本情况
-        assert_eq!(calculate_list_layout(10, 5, 10), (1, 1)); // 10宽度，每个名称10字符 -> 1列，1个空格
-        assert_eq!(calculate_list_layout(10, 5, 80), (7, 1)); // 80宽度，每个名称10字符 -> 7列，1个空格
-        assert_eq!(calculate_list_layout(15, 3, 40), (2, 10)); // 40宽度，每个名称15字符 -> 2列，10个空格
-        assert_eq!(calculate_list_layout(20, 10, 100), (4, 6)); // 100宽度，每个名称20字符 -> 4列，6个空格
+        assert_eq!(calculate_list_layout(10, 5, 10, 0, 0), (1, 1)); // 10宽度，每个名称10字符 -> 1列，1个空格
+        assert_eq!(calculate_list_layout(10, 5, 80, 0, 0), (7, 1)); // 80宽度，每个名称10字符 -> 7列，1个空格
+        assert_eq!(calculate_list_layout(15, 3, 40, 0, 0), (2, 10)); // 40宽度，每个名称15字符 -> 2列，10个空格
+        assert_eq!(calculate_list_layout(20, 10, 100, 0, 0), (4, 6)); // 100宽度，每个名称20字符 -> 4列，6个空格
+    }
+
+    #[test]
+    fn test_calculate_list_layout_max_columns() {
+        // 未限制时为7列，限制为3列
+        assert_eq!(calculate_list_layout(10, 50, 80, 3, 0).0, 3);
+        // 限制大于自动计算的列数时不生效
+        assert_eq!(calculate_list_layout(10, 50, 80, 20, 0).0, 7);
+        // 0表示自动
+        assert_eq!(calculate_list_layout(10, 50, 80, 0, 0).0, 7);
+    }
+
+    #[test]
+    fn test_calculate_list_layout_min_column_width() {
+        // 名称长度10，最小列宽20 -> 按20计算，80宽度只能放3列
+        let (cols, spacing) = calculate_list_layout(10, 50, 80, 0, 20);
+        assert_eq!(cols, 3);
+        assert!(cols * 20 + (cols - 1) * spacing <= 80);
+        // 最小列宽小于名称长度时不生效
+        assert_eq!(
+            calculate_list_layout(10, 50, 80, 0, 5),
+            calculate_list_layout(10, 50, 80, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_scroll_percentage() {
+        // 内容行数等于可见行数，没有可滚动范围
+        assert_eq!(scroll_percentage(0, 10, 10), 0);
+        assert_eq!(scroll_percentage(3, 10, 10), 0);
+        // 单行内容
+        assert_eq!(scroll_percentage(0, 1, 10), 0);
+        assert_eq!(scroll_percentage(0, 1, 1), 0);
+        assert_eq!(scroll_percentage(0, 1, 0), 0);
+        // 内容少于可见行数
+        assert_eq!(scroll_percentage(0, 3, 10), 0);
+        // 正常滚动
+        assert_eq!(scroll_percentage(0, 20, 10), 0);
+        assert_eq!(scroll_percentage(5, 20, 10), 50);
+        assert_eq!(scroll_percentage(10, 20, 10), 100);
+        // 偏移超出范围
+        assert_eq!(scroll_percentage(15, 20, 10), 100);
+    }
+
+    #[test]
+    fn test_displayed_version_range() {
+        // 不限制或未超过上限时显示全部版本
+        assert_eq!(displayed_version_range(10, 0, 7), 0..10);
+        assert_eq!(displayed_version_range(10, 10, 7), 0..10);
+        assert_eq!(displayed_version_range(0, 4, 0), 0..0);
+
+        // 超过上限时显示包含选中版本的一页，最后一页可能不满
+        assert_eq!(displayed_version_range(10, 4, 0), 0..4);
+        assert_eq!(displayed_version_range(10, 4, 5), 4..8);
+        assert_eq!(displayed_version_range(10, 4, 9), 8..10);
+        // 选中索引越界时显示最后一页
+        assert_eq!(displayed_version_range(10, 4, 42), 8..10);
+    }
+
+    #[test]
+    fn test_collapsed_group_label() {
+        assert_eq!(
+            collapsed_group_label("model=cnn, lr=0.001", &[3, 7, 8, 9, 12]),
+            "model=cnn, lr=0.001 (×5: v3,v7,v8,v9,v12)"
+        );
+    }
+
+    #[test]
+    fn test_version_display_name() {
+        let mut version = VersionData {
+            path: std::path::PathBuf::from("logs/run_b"),
+            ..VersionData::for_test(4)
+        };
+        assert_eq!(version_display_name(&version), "run_b");
+
+        // 路径以..结尾时没有目录名，使用版本号
+        version.path = std::path::PathBuf::from("logs/..");
+        assert_eq!(version_display_name(&version), "version_4");
+    }
+
+    #[test]
+    fn test_extract_version_names_empty_path() {
+        let version = VersionData {
+            path: std::path::PathBuf::new(),
+            ..VersionData::for_test(5)
+        };
+        let mut tui = TuiConfig::default();
+        assert_eq!(
+            extract_version_names(std::slice::from_ref(&version), &tui),
+            vec!["version_5"]
+        );
+
+        // 模板中的{name}同样使用回退名称
+        tui.version_name_template = Some("[{name}]".to_string());
+        assert_eq!(
+            extract_version_names(std::slice::from_ref(&version), &tui),
+            vec!["[version_5]"]
+        );
+    }
+
+    #[test]
+    fn test_format_version_name() {
+        let mut full_hparams = std::collections::HashMap::new();
+        full_hparams.insert(
+            "model".to_string(),
+            ParameterValue::Basic(BasicParameterValue::String("cnn".to_string())),
+        );
+        full_hparams.insert(
+            "lr".to_string(),
+            ParameterValue::Basic(BasicParameterValue::Float(0.001)),
+        );
+        let version = VersionData {
+            path: std::path::PathBuf::from("logs/version_7"),
+            full_hparams,
+            ..VersionData::for_test(7)
+        };
+        let mut tui = TuiConfig {
+            trim_trailing_zeros: true,
+            ..TuiConfig::default()
+        };
+
+        assert_eq!(format_version_name("v{num}", &version, &tui), "v7");
+        assert_eq!(
+            format_version_name("{num}: {model} lr={lr}", &version, &tui),
+            "7: cnn lr=0.001"
+        );
+        assert_eq!(format_version_name("{name}", &version, &tui), "version_7");
+        // 缺少的参数显示为missing，没有闭合的花括号原样保留
+        assert_eq!(
+            format_version_name("{num} {seed}", &version, &tui),
+            "7 missing"
+        );
+        assert_eq!(
+            format_version_name("v{num} {model", &version, &tui),
+            "v7 {model"
+        );
+
+        // 未配置模板时使用版本目录名
+        assert_eq!(
+            extract_version_names(std::slice::from_ref(&version), &tui),
+            vec!["version_7"]
+        );
+        tui.version_name_template = Some("run {num}".to_string());
+        assert_eq!(extract_version_names(&[version], &tui), vec!["run 7"]);
+    }
+
+    #[test]
+    fn test_max_horizontal_offset() {
+        let lines = vec!["short".to_string(), "a much longer line".to_string()];
+        assert_eq!(max_horizontal_offset(&lines, 10), 8);
+        // 可见宽度足够时不能滚动
+        assert_eq!(max_horizontal_offset(&lines, 18), 0);
+        assert_eq!(max_horizontal_offset(&lines, 100), 0);
+        assert_eq!(max_horizontal_offset(&[], 10), 0);
+        // 按显示宽度而非字符数或字节数计算长度，中文字符占两列
+        assert_eq!(max_horizontal_offset(&["参数参数".to_string()], 2), 6);
+        assert_eq!(max_horizontal_offset(&["lr: 学习率".to_string()], 4), 6);
+    }
+
+    #[test]
+    fn test_scroll_horizontal_clamp() {
+        assert_eq!(scroll_horizontal(0, UserAction::ScrollDetailLeft, 5), 0);
+        assert_eq!(scroll_horizontal(0, UserAction::ScrollDetailRight, 5), 1);
+        assert_eq!(scroll_horizontal(5, UserAction::ScrollDetailRight, 5), 5);
+        assert_eq!(scroll_horizontal(3, UserAction::ScrollDetailLeft, 5), 2);
+        // 内容变短后偏移被收回到新的最大值
+        assert_eq!(scroll_horizontal(8, UserAction::ScrollDetailLeft, 5), 5);
+        assert_eq!(scroll_horizontal(8, UserAction::None, 5), 5);
+        // 无法滚动
+        assert_eq!(scroll_horizontal(0, UserAction::ScrollDetailRight, 0), 0);
+    }
+
+    #[test]
+    fn test_navigate_index_clamp() {
+        // 3列网格，共8个元素：
+        // 0 1 2
+        // 3 4 5
+        // 6 7
+        assert_eq!(navigate_index(0, UserAction::MoveLeft, 3, 8, false), 0);
+        assert_eq!(navigate_index(7, UserAction::MoveRight, 3, 8, false), 7);
+        assert_eq!(navigate_index(1, UserAction::MoveUp, 3, 8, false), 0);
+        assert_eq!(navigate_index(5, UserAction::MoveDown, 3, 8, false), 7);
+        assert_eq!(navigate_index(7, UserAction::MoveDown, 3, 8, false), 7);
+
+        // 非边界移动
+        assert_eq!(navigate_index(4, UserAction::MoveUp, 3, 8, false), 1);
+        assert_eq!(navigate_index(4, UserAction::MoveDown, 3, 8, false), 7);
+        assert_eq!(navigate_index(4, UserAction::Select, 3, 8, false), 4);
+    }
+
+    #[test]
+    fn test_navigate_index_wrap() {
+        assert_eq!(navigate_index(0, UserAction::MoveLeft, 3, 8, true), 7);
+        assert_eq!(navigate_index(7, UserAction::MoveRight, 3, 8, true), 0);
+
+        // 向上循环到同一列的最后一行
+        assert_eq!(navigate_index(0, UserAction::MoveUp, 3, 8, true), 6);
+        assert_eq!(navigate_index(1, UserAction::MoveUp, 3, 8, true), 7);
+        // 第2列最后一行为空，跳到该列的倒数第二行
+        assert_eq!(navigate_index(2, UserAction::MoveUp, 3, 8, true), 5);
+
+        // 向下循环到同一列的第一行
+        assert_eq!(navigate_index(6, UserAction::MoveDown, 3, 8, true), 0);
+        assert_eq!(navigate_index(5, UserAction::MoveDown, 3, 8, true), 2);
+
+        // 单列列表
+        assert_eq!(navigate_index(0, UserAction::MoveUp, 1, 3, true), 2);
+        assert_eq!(navigate_index(2, UserAction::MoveDown, 1, 3, true), 0);
+
+        // 空列表
+        assert_eq!(navigate_index(0, UserAction::MoveDown, 3, 0, true), 0);
+    }
+
+    #[test]
+    fn test_numeric_delta_formatting() {
+        let float = |v: f64| ParameterValue::Basic(BasicParameterValue::Float(v));
+        let int = |v: i64| ParameterValue::Basic(BasicParameterValue::Int(v));
+
+        // 差异较小时显示带符号的差值
+        assert_eq!(numeric_delta(&float(0.001), &float(0.0015), 6, true).as_deref(), Some("+0.0005"));
+        assert_eq!(numeric_delta(&float(0.5), &float(0.3), 6, true).as_deref(), Some("-0.2"));
+        assert_eq!(numeric_delta(&int(32), &int(48), 6, true).as_deref(), Some("+16"));
+        assert_eq!(numeric_delta(&int(48), &int(32), 6, true).as_deref(), Some("-16"));
+
+        // 相差两倍以上时显示倍数
+        assert_eq!(numeric_delta(&float(0.001), &float(0.01), 6, true).as_deref(), Some("×10"));
+        assert_eq!(numeric_delta(&int(64), &int(32), 6, true).as_deref(), Some("×0.5"));
+        // 旧值为0或符号相反时退回差值
+        assert_eq!(numeric_delta(&int(0), &int(5), 6, true).as_deref(), Some("+5"));
+        assert_eq!(numeric_delta(&float(1.0), &float(-3.0), 6, true).as_deref(), Some("-4"));
+
+        // 非数值或类型不一致时不计算
+        let text = |v: &str| ParameterValue::Basic(BasicParameterValue::String(v.to_string()));
+        assert_eq!(numeric_delta(&int(1), &float(1.5), 6, true), None);
+        assert_eq!(numeric_delta(&text("adam"), &text("sgd"), 6, true), None);
+    }
+
+    #[test]
+    fn test_diff_lists() {
+        let ints = |values: &[i64]| -> Vec<ParameterValue> {
+            values
+                .iter()
+                .map(|v| ParameterValue::Basic(BasicParameterValue::Int(*v)))
+                .collect()
+        };
+        let int = |v: i64| ParameterValue::Basic(BasicParameterValue::Int(v));
+
+        // 新增元素
+        assert_eq!(
+            diff_lists(&ints(&[64, 128]), &ints(&[64, 128, 256])),
+            vec![ListChange::Added { index: 2, value: int(256) }]
+        );
+        // 删除元素
+        assert_eq!(
+            diff_lists(&ints(&[64, 128, 256]), &ints(&[128, 256])),
+            vec![ListChange::Removed { index: 0, value: int(64) }]
+        );
+        // 修改元素
+        assert_eq!(
+            diff_lists(&ints(&[64, 128, 256]), &ints(&[64, 512, 256])),
+            vec![ListChange::Changed { index: 1, from: int(128), to: int(512) }]
+        );
+        // 修改和新增混合
+        assert_eq!(
+            diff_lists(&ints(&[1, 2]), &ints(&[1, 3, 4])),
+            vec![
+                ListChange::Changed { index: 1, from: int(2), to: int(3) },
+                ListChange::Added { index: 2, value: int(4) },
+            ]
+        );
+        assert!(diff_lists(&ints(&[1, 2]), &ints(&[1, 2])).is_empty());
+    }
+
+    #[test]
+    fn test_format_diff_line() {
+        let float = |v: f64| ParameterValue::Basic(BasicParameterValue::Float(v));
+        let text = ParameterValue::Basic(BasicParameterValue::String("adam".to_string()));
+
+        assert_eq!(
+            format_diff_line("lr", Some(&float(0.001)), Some(&float(0.01)), 6, true, true),
+            "lr: 0.001 vs 0.01 (×10)"
+        );
+        assert_eq!(
+            format_diff_line("lr", Some(&float(0.001)), Some(&float(0.01)), 6, true, false),
+            "lr: 0.001 vs 0.01"
+        );
+        assert_eq!(
+            format_diff_line("optimizer", Some(&text), Some(&float(0.1)), 6, true, true),
+            "optimizer: adam vs 0.1"
+        );
+        assert_eq!(
+            format_diff_line("lr", None, Some(&float(0.01)), 6, true, true),
+            "lr: <missing> vs 0.01"
+        );
+
+        let list = |values: &[i64]| {
+            ParameterValue::List(
+                values
+                    .iter()
+                    .map(|v| ParameterValue::Basic(BasicParameterValue::Int(*v)))
+                    .collect(),
+            )
+        };
+        assert_eq!(
+            format_diff_line(
+                "layers",
+                Some(&list(&[64, 128])),
+                Some(&list(&[128, 256])),
+                6,
+                true,
+                false
+            ),
+            "layers: [64, 128] vs [128, 256] (removed [0]: 64; added [1]: 256)"
+        );
     }
 
     /// 创建测试用的App实例
@@ -190,7 +1181,206 @@ mod tests {
             experiment_groups: vec![],
             config: Config::default(),
             group_common_hparams: std::collections::HashMap::new(),
+            load_report: crate::models::LoadReport::default(),
         };
         App::new(app_state)
     }
+
+    #[test]
+    fn test_sort_parameter_entries() {
+        let string = |s: &str| ParameterValue::Basic(BasicParameterValue::String(s.to_string()));
+        let params: Vec<(String, ParameterValue)> = vec![
+            ("model".to_string(), string("resnet")),
+            (
+                "lr".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Float(0.1)),
+            ),
+            (
+                "shuffle".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Bool(true)),
+            ),
+            ("layers".to_string(), ParameterValue::List(vec![])),
+            (
+                "batch_size".to_string(),
+                ParameterValue::Basic(BasicParameterValue::Int(32)),
+            ),
+            ("dataset".to_string(), string("cifar")),
+        ];
+        let param_order: Vec<String> = ["model", "lr", "shuffle", "layers", "batch_size"]
+            .iter()
+            .map(|key| key.to_string())
+            .collect();
+        let sorted_keys = |mode: DetailSortMode| {
+            let mut entries: Vec<(&String, &ParameterValue)> =
+                params.iter().map(|(key, value)| (key, value)).collect();
+            sort_parameter_entries(&mut entries, mode, &param_order);
+            entries
+                .into_iter()
+                .map(|(key, _)| key.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sorted_keys(DetailSortMode::Alpha),
+            vec!["batch_size", "dataset", "layers", "lr", "model", "shuffle"]
+        );
+        assert_eq!(
+            sorted_keys(DetailSortMode::ByType),
+            vec!["dataset", "model", "batch_size", "lr", "shuffle", "layers"]
+        );
+        // 不在文件顺序中的参数排在最后
+        assert_eq!(
+            sorted_keys(DetailSortMode::Insertion),
+            vec!["model", "lr", "shuffle", "layers", "batch_size", "dataset"]
+        );
+    }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(format_file_size(0), "0 B");
+        assert_eq!(format_file_size(1023), "1023 B");
+        assert_eq!(format_file_size(1536), "1.5 KiB");
+        assert_eq!(format_file_size(2 * 1024 * 1024), "2.0 MiB");
+    }
+
+    #[test]
+    fn test_sort_versions_by_mtime() {
+        use std::time::{Duration, SystemTime};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        // version_2最新，version_1最旧，version_3没有修改时间
+        let fixtures = [
+            ("version_1", Some(0)),
+            ("version_2", Some(120)),
+            ("version_10", Some(60)),
+        ];
+        let mut versions: Vec<crate::models::VersionData> = fixtures
+            .iter()
+            .enumerate()
+            .map(|(index, (name, offset))| {
+                let path = temp_dir.path().join(name);
+                std::fs::create_dir(&path).unwrap();
+                if let Some(offset) = offset {
+                    std::fs::File::open(&path)
+                        .unwrap()
+                        .set_modified(base + Duration::from_secs(*offset))
+                        .unwrap();
+                }
+                crate::models::VersionData {
+                    mtime: std::fs::metadata(&path).and_then(|m| m.modified()).ok(),
+                    path,
+                    ..crate::models::VersionData::for_test(index as u32)
+                }
+            })
+            .collect();
+        versions.push(crate::models::VersionData {
+            path: temp_dir.path().join("version_3"),
+            ..crate::models::VersionData::for_test(3)
+        });
+        let names = |versions: &[crate::models::VersionData]| -> Vec<String> {
+            versions.iter().map(version_display_name).collect()
+        };
+
+        let mut config = Config::default();
+        config.tui.version_sort_mode = VersionSortMode::RecentlyModified;
+        sort_versions(&mut versions, &config);
+        assert_eq!(
+            names(&versions),
+            vec!["version_2", "version_10", "version_1", "version_3"]
+        );
+
+        config.tui.version_sort_mode = VersionSortMode::Name;
+        sort_versions(&mut versions, &config);
+        assert_eq!(
+            names(&versions),
+            vec!["version_1", "version_2", "version_3", "version_10"]
+        );
+    }
+
+    fn metric_version(name: &str, val_acc: Option<f64>) -> VersionData {
+        VersionData {
+            path: std::path::PathBuf::from(format!("logs/{}", name)),
+            metrics: val_acc
+                .map(|value| BTreeMap::from([("val_acc".to_string(), value)]))
+                .unwrap_or_default(),
+            ..VersionData::for_test(0)
+        }
+    }
+
+    #[test]
+    fn test_sort_versions_by_metric() {
+        let mut versions = vec![
+            metric_version("version_1", Some(0.7)),
+            metric_version("version_2", None),
+            metric_version("version_3", Some(0.9)),
+            metric_version("version_4", Some(0.8)),
+            metric_version("version_5", Some(0.9)),
+        ];
+        let names = |versions: &[VersionData]| -> Vec<String> {
+            versions.iter().map(version_display_name).collect()
+        };
+        let mut config = Config::default();
+        config.tui.version_sort_mode = VersionSortMode::Metric;
+        config.tui.sort_metric = Some("val_acc".to_string());
+
+        // 按名称推断为越大越好：相同值按名称排列，缺少指标的排在最后
+        sort_versions(&mut versions, &config);
+        assert_eq!(
+            names(&versions),
+            vec!["version_3", "version_5", "version_4", "version_1", "version_2"]
+        );
+
+        // 显式配置为越小越好
+        config
+            .metrics
+            .metric_directions
+            .insert("val_acc".to_string(), MetricDirection::Lower);
+        sort_versions(&mut versions, &config);
+        assert_eq!(
+            names(&versions),
+            vec!["version_1", "version_4", "version_3", "version_5", "version_2"]
+        );
+
+        // 指标不存在时回退到数值型超参数，都不存在时按名称排列
+        config.tui.sort_metric = Some("missing".to_string());
+        sort_versions(&mut versions, &config);
+        assert_eq!(
+            names(&versions),
+            vec!["version_1", "version_2", "version_3", "version_4", "version_5"]
+        );
+    }
+
+    #[test]
+    fn test_metric_rank_colors() {
+        let versions = vec![
+            metric_version("version_1", Some(0.7)),
+            metric_version("version_2", None),
+            metric_version("version_3", Some(0.9)),
+            metric_version("version_4", Some(0.9)),
+            metric_version("version_5", Some(0.8)),
+        ];
+        let (ranks, count) = metric_ranks(&versions, "val_acc", true);
+        assert_eq!(ranks, vec![Some(3), None, Some(0), Some(0), Some(2)]);
+        assert_eq!(count, 4);
+        let (ranks, _) = metric_ranks(&versions, "val_acc", false);
+        assert_eq!(ranks, vec![Some(0), None, Some(2), Some(2), Some(1)]);
+
+        // 最佳为绿色，中间为黄色，最差为红色
+        assert_eq!(rank_to_color(0, 5), Color::Rgb(0, 255, 0));
+        assert_eq!(rank_to_color(2, 5), Color::Rgb(255, 255, 0));
+        assert_eq!(rank_to_color(4, 5), Color::Rgb(255, 0, 0));
+        assert_eq!(rank_to_color(1, 5), Color::Rgb(128, 255, 0));
+        assert_eq!(rank_to_color(0, 1), Color::Rgb(0, 255, 0));
+    }
+
+    #[test]
+    fn test_osc52_copy_sequence() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"--lr 0.1"), "LS1sciAwLjE=");
+        assert_eq!(osc52_copy_sequence("foo"), "\x1b]52;c;Zm9v\x07");
+    }
 }
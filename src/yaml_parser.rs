@@ -1,49 +1,362 @@
 // src/yaml_parser.rs
-use crate::models::{BasicParameterValue, ParameterValue};
+use crate::models::{BasicParameterValue, Config, ParameterValue};
 use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use serde::de::{self, DeserializeSeed, Deserializer, EnumAccess, VariantAccess, Visitor};
 use serde_yaml;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::Read;
 use std::path::Path;
 
+/// 解析选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    pub unordered_map_lists: bool, // 映射列表是否作为无序的结构化值保留，而不是按索引展平
+    pub coerce_boolean_strings: bool, // 是否将yes/no/on/off字符串解析为布尔值
+    pub compute_content_hash: bool, // 是否计算文件内容的SHA-256
+    pub max_file_bytes: Option<u64>, // 文件的最大字节数，超过时拒绝解析，None表示不限制
+    pub warn_duplicate_keys: bool, // 是否容忍YAML映射中的重复键：保留最后一次出现的值并记录警告，false时重复键导致解析失败
+}
+
+impl From<&Config> for ParseOptions {
+    fn from(config: &Config) -> Self {
+        Self {
+            unordered_map_lists: config.grouping.unordered_map_lists,
+            coerce_boolean_strings: config.tolerance.coerce_boolean_strings,
+            compute_content_hash: config.general.compute_content_hash,
+            max_file_bytes: Some(config.general.max_hparams_file_bytes).filter(|&max| max > 0),
+            warn_duplicate_keys: config.general.warn_duplicate_keys,
+        }
+    }
+}
+
 /// 解析单个hparams.yaml文件到HashMap<String, ParameterValue>
+#[cfg(test)]
+pub fn parse_hparams_file(file_path: &Path) -> Result<HashMap<String, ParameterValue>> {
+    parse_hparams_file_with_options(file_path, &ParseOptions::default())
+}
+
 // ————————————————————————————————————————————————————————————————————————
 // 核心解析函数
 // ————————————————————————————————————————————————————————————————————————
-pub fn parse_hparams_file(file_path: &Path) -> Result<HashMap<String, ParameterValue>> {
-    let contents = std::fs::read_to_string(file_path)
+pub fn parse_hparams_file_with_options(
+    file_path: &Path,
+    options: &ParseOptions,
+) -> Result<HashMap<String, ParameterValue>> {
+    parse_hparams_file_detailed(file_path, options).map(|parsed| parsed.hparams)
+}
+
+/// 超参数文件（解压后）超过`max_file_bytes`时的错误，调用方可以据此与其他解析失败区分
+#[derive(Debug)]
+pub struct FileTooLarge {
+    pub path: std::path::PathBuf, // 文件路径
+    pub limit: u64,               // 允许的最大字节数
+}
+
+impl fmt::Display for FileTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Hparams file {} is too large (more than {} bytes, see max_hparams_file_bytes)",
+            self.path.display(),
+            self.limit
+        )
+    }
+}
+
+impl std::error::Error for FileTooLarge {}
+
+/// 最多读取`limit + 1`字节，调用方据此判断内容是否超过限制，而不必把超大的内容整个读入内存
+fn read_limited(reader: impl Read, limit: u64) -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// 解析hparams文件，同时返回参数在文件中出现的顺序，
+/// `compute_content_hash`为true时还返回文件原始内容的SHA-256
+///
+/// `.gz`结尾的文件先解压，再按去掉`.gz`后的文件名选择解析格式，
+/// 内容哈希基于解压后的内容计算，与未压缩的文件一致
+pub fn parse_hparams_file_detailed(
+    file_path: &Path,
+    options: &ParseOptions,
+) -> Result<ParsedHparamsFile> {
+    // 边读取边检查大小，避免把损坏的超大文件或压缩炸弹整个读入内存；
+    // 不预先检查文件元数据，因为检查与读取之间文件可能仍在被写入
+    let limit = options.max_file_bytes.unwrap_or(u64::MAX);
+    let too_large = || FileTooLarge {
+        path: file_path.to_path_buf(),
+        limit,
+    };
+    let mut bytes = std::fs::File::open(file_path)
+        .and_then(|file| read_limited(file, limit))
         .with_context(|| format!("Failed to read hparams file: {}", file_path.display()))?;
+    if bytes.len() as u64 > limit {
+        return Err(too_large().into());
+    }
+    let mut format_path = file_path;
+    let stripped_path;
+    if has_extension(file_path, "gz") {
+        bytes = read_limited(GzDecoder::new(bytes.as_slice()), limit).with_context(|| {
+            format!("Failed to decompress hparams file: {}", file_path.display())
+        })?;
+        if bytes.len() as u64 > limit {
+            return Err(too_large().into());
+        }
+        stripped_path = file_path.with_extension("");
+        format_path = &stripped_path;
+    }
+    let content_hash = options
+        .compute_content_hash
+        .then(|| format!("{:x}", Sha256::digest(&bytes)));
+    let mut warnings = Vec::new();
+    let contents = decode_text(bytes, file_path, &mut warnings);
 
-    let yaml_value: serde_yaml::Value = serde_yaml::from_str(&contents)
-        .with_context(|| format!("Failed to parse YAML from file: {}", file_path.display()))?;
+    // .txt文件按`key = value`格式的纯文本解析，其余按YAML解析
+    if has_extension(format_path, "txt") {
+        let entries = parse_txt_hparams_entries(&contents).with_context(|| {
+            format!(
+                "Failed to parse text hparams from file: {}",
+                file_path.display()
+            )
+        })?;
+        let (hparams, key_order) = collect_ordered_entries(entries);
+        return Ok(ParsedHparamsFile {
+            path: file_path.to_path_buf(),
+            hparams,
+            key_order,
+            content_hash,
+            warnings,
+        });
+    }
 
-    let mut result = HashMap::new();
-    flatten_yaml_value(&yaml_value, &mut result, String::new())?;
-    Ok(result)
+    let yaml_value = if options.warn_duplicate_keys {
+        let mut duplicates = Vec::new();
+        let value = parse_yaml_allowing_duplicates(&contents, &mut duplicates);
+        warnings.extend(duplicates.into_iter().map(|key| {
+            format!(
+                "Duplicate key '{}' in {}, keeping the last value",
+                key,
+                file_path.display()
+            )
+        }));
+        value
+    } else {
+        serde_yaml::from_str(&contents).map_err(anyhow::Error::from)
+    }
+    .with_context(|| format!("Failed to parse YAML from file: {}", file_path.display()))?;
+
+    let mut entries = Vec::new();
+    flatten_yaml_value(&yaml_value, &mut entries, String::new(), options)?;
+    let (hparams, key_order) = collect_ordered_entries(entries);
+    Ok(ParsedHparamsFile {
+        path: file_path.to_path_buf(),
+        hparams,
+        key_order,
+        content_hash,
+        warnings,
+    })
+}
+
+/// 解析YAML文本，映射中出现重复键时不报错，而是保留最后一次出现的值，
+/// 并将重复键的展平路径记录到`duplicates`中
+///
+/// `serde_yaml::Value`的反序列化遇到重复键会直接失败，因此这里用自定义的访问器构建值
+fn parse_yaml_allowing_duplicates(
+    contents: &str,
+    duplicates: &mut Vec<String>,
+) -> Result<serde_yaml::Value> {
+    let seed = DuplicateTrackingSeed {
+        path: String::new(),
+        duplicates,
+    };
+    Ok(seed.deserialize(serde_yaml::Deserializer::from_str(contents))?)
+}
+
+/// 构建YAML值并记录重复键的反序列化种子，`path`为当前值的展平路径
+struct DuplicateTrackingSeed<'a> {
+    path: String,
+    duplicates: &'a mut Vec<String>,
+}
+
+impl DuplicateTrackingSeed<'_> {
+    /// 生成子节点的种子，路径拼接规则与`flatten_yaml_value`一致
+    fn child(&mut self, segment: &str) -> DuplicateTrackingSeed<'_> {
+        DuplicateTrackingSeed {
+            path: if self.path.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{}-{}", self.path, segment)
+            },
+            duplicates: self.duplicates,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for DuplicateTrackingSeed<'_> {
+    type Value = serde_yaml::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DuplicateTrackingSeed<'_> {
+    type Value = serde_yaml::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any YAML value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(serde_yaml::Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(serde_yaml::Value::Number(value.into()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(serde_yaml::Value::Number(value.into()))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(serde_yaml::Value::Number(value.into()))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(serde_yaml::Value::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(serde_yaml::Value::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(serde_yaml::Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(serde_yaml::Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(mut self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut sequence = serde_yaml::Sequence::new();
+        while let Some(item) = seq.next_element_seed(self.child(&sequence.len().to_string()))? {
+            sequence.push(item);
+        }
+        Ok(serde_yaml::Value::Sequence(sequence))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(mut self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut mapping = serde_yaml::Mapping::new();
+        while let Some(key) = map.next_key::<serde_yaml::Value>()? {
+            let segment = mapping_key_to_string(&key).unwrap_or_else(|_| format!("{:?}", key));
+            let child = self.child(&segment);
+            let path = child.path.clone();
+            if mapping.insert(key, map.next_value_seed(child)?).is_some() {
+                self.duplicates.push(path);
+            }
+        }
+        Ok(serde_yaml::Value::Mapping(mapping))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        // 带标签的值（如`!!python/tuple`）
+        let (tag, variant) = data.variant::<String>()?;
+        let value = variant.newtype_variant_seed(self)?;
+        Ok(serde_yaml::Value::Tagged(Box::new(
+            serde_yaml::value::TaggedValue {
+                tag: serde_yaml::value::Tag::new(tag),
+                value,
+            },
+        )))
+    }
+}
+
+/// UTF-8字节顺序标记，Windows上写出的文件常以此开头
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// 将文件内容解码为文本：去掉开头的BOM，非UTF-8字节按替换字符处理并记录警告
+fn decode_text(mut bytes: Vec<u8>, file_path: &Path, warnings: &mut Vec<String>) -> String {
+    if bytes.starts_with(UTF8_BOM) {
+        bytes.drain(..UTF8_BOM.len());
+    }
+    match String::from_utf8(bytes) {
+        Ok(contents) => contents,
+        Err(e) => {
+            warnings.push(format!(
+                "{} is not valid UTF-8, invalid bytes were replaced",
+                file_path.display()
+            ));
+            String::from_utf8_lossy(e.as_bytes()).into_owned()
+        }
+    }
+}
+
+/// 检查文件扩展名（不区分大小写）
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+/// 将按出现顺序排列的参数收集为映射，并返回去重后的键顺序
+///
+/// 同名参数保留最后一次出现的值和第一次出现的位置
+fn collect_ordered_entries(
+    entries: Vec<(String, ParameterValue)>,
+) -> (HashMap<String, ParameterValue>, Vec<String>) {
+    let mut hparams = HashMap::with_capacity(entries.len());
+    let mut key_order = Vec::with_capacity(entries.len());
+    for (key, value) in entries {
+        if hparams.insert(key.clone(), value).is_none() {
+            key_order.push(key);
+        }
+    }
+    (hparams, key_order)
 }
 
 // ————————————————————————————————————————————————————————————————————————
 // 递归扁平化函数：处理路径拼接
+//
+// 空值的处理约定：
+// - `key: []`（空序列）是一个明确的值，保留为`ParameterValue::List(vec![])`，
+//   两个空列表彼此相等、哈希相同
+// - `key:`或`key: null`与未出现该参数等价，直接跳过
 // ————————————————————————————————————————————————————————————————————————
 fn flatten_yaml_value(
     value: &serde_yaml::Value,
-    output: &mut HashMap<String, ParameterValue>,
+    output: &mut Vec<(String, ParameterValue)>,
     path: String,
+    options: &ParseOptions,
 ) -> Result<()> {
     match value {
         serde_yaml::Value::Mapping(map) => {
             for (key, val) in map {
-                let key_str = key
-                    .as_str()
-                    .ok_or_else(|| anyhow::anyhow!("Non-string key in mapping: {:?}", key))?;
+                let key_str = mapping_key_to_string(key)?;
                 let new_path = if path.is_empty() {
-                    key_str.to_string()
+                    key_str
                 } else {
                     format!("{}-{}", path, key_str)
                 };
-                flatten_yaml_value(val, output, new_path)?;
+                flatten_yaml_value(val, output, new_path, options)?;
             }
         }
 
+        serde_yaml::Value::Sequence(seq) if seq.is_empty() => {
+            // 空序列保留为空列表，不受unordered_map_lists等选项影响
+            output.push((path, ParameterValue::List(Vec::new())));
+        }
+
         serde_yaml::Value::Sequence(seq) => {
             // Check if all items are simple (leaf) values
             if seq.iter().all(|v| {
@@ -56,21 +369,30 @@ fn flatten_yaml_value(
             }) {
                 let list: Result<Vec<ParameterValue>> = seq
                     .iter()
-                    .map(|v| base_value_to_parameter_value(v))
+                    .map(|v| base_value_to_parameter_value(v, options))
                     .collect();
-                output.insert(path, ParameterValue::List(list?));
+                output.push((path, ParameterValue::List(list?)));
+            } else if options.unordered_map_lists && seq.iter().all(serde_yaml::Value::is_mapping) {
+                // 映射列表作为无序集合保留：每个元素转换为结构化值，并按规范字符串排序，
+                // 使元素顺序不同的列表得到相同的值
+                let mut list = seq
+                    .iter()
+                    .map(|item| structured_value_to_parameter_value(item, options))
+                    .collect::<Result<Vec<_>>>()?;
+                list.sort_by_cached_key(|item| item.to_simple_string());
+                output.push((path, ParameterValue::List(list)));
             } else {
                 // Recurse into complex list items (e.g., maps or nested lists)
                 for (i, item) in seq.iter().enumerate() {
                     let item_path = format!("{}-{}", path, i);
-                    flatten_yaml_value(item, output, item_path)?;
+                    flatten_yaml_value(item, output, item_path, options)?;
                 }
             }
         }
 
         serde_yaml::Value::Tagged(tagged) => {
             // Ignore YAML tags, just recurse into the value
-            flatten_yaml_value(&tagged.value, output, path)?;
+            flatten_yaml_value(&tagged.value, output, path, options)?;
         }
 
         serde_yaml::Value::Null => {
@@ -79,20 +401,47 @@ fn flatten_yaml_value(
 
         _ => {
             // Leaf node: string, number, bool
-            output.insert(path, base_value_to_parameter_value(value)?);
+            output.push((path, base_value_to_parameter_value(value, options)?));
         }
     }
     Ok(())
 }
 
+/// 将映射的键转换为字符串，数字、布尔值和null等标量键使用其字符串表示
+///
+/// 只有无法表示为参数名的键（如以序列或映射作为键）才返回错误
+fn mapping_key_to_string(key: &serde_yaml::Value) -> Result<String> {
+    match key {
+        serde_yaml::Value::String(s) => Ok(s.clone()),
+        serde_yaml::Value::Number(n) => Ok(n.to_string()),
+        serde_yaml::Value::Bool(b) => Ok(b.to_string()),
+        serde_yaml::Value::Null => Ok("null".to_string()),
+        serde_yaml::Value::Tagged(tagged) => mapping_key_to_string(&tagged.value),
+        serde_yaml::Value::Sequence(_) | serde_yaml::Value::Mapping(_) => Err(anyhow::anyhow!(
+            "Unsupported non-scalar key in mapping: {:?}",
+            key
+        )),
+    }
+}
+
 // ————————————————————————————————————————————————————————————————————————
 // 将 serde_yaml::Value 转换为 ParameterValue（支持递归）
 // ————————————————————————————————————————————————————————————————————————
-fn base_value_to_parameter_value(value: &serde_yaml::Value) -> Result<ParameterValue> {
+fn base_value_to_parameter_value(
+    value: &serde_yaml::Value,
+    options: &ParseOptions,
+) -> Result<ParameterValue> {
     match value {
-        serde_yaml::Value::String(s) => Ok(ParameterValue::Basic(BasicParameterValue::String(
-            s.clone(),
-        ))),
+        serde_yaml::Value::String(s) => {
+            let boolean = options
+                .coerce_boolean_strings
+                .then(|| parse_boolean_token(s))
+                .flatten();
+            Ok(ParameterValue::Basic(match boolean {
+                Some(b) => BasicParameterValue::Bool(b),
+                None => BasicParameterValue::String(s.clone()),
+            }))
+        }
         serde_yaml::Value::Number(n) => {
             if let Some(i) = n.as_i64() {
                 Ok(ParameterValue::Basic(BasicParameterValue::Int(i)))
@@ -107,26 +456,134 @@ fn base_value_to_parameter_value(value: &serde_yaml::Value) -> Result<ParameterV
     }
 }
 
-/// 批量解析多个hparams.yaml文件
+/// 解析YAML 1.1风格的布尔字符串（yes/no/on/off，不区分大小写）
+fn parse_boolean_token(s: &str) -> Option<bool> {
+    match s.to_ascii_lowercase().as_str() {
+        "yes" | "on" => Some(true),
+        "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// 将YAML值完整转换为结构化的ParameterValue，映射转换为Map，序列转换为List，null被跳过
+fn structured_value_to_parameter_value(
+    value: &serde_yaml::Value,
+    options: &ParseOptions,
+) -> Result<ParameterValue> {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            let mut entries = BTreeMap::new();
+            for (key, val) in map {
+                let key_str = mapping_key_to_string(key)?;
+                if !val.is_null() {
+                    entries.insert(key_str, structured_value_to_parameter_value(val, options)?);
+                }
+            }
+            Ok(ParameterValue::Map(entries))
+        }
+        serde_yaml::Value::Sequence(seq) => Ok(ParameterValue::List(
+            seq.iter()
+                .filter(|item| !item.is_null())
+                .map(|item| structured_value_to_parameter_value(item, options))
+                .collect::<Result<_>>()?,
+        )),
+        serde_yaml::Value::Tagged(tagged) => {
+            structured_value_to_parameter_value(&tagged.value, options)
+        }
+        _ => base_value_to_parameter_value(value, options),
+    }
+}
+
+/// 解析纯文本格式的超参数内容
+///
+/// 空行和以`#`开头的注释行会被跳过；键值分隔符取`=`和`:`中先出现的一个；
+/// 值依次尝试解析为Int、Float、Bool，否则作为String（去掉首尾引号）；
+/// 值为空或为`None`/`null`的参数与YAML中的null一样被跳过
+#[cfg(test)]
+fn parse_txt_hparams(contents: &str) -> Result<HashMap<String, ParameterValue>> {
+    parse_txt_hparams_entries(contents).map(|entries| entries.into_iter().collect())
+}
+
+/// 按行的顺序解析纯文本格式的超参数
+fn parse_txt_hparams_entries(contents: &str) -> Result<Vec<(String, ParameterValue)>> {
+    let mut result = Vec::new();
+
+    for (line_num, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let separator = line.find(['=', ':']).ok_or_else(|| {
+            anyhow::anyhow!("Missing '=' or ':' on line {}: {}", line_num + 1, line)
+        })?;
+        let key = line[..separator].trim();
+        let value = line[separator + 1..].trim();
+        if key.is_empty() {
+            anyhow::bail!("Empty key on line {}: {}", line_num + 1, line);
+        }
+        if value.is_empty() || value == "None" || value == "null" {
+            continue;
+        }
+
+        result.push((key.to_string(), infer_text_value(value)));
+    }
+
+    Ok(result)
+}
+
+/// 推断纯文本值的类型
+fn infer_text_value(value: &str) -> ParameterValue {
+    let basic = if let Ok(i) = value.parse::<i64>() {
+        BasicParameterValue::Int(i)
+    } else if let Ok(f) = value.parse::<f64>() {
+        BasicParameterValue::Float(f)
+    } else if value.eq_ignore_ascii_case("true") {
+        BasicParameterValue::Bool(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        BasicParameterValue::Bool(false)
+    } else {
+        let unquoted = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        BasicParameterValue::String(unquoted.to_string())
+    };
+    ParameterValue::Basic(basic)
+}
+
+/// 单个hparams文件的解析结果
+#[derive(Debug)]
+pub struct ParsedHparamsFile {
+    pub path: std::path::PathBuf,                 // 文件路径
+    pub hparams: HashMap<String, ParameterValue>, // 展平后的参数
+    pub key_order: Vec<String>,                   // 展平后的参数名在文件中出现的顺序
+    pub content_hash: Option<String>,             // 文件内容的SHA-256，未启用时为None
+    pub warnings: Vec<String>,                    // 解析过程中产生的警告，如被覆盖的重复键
+}
+
+/// 解析失败的文件及对应的错误
+pub type ParseFailure = (std::path::PathBuf, anyhow::Error);
+
+/// 批量解析多个hparams.yaml文件，解析失败的文件被跳过
+///
+/// 返回解析成功的结果和解析失败的文件；警告和错误都不直接打印，由调用方决定如何报告
 pub fn parse_multiple_hparams_files(
     file_paths: &[std::path::PathBuf],
-) -> Result<Vec<(std::path::PathBuf, HashMap<String, ParameterValue>)>> {
+    options: &ParseOptions,
+) -> Result<(Vec<ParsedHparamsFile>, Vec<ParseFailure>)> {
     let mut results = Vec::new();
+    let mut failures = Vec::new();
 
     for file_path in file_paths {
-        match parse_hparams_file(file_path) {
-            Ok(hparams) => {
-                results.push((file_path.clone(), hparams));
-            }
-            Err(e) => {
-                eprintln!("Warning: Failed to parse {}: {}", file_path.display(), e);
-                // 可以选择跳过错误文件或返回错误
-                // 这里我们选择跳过并继续处理其他文件
-            }
+        match parse_hparams_file_detailed(file_path, options) {
+            Ok(parsed) => results.push(parsed),
+            Err(e) => failures.push((file_path.clone(), e)),
         }
     }
 
-    Ok(results)
+    Ok((results, failures))
 }
 
 #[cfg(test)]
@@ -336,4 +793,478 @@ employees:
         // 清理
         std::fs::remove_file(&test_file).unwrap();
     }
+
+    #[test]
+    fn test_parse_txt_hparams_type_inference() {
+        let contents = r#"
+lr = 1e-3
+batch_size = 32
+offset: -4
+use_amp = True
+shuffle: false
+model = resnet50
+name = "my run"
+tag = 'baseline'
+monitor = val:acc
+"#;
+        let hparams = parse_txt_hparams(contents).unwrap();
+
+        assert_eq!(
+            hparams.get("lr"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Float(1e-3)))
+        );
+        assert_eq!(
+            hparams.get("batch_size"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Int(32)))
+        );
+        assert_eq!(
+            hparams.get("offset"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Int(-4)))
+        );
+        assert_eq!(
+            hparams.get("use_amp"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Bool(true)))
+        );
+        assert_eq!(
+            hparams.get("shuffle"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Bool(false)))
+        );
+        assert_eq!(
+            hparams.get("model"),
+            Some(&ParameterValue::Basic(BasicParameterValue::String(
+                "resnet50".to_string()
+            )))
+        );
+        assert_eq!(
+            hparams.get("name"),
+            Some(&ParameterValue::Basic(BasicParameterValue::String(
+                "my run".to_string()
+            )))
+        );
+        assert_eq!(
+            hparams.get("tag"),
+            Some(&ParameterValue::Basic(BasicParameterValue::String(
+                "baseline".to_string()
+            )))
+        );
+        // 只在第一个分隔符处拆分
+        assert_eq!(
+            hparams.get("monitor"),
+            Some(&ParameterValue::Basic(BasicParameterValue::String(
+                "val:acc".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_parse_txt_hparams_skips_comments_and_blank_lines() {
+        let contents =
+            "# training params\n\n   \nseed = 42\n  # indented comment\nckpt = None\ndropout =\n";
+        let hparams = parse_txt_hparams(contents).unwrap();
+
+        assert_eq!(hparams.len(), 1);
+        assert_eq!(
+            hparams.get("seed"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Int(42)))
+        );
+
+        // 没有分隔符的行报错
+        assert!(parse_txt_hparams("seed 42").is_err());
+    }
+
+    #[test]
+    fn test_parse_hparams_file_dispatches_txt() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_file = temp_dir.path().join("params.txt");
+        std::fs::write(&test_file, "seed = 7\noptimizer: adam\n").unwrap();
+
+        let hparams = parse_hparams_file(&test_file).unwrap();
+        assert_eq!(
+            hparams.get("seed"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Int(7)))
+        );
+        assert_eq!(
+            hparams.get("optimizer"),
+            Some(&ParameterValue::Basic(BasicParameterValue::String(
+                "adam".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_unordered_map_lists() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_file = temp_dir.path().join("hparams.yaml");
+        std::fs::write(
+            &test_file,
+            "employees:\n  - id: 2\n    name: bob\n  - id: 1\n    name: alice\n    manager: null\n",
+        )
+        .unwrap();
+
+        // 默认按索引展平
+        let hparams = parse_hparams_file(&test_file).unwrap();
+        assert_eq!(
+            hparams.get("employees-0-id"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Int(2)))
+        );
+        assert!(!hparams.contains_key("employees"));
+
+        // 启用后作为排序后的结构化列表保留
+        let options = ParseOptions {
+            unordered_map_lists: true,
+            ..ParseOptions::default()
+        };
+        let hparams = parse_hparams_file_with_options(&test_file, &options).unwrap();
+        assert!(!hparams.contains_key("employees-0-id"));
+        let employee = |id: i64, name: &str| {
+            ParameterValue::Map(BTreeMap::from([
+                (
+                    "id".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::Int(id)),
+                ),
+                (
+                    "name".to_string(),
+                    ParameterValue::Basic(BasicParameterValue::String(name.to_string())),
+                ),
+            ]))
+        };
+        assert_eq!(
+            hparams.get("employees"),
+            Some(&ParameterValue::List(vec![
+                employee(1, "alice"),
+                employee(2, "bob")
+            ]))
+        );
+        assert_eq!(
+            hparams["employees"].to_string(),
+            "[{id: 1, name: alice}, {id: 2, name: bob}]"
+        );
+    }
+
+    #[test]
+    fn test_coerce_boolean_strings() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_file = temp_dir.path().join("hparams.yaml");
+        std::fs::write(
+            &test_file,
+            "shuffle: yes\npin_memory: Off\nflags: [on, no]\nname: yesterday\n",
+        )
+        .unwrap();
+
+        let string = |s: &str| ParameterValue::Basic(BasicParameterValue::String(s.to_string()));
+        let boolean = |b: bool| ParameterValue::Basic(BasicParameterValue::Bool(b));
+
+        // 默认保持字符串
+        let hparams = parse_hparams_file(&test_file).unwrap();
+        assert_eq!(hparams.get("shuffle"), Some(&string("yes")));
+        assert_eq!(hparams.get("pin_memory"), Some(&string("Off")));
+
+        // 启用后转换为布尔值
+        let options = ParseOptions {
+            coerce_boolean_strings: true,
+            ..ParseOptions::default()
+        };
+        let hparams = parse_hparams_file_with_options(&test_file, &options).unwrap();
+        assert_eq!(hparams.get("shuffle"), Some(&boolean(true)));
+        assert_eq!(hparams.get("pin_memory"), Some(&boolean(false)));
+        assert_eq!(
+            hparams.get("flags"),
+            Some(&ParameterValue::List(vec![boolean(true), boolean(false)]))
+        );
+        assert_eq!(hparams.get("name"), Some(&string("yesterday")));
+    }
+
+    #[test]
+    fn test_empty_sequences() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_file = temp_dir.path().join("hparams.yaml");
+        std::fs::write(
+            &test_file,
+            "devices: []
+gpus:
+seed: null
+trainer:
+  callbacks: []
+layers: [[], [1]]
+",
+        )
+        .unwrap();
+        let empty = ParameterValue::List(Vec::new());
+
+        for unordered_map_lists in [false, true] {
+            let options = ParseOptions {
+                unordered_map_lists,
+                ..ParseOptions::default()
+            };
+            let hparams = parse_hparams_file_with_options(&test_file, &options).unwrap();
+
+            // 空序列保留为空列表，null与缺失等价
+            assert_eq!(hparams.get("devices"), Some(&empty));
+            assert_eq!(hparams.get("trainer-callbacks"), Some(&empty));
+            assert_eq!(hparams.get("layers-0"), Some(&empty));
+            assert!(!hparams.contains_key("gpus"));
+            assert!(!hparams.contains_key("seed"));
+        }
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_a = temp_dir.path().join("a.yaml");
+        let file_b = temp_dir.path().join("b.yaml");
+        let file_c = temp_dir.path().join("c.yaml");
+        std::fs::write(&file_a, "lr: 0.01\n").unwrap();
+        std::fs::write(&file_b, "lr: 0.01\n").unwrap();
+        // 内容不同但解析结果相同
+        std::fs::write(&file_c, "lr: 0.01 # edited\n").unwrap();
+
+        // 默认不计算
+        let parsed = parse_hparams_file_detailed(&file_a, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.content_hash, None);
+
+        let options = ParseOptions {
+            compute_content_hash: true,
+            ..ParseOptions::default()
+        };
+        let (parsed, _) =
+            parse_multiple_hparams_files(&[file_a, file_b, file_c], &options).unwrap();
+        let hashes: Vec<&str> = parsed
+            .iter()
+            .map(|file| file.content_hash.as_deref().unwrap())
+            .collect();
+        assert_eq!(hashes[0].len(), 64);
+        assert_eq!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+        assert_eq!(parsed[0].hparams, parsed[2].hparams);
+    }
+
+    #[test]
+    fn test_non_string_mapping_keys() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_file = temp_dir.path().join("hparams.yaml");
+        std::fs::write(
+            &test_file,
+            "class_weights:\n  1: 0.5\n  2: 2.0\nflags:\n  true: enabled\n  false: disabled\n",
+        )
+        .unwrap();
+
+        let hparams = parse_hparams_file(&test_file).unwrap();
+        assert_eq!(
+            hparams.get("class_weights-1"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Float(0.5)))
+        );
+        assert_eq!(
+            hparams.get("class_weights-2"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Float(2.0)))
+        );
+        assert_eq!(
+            hparams.get("flags-true"),
+            Some(&ParameterValue::Basic(BasicParameterValue::String(
+                "enabled".to_string()
+            )))
+        );
+        assert!(hparams.contains_key("flags-false"));
+
+        // 以映射作为键时无法表示为参数名，仍然报错
+        std::fs::write(&test_file, "? {a: 1}\n: value\n").unwrap();
+        let err = parse_hparams_file(&test_file).unwrap_err();
+        assert!(err.to_string().contains("non-scalar key"));
+    }
+
+    #[test]
+    fn test_non_finite_floats() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_file = temp_dir.path().join("hparams.yaml");
+        std::fs::write(
+            &test_file,
+            "max_norm: .inf\nmin_norm: -.inf\nthreshold: .nan\n",
+        )
+        .unwrap();
+
+        let hparams = parse_hparams_file(&test_file).unwrap();
+        let float = |key: &str| match hparams.get(key) {
+            Some(ParameterValue::Basic(BasicParameterValue::Float(f))) => *f,
+            other => panic!("{} should be a float, got {:?}", key, other),
+        };
+        assert_eq!(float("max_norm"), f64::INFINITY);
+        assert_eq!(float("min_norm"), f64::NEG_INFINITY);
+        assert!(float("threshold").is_nan());
+    }
+
+    #[test]
+    fn test_max_file_bytes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let small_file = temp_dir.path().join("small.yaml");
+        let large_file = temp_dir.path().join("large.yaml");
+        std::fs::write(&small_file, "lr: 0.001\n").unwrap();
+        let large_contents: String = (0..200).map(|i| format!("param_{}: {}\n", i, i)).collect();
+        std::fs::write(&large_file, &large_contents).unwrap();
+
+        let options = ParseOptions {
+            max_file_bytes: Some(256),
+            ..ParseOptions::default()
+        };
+        assert!(parse_hparams_file_with_options(&small_file, &options).is_ok());
+        let err = parse_hparams_file_with_options(&large_file, &options).unwrap_err();
+        assert!(err.to_string().contains("too large"));
+        assert!(err.downcast_ref::<FileTooLarge>().is_some());
+
+        // 超大文件被跳过，其余文件照常解析
+        let (parsed, failures) =
+            parse_multiple_hparams_files(&[small_file.clone(), large_file.clone()], &options)
+                .unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].path, small_file);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, large_file);
+
+        // 不限制时正常解析
+        let hparams = parse_hparams_file(&large_file).unwrap();
+        assert_eq!(hparams.len(), 200);
+    }
+
+    #[test]
+    fn test_key_order() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let yaml_file = temp_dir.path().join("hparams.yaml");
+        std::fs::write(
+            &yaml_file,
+            "model: resnet\noptimizer:\n  name: adam\n  lr: 0.01\nbatch_size: 32\n",
+        )
+        .unwrap();
+        let parsed = parse_hparams_file_detailed(&yaml_file, &ParseOptions::default()).unwrap();
+        assert_eq!(
+            parsed.key_order,
+            vec!["model", "optimizer-name", "optimizer-lr", "batch_size"]
+        );
+
+        let txt_file = temp_dir.path().join("hparams.txt");
+        std::fs::write(&txt_file, "seed = 1\nlr = 0.1\nseed = 2\n").unwrap();
+        let parsed = parse_hparams_file_detailed(&txt_file, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.key_order, vec!["seed", "lr"]);
+        assert_eq!(
+            parsed.hparams.get("seed"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Int(2)))
+        );
+    }
+
+    #[test]
+    fn test_gzip_hparams_file() {
+        // `gzip -n`压缩的 "model: resnet\nlr: 0.001\nlayers: [64, 128]\n"
+        const COMPRESSED: [u8; 62] = [
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xcb, 0xcd, 0x4f, 0x49,
+            0xcd, 0xb1, 0x52, 0x28, 0x4a, 0x2d, 0xce, 0x4b, 0x2d, 0xe1, 0xca, 0x29, 0xb2, 0x52,
+            0x30, 0xd0, 0x33, 0x30, 0x30, 0xe4, 0xca, 0x49, 0xac, 0x4c, 0x2d, 0x2a, 0xb6, 0x52,
+            0x88, 0x36, 0x33, 0xd1, 0x51, 0x30, 0x34, 0xb2, 0x88, 0xe5, 0x02, 0x00, 0xd7, 0xf9,
+            0x1c, 0x55, 0x2a, 0x00, 0x00, 0x00,
+        ];
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plain_file = temp_dir.path().join("hparams.yaml");
+        let gz_file = temp_dir.path().join("hparams.yaml.gz");
+        std::fs::write(&plain_file, "model: resnet\nlr: 0.001\nlayers: [64, 128]\n").unwrap();
+        std::fs::write(&gz_file, COMPRESSED).unwrap();
+
+        let options = ParseOptions {
+            compute_content_hash: true,
+            ..ParseOptions::default()
+        };
+        let plain = parse_hparams_file_detailed(&plain_file, &options).unwrap();
+        let compressed = parse_hparams_file_detailed(&gz_file, &options).unwrap();
+        assert_eq!(compressed.hparams, plain.hparams);
+        assert_eq!(compressed.key_order, plain.key_order);
+        assert_eq!(compressed.content_hash, plain.content_hash);
+
+        // 解压后超过大小限制时报错，即使压缩文件本身很小
+        let bomb_file = temp_dir.path().join("bomb.yaml.gz");
+        let content = format!("lr: 0.1\n#{}\n", "x".repeat(10_000));
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        std::io::Write::write_all(&mut encoder, content.as_bytes()).unwrap();
+        std::fs::write(&bomb_file, encoder.finish().unwrap()).unwrap();
+        let limited = ParseOptions {
+            max_file_bytes: Some(1024),
+            ..ParseOptions::default()
+        };
+        let err = parse_hparams_file_detailed(&bomb_file, &limited).unwrap_err();
+        assert!(err.downcast_ref::<FileTooLarge>().is_some());
+        assert!(parse_hparams_file(&bomb_file).is_ok());
+
+        // 损坏的压缩文件报错
+        std::fs::write(&gz_file, &COMPRESSED[..30]).unwrap();
+        assert!(parse_hparams_file(&gz_file).is_err());
+    }
+
+    #[test]
+    fn test_bom_and_invalid_utf8() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let yaml_file = temp_dir.path().join("hparams.yaml");
+        std::fs::write(&yaml_file, b"\xEF\xBB\xBFmodel: resnet\nlr: 0.01\n").unwrap();
+        let parsed = parse_hparams_file_detailed(&yaml_file, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.key_order, vec!["model", "lr"]);
+        assert!(parsed.hparams.contains_key("model"));
+
+        let txt_file = temp_dir.path().join("hparams.txt");
+        std::fs::write(&txt_file, b"\xEF\xBB\xBFseed = 1\n").unwrap();
+        let hparams = parse_hparams_file(&txt_file).unwrap();
+        assert!(hparams.contains_key("seed"));
+
+        // 非UTF-8字节被替换，文件其余部分正常解析
+        std::fs::write(&yaml_file, b"model: res\xFFnet\nlr: 0.01\n").unwrap();
+        let parsed = parse_hparams_file_detailed(&yaml_file, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.warnings.len(), 1);
+        assert!(parsed.warnings[0].ends_with("is not valid UTF-8, invalid bytes were replaced"));
+        let hparams = parsed.hparams;
+        assert_eq!(
+            hparams.get("model"),
+            Some(&ParameterValue::Basic(BasicParameterValue::String(
+                "res\u{FFFD}net".to_string()
+            )))
+        );
+        assert!(hparams.contains_key("lr"));
+    }
+
+    #[test]
+    fn test_warn_duplicate_keys() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let yaml_file = temp_dir.path().join("hparams.yaml");
+        std::fs::write(
+            &yaml_file,
+            "lr: 0.1\nbatch_size: 32\nlr: 0.2\noptimizer:\n  name: adam\n  name: sgd\n\
+             shape: !!python/tuple [1, 2]\n",
+        )
+        .unwrap();
+
+        // 默认情况下重复键导致解析失败
+        assert!(parse_hparams_file(&yaml_file).is_err());
+
+        // 启用后保留最后一次出现的值，并为每个重复键记录警告
+        let options = ParseOptions {
+            warn_duplicate_keys: true,
+            ..ParseOptions::default()
+        };
+        let parsed = parse_hparams_file_detailed(&yaml_file, &options).unwrap();
+        assert_eq!(
+            parsed.hparams.get("lr"),
+            Some(&ParameterValue::Basic(BasicParameterValue::Float(0.2)))
+        );
+        assert_eq!(
+            parsed.hparams.get("optimizer-name"),
+            Some(&ParameterValue::Basic(BasicParameterValue::String(
+                "sgd".to_string()
+            )))
+        );
+        assert!(parsed.hparams.contains_key("shape"));
+        assert_eq!(
+            parsed.key_order,
+            vec!["lr", "batch_size", "optimizer-name", "shape"]
+        );
+        assert_eq!(parsed.warnings.len(), 2);
+        assert!(parsed.warnings[0].starts_with("Duplicate key 'lr'"));
+        assert!(parsed.warnings[1].starts_with("Duplicate key 'optimizer-name'"));
+
+        // 没有重复键时不产生警告，结果与默认解析一致
+        std::fs::write(&yaml_file, "lr: 0.1\nlayers: [64, 128]\n").unwrap();
+        let parsed = parse_hparams_file_detailed(&yaml_file, &options).unwrap();
+        assert!(parsed.warnings.is_empty());
+        assert_eq!(parsed.hparams, parse_hparams_file(&yaml_file).unwrap());
+    }
 }